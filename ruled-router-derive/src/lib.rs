@@ -6,15 +6,22 @@
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, Data, DeriveInput, Fields, Lit, Meta};
 
+mod param;
+mod pattern;
 mod query;
+mod query_serde;
 mod querystring;
 mod route;
 mod router_match;
+mod routes;
 
+use param::expand_param_derive;
 use query::expand_query_derive;
+use query_serde::expand_query_serde_derive;
 use querystring::expand_querystring_derive;
 use route::expand_route_derive;
 use router_match::expand_router_match_derive;
+use routes::expand_routes;
 
 /// sub_router attribute macro
 #[proc_macro_attribute]
@@ -28,6 +35,43 @@ pub fn sub_router(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// as a top-level router. For top-level routing, use `RouterMatch` with an enum structure
 /// that contains multiple `RouterData` implementations.
 ///
+/// The route pattern is given with `#[router(pattern = "...")]`; `#[route(pattern = "...")]`
+/// is accepted as an equivalent spelling. The macro is also re-exported as
+/// `ruled_router::Router`, next to the `ruled_router::Router` alias of the trait.
+///
+/// The pattern is validated while expanding the macro: a malformed pattern (such as an
+/// empty parameter name in `"/users/:"`) or a `:param` without a field of the same name
+/// is reported as a compile error on the pattern literal. Likewise, every field that is not
+/// `#[query]` or `#[sub_router]` must match a pattern parameter.
+///
+/// A trailing wildcard segment such as `*rest` in `"/assets/*rest"` binds the remaining
+/// path, slashes included, to the field of the same name (typically a `String`).
+///
+/// An optional segment such as `?:format` binds to an `Option<T>` field: it is `None` when the
+/// segment is absent, and formatting only emits the segment for `Some`.
+///
+/// Tuple structs such as `struct UserRoute(u32);` bind their fields to the pattern
+/// parameters by position, so they must have exactly one field per parameter.
+///
+/// Legacy URL shapes can be accepted with a repeatable `alias`, e.g.
+/// `#[router(pattern = "/users/:id", alias = "/u/:id")]`: `parse` tries the primary pattern
+/// and then each alias in order, while `format` always emits the primary pattern. Every alias
+/// must bind the same parameters as the primary pattern.
+///
+/// Add `std_traits` to the attribute (`#[router(pattern = "...", std_traits)]`) to also
+/// generate `Display` (via `format`) and `FromStr` (via `parse`, with `Err = ParseError`).
+/// It is opt-in so that types implementing those traits manually keep compiling.
+///
+/// A large query type can be attached at the attribute level instead of as a `#[query]` field,
+/// e.g. `#[router(pattern = "/search", query = SearchQuery)]`. `parse` then rejects URLs whose
+/// query string fails `SearchQuery::parse`, and the query is exposed through the generated
+/// `parse_query`, `parse_with_query` and `format_with` methods rather than a field.
+/// It cannot be combined with `#[query]` fields.
+///
+/// A `#[sub_router]` field declared as `Option<T>` or `RouteState<T>` is optional.
+/// A field declared as plain `T` is mandatory: `parse` fails with
+/// `ParseError::InvalidPath` when no sub-route matches, and `format` always appends it.
+///
 /// # Example
 ///
 /// ```rust
@@ -48,7 +92,7 @@ pub fn sub_router(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// //     // ... other routes
 /// // }
 /// ```
-#[proc_macro_derive(RouterData, attributes(router, query, sub_router))]
+#[proc_macro_derive(RouterData, attributes(router, route, query, sub_router))]
 pub fn derive_router(input: TokenStream) -> TokenStream {
   let input = parse_macro_input!(input as DeriveInput);
   expand_route_derive(input).unwrap_or_else(syn::Error::into_compile_error).into()
@@ -56,6 +100,27 @@ pub fn derive_router(input: TokenStream) -> TokenStream {
 
 /// Derive macro for implementing the Query trait
 ///
+/// `#[query(with = "my_mod")]` delegates a field to `my_mod::parse(&str) -> Result<T, ParseError>`
+/// and `my_mod::format(&T) -> String` instead of `FromParam`/`ToParam`, much like serde's `with`.
+///
+/// `#[query(bool_style = "flag")]` on a `bool` field treats a bare key (`?featured`) as `true`
+/// and a missing key as `false`; formatting emits only the key when the field is `true`.
+///
+/// `#[query(max_len = 100)]` and `#[query(range = "1..=100")]` validate single-value or `Option<T>`
+/// fields after parsing; a violation makes `parse` fail with `ParseError::InvalidQuery`, while
+/// a lenient `parse_lenient` records a warning and falls back to the field's default.
+///
+/// `#[query(lenient)]` on the struct generates a field-by-field `parse_lenient` that skips invalid
+/// values with a warning. Every parsed field type must then implement `Default`; without the
+/// attribute the trait's default `parse_lenient` falls back to `Self::default()` as a whole.
+///
+/// `#[query(nested)]` on a field whose type implements `Query` groups its keys as `name[key]`,
+/// while `#[query(flatten)]` merges them into the parent as-is (`page`, `per_page`), so a shared
+/// query struct can be embedded without prefixing its keys.
+///
+/// `#[query(rename_all = "kebab-case")]` on the struct derives every key from the field name in
+/// `kebab-case`, `camelCase` or `SCREAMING_SNAKE_CASE`; a field's own `name` still takes precedence.
+///
 /// # Example
 ///
 /// ```rust
@@ -74,6 +139,43 @@ pub fn derive_query(input: TokenStream) -> TokenStream {
   expand_query_derive(input).unwrap_or_else(syn::Error::into_compile_error).into()
 }
 
+/// Derive macro for implementing the Query trait through serde
+///
+/// `parse` delegates to `serde_urlencoded::from_str` and `format` to
+/// `serde_urlencoded::to_string`, so serde attributes such as `#[serde(rename)]`
+/// and `#[serde(default)]` apply. The type must implement `Serialize` and
+/// `DeserializeOwned`, and the `serde-query` feature of `ruled-router` must be enabled.
+///
+/// `serde_urlencoded` only handles flat key/value pairs and never collects repeated keys
+/// into arrays, so sequence fields like `Vec<String>` are not supported: the derive rejects
+/// them at compile time. Use `QueryDerive` when a parameter can repeat. `from_query_map`
+/// deserializes the already parsed pairs directly with the same value rules.
+///
+/// `Query::format` cannot return an error, so a value `serde_urlencoded` fails to serialize
+/// formats as an empty string in every build profile; call
+/// `ruled_router::serde_query::format` directly to handle the error. `query_keys` follows the
+/// struct's `#[serde(rename_all = "...")]` and each field's `#[serde(rename = "...")]`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ruled_router::QuerySerde;
+///
+/// #[derive(serde::Serialize, serde::Deserialize, QuerySerde)]
+/// struct SearchQuery {
+///     #[serde(rename = "q")]
+///     keyword: String,
+///     page: Option<u32>,
+/// }
+/// ```
+#[proc_macro_derive(QuerySerde)]
+pub fn derive_query_serde(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand_query_serde_derive(input)
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
 /// Derive macro for implementing querystring parsing and formatting
 ///
 /// This macro automatically implements parsing from and formatting to
@@ -107,6 +209,22 @@ pub fn derive_querystring(input: TokenStream) -> TokenStream {
 /// This macro generates implementations for parsing and formatting
 /// nested router structures with automatic prefix extraction.
 ///
+/// When several variants share a prefix, mark a variant with `#[route(prefix = "...")]`
+/// so that it only matches paths starting with that prefix (at a segment boundary).
+/// Variants without the attribute fall back to their `RouterData::pattern()`.
+///
+/// A variant may also wrap another `RouteMatcher` instead of a `RouterData` type. The macro
+/// cannot see which trait the field type implements, so mark such variants with
+/// `#[route(matcher)]`: the wrapped matcher consumes no path of its own, its routes compete
+/// with the other variants, and `patterns()`/`describe()` include its patterns in place.
+///
+/// `try_parse` considers every variant and picks the one whose pattern has the longest
+/// leading literal part (`/users/new` beats `/users/:id`), so declaration order only
+/// matters between equally specific patterns.
+///
+/// `describe()` returns a static `RouteTree` of every variant's pattern and, recursively,
+/// its sub-router variants, independent of any route instance.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -130,7 +248,7 @@ pub fn derive_querystring(input: TokenStream) -> TokenStream {
 ///     Api(ApiRoute),
 /// }
 /// ```
-#[proc_macro_derive(RouterMatch)]
+#[proc_macro_derive(RouterMatch, attributes(route))]
 pub fn derive_router_match(input: TokenStream) -> TokenStream {
   let input = parse_macro_input!(input as DeriveInput);
   expand_router_match_derive(input)
@@ -138,42 +256,163 @@ pub fn derive_router_match(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Derive macro for implementing the FromParam and ToParam traits on fieldless enums
+///
+/// Each unit variant maps to its own name. Use `#[param(rename = "...")]` on a variant
+/// to choose a different value, and `#[param(case_insensitive)]` on the enum to accept
+/// any ASCII casing when parsing. Formatting always emits the declared value.
+/// Unknown values fail with `ParseError::TypeConversion` listing the allowed values.
+///
+/// # Example
+///
+/// ```rust
+/// use ruled_router_derive::Param;
+/// use ruled_router::traits::{FromParam, ToParam};
+///
+/// #[derive(Debug, PartialEq, Param)]
+/// #[param(case_insensitive)]
+/// enum UserRole {
+///     #[param(rename = "admin")]
+///     Admin,
+///     #[param(rename = "user")]
+///     User,
+/// }
+///
+/// assert_eq!(UserRole::from_param("ADMIN").unwrap(), UserRole::Admin);
+/// assert_eq!(UserRole::User.to_param(), "user");
+/// ```
+#[proc_macro_derive(Param, attributes(param))]
+pub fn derive_param(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand_param_derive(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Function-like macro generating a nested route hierarchy from a tree literal
+///
+/// The input is a top-level `RouterMatch` enum followed by its routes. Each route is written as
+/// `"/pattern" => Name(fields) { children }`, where both the field list and the children are
+/// optional. Fields are written exactly like the fields of a hand-written `RouterData` struct,
+/// so `#[query]` attaches a query type. For every route the macro generates:
+///
+/// - a `RouterData` struct `Name` with the given pattern and fields
+/// - when it has children, a `sub_router: Option<NameMatch>` field and a `RouterMatch` enum
+///   `NameMatch` whose variants wrap the child routes, each variant named after its route
+///
+/// All generated items derive `Debug`, `Clone` and `PartialEq` and use the visibility of the
+/// top-level enum; attributes on a route (such as doc comments) are copied to its struct.
+///
+/// # Example
+///
+/// ```rust
+/// use ruled_router::prelude::*;
+/// use ruled_router::routes;
+///
+/// #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+/// struct ListQuery {
+///     page: Option<u32>,
+/// }
+///
+/// routes! {
+///     pub enum AppRoute {
+///         "/" => Home,
+///         "/users" => Users(#[query] query: ListQuery) {
+///             "/:id" => UserDetail(id: u32),
+///         },
+///     }
+/// }
+///
+/// let route = AppRoute::try_parse("/users/42").unwrap();
+/// assert_eq!(
+///     route,
+///     AppRoute::Users(Users {
+///         query: ListQuery::default(),
+///         sub_router: Some(UsersMatch::UserDetail(UserDetail { id: 42 })),
+///     })
+/// );
+/// ```
+#[proc_macro]
+pub fn routes(input: TokenStream) -> TokenStream {
+  expand_routes(input.into()).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Route configuration extracted from `#[router(...)]` / `#[route(...)]`
+struct RouteConfig {
+  pattern: syn::LitStr,
+  /// 额外的别名模式，按声明顺序排列
+  aliases: Vec<syn::LitStr>,
+  std_traits: bool,
+  /// `query = Type` 声明的外部查询类型，不占用结构体字段
+  query_type: Option<syn::Type>,
+}
+
 /// Extract route configuration from router attribute
-fn extract_route_config(input: &DeriveInput) -> syn::Result<(String, Option<String>)> {
+fn extract_route_config(input: &DeriveInput) -> syn::Result<RouteConfig> {
+  let mut pattern = None;
+  let mut aliases = Vec::new();
+  let mut std_traits = false;
+  let mut query_type = None;
+
+  // 配置可以分散在多个属性中，例如 `#[router(pattern = "...")]` 与 `#[router(std_traits)]`
   for attr in &input.attrs {
-    if attr.path().is_ident("router") {
+    // `#[route(...)]` 与 `#[router(...)]` 等价
+    if attr.path().is_ident("router") || attr.path().is_ident("route") {
       if let Meta::List(meta_list) = &attr.meta {
-        let mut pattern = None;
-        let mut query_type = None;
-
         // Parse multiple name-value pairs
         let parser = meta_list.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
 
         for meta in parser {
-          if let Meta::NameValue(name_value) = meta {
+          if let Meta::Path(path) = &meta {
+            if path.is_ident("std_traits") {
+              std_traits = true;
+            }
+          } else if let Meta::NameValue(name_value) = meta {
             if name_value.path.is_ident("pattern") {
               if let syn::Expr::Lit(expr_lit) = &name_value.value {
                 if let Lit::Str(lit_str) = &expr_lit.lit {
-                  pattern = Some(lit_str.value());
+                  pattern = Some(lit_str.clone());
                 }
               }
+            } else if name_value.path.is_ident("alias") {
+              match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                  lit: Lit::Str(lit_str), ..
+                }) => aliases.push(lit_str.clone()),
+                other => return Err(syn::Error::new_spanned(other, "alias expects a string literal")),
+              }
             } else if name_value.path.is_ident("query") {
-              if let syn::Expr::Lit(expr_lit) = &name_value.value {
-                if let Lit::Str(lit_str) = &expr_lit.lit {
-                  query_type = Some(lit_str.value());
+              match &name_value.value {
+                syn::Expr::Path(expr_path) => {
+                  query_type = Some(syn::Type::Path(syn::TypePath {
+                    qself: expr_path.qself.clone(),
+                    path: expr_path.path.clone(),
+                  }))
+                }
+                other => {
+                  return Err(syn::Error::new_spanned(
+                    other,
+                    "query expects a type path, e.g. `query = SearchQuery`",
+                  ))
                 }
               }
             }
           }
         }
-
-        if let Some(pattern) = pattern {
-          return Ok((pattern, query_type));
-        }
       }
     }
   }
-  Err(syn::Error::new_spanned(input, "Missing #[router(pattern = \"...\")]"))
+
+  match pattern {
+    Some(pattern) => Ok(RouteConfig {
+      pattern,
+      aliases,
+      std_traits,
+      query_type,
+    }),
+    None => Err(syn::Error::new_spanned(
+      input,
+      "Missing #[router(pattern = \"...\")] (or #[route(pattern = \"...\")])",
+    )),
+  }
 }
 
 /// Extract field information from struct
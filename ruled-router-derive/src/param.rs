@@ -0,0 +1,163 @@
+//! Implementation of the Param derive macro
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Lit, Meta};
+
+/// 单个枚举变体的参数信息
+struct VariantInfo {
+  ident: syn::Ident,
+  param_name: String,
+}
+
+/// Expand the Param derive macro
+pub fn expand_param_derive(input: DeriveInput) -> syn::Result<TokenStream> {
+  let enum_name = &input.ident;
+  let case_insensitive = extract_case_insensitive(&input.attrs)?;
+  let variants = extract_variants(&input, case_insensitive)?;
+
+  let allowed_values = variants.iter().map(|v| v.param_name.as_str()).collect::<Vec<_>>().join(", ");
+  let enum_name_str = enum_name.to_string();
+
+  // 生成匹配分支
+  let parse_arms = variants.iter().map(|variant| {
+    let ident = &variant.ident;
+    let param_name = &variant.param_name;
+    if case_insensitive {
+      quote! {
+          if param.eq_ignore_ascii_case(#param_name) {
+              return Ok(Self::#ident);
+          }
+      }
+    } else {
+      quote! {
+          if param == #param_name {
+              return Ok(Self::#ident);
+          }
+      }
+    }
+  });
+
+  let format_arms = variants.iter().map(|variant| {
+    let ident = &variant.ident;
+    let param_name = &variant.param_name;
+    quote! { Self::#ident => #param_name.to_string() }
+  });
+
+  let expanded = quote! {
+      impl ::ruled_router::traits::FromParam for #enum_name {
+          fn from_param(param: &str) -> Result<Self, ::ruled_router::error::ParseError> {
+              #(#parse_arms)*
+              Err(::ruled_router::error::ParseError::type_conversion(format!(
+                  "Cannot convert '{}' to {}. Expected one of: {}",
+                  param, #enum_name_str, #allowed_values
+              )))
+          }
+      }
+
+      impl ::ruled_router::traits::ToParam for #enum_name {
+          fn to_param(&self) -> String {
+              match *self {
+                  #(#format_arms),*
+              }
+          }
+      }
+  };
+
+  Ok(expanded)
+}
+
+/// 提取枚举级别的 `#[param(case_insensitive)]`
+fn extract_case_insensitive(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+  let mut case_insensitive = false;
+
+  for attr in attrs {
+    if attr.path().is_ident("param") {
+      let parser = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
+      for meta in parser {
+        match meta {
+          Meta::Path(path) if path.is_ident("case_insensitive") => case_insensitive = true,
+          other => {
+            return Err(syn::Error::new_spanned(
+              other,
+              "Unknown param attribute on enum, expected `case_insensitive`",
+            ))
+          }
+        }
+      }
+    }
+  }
+
+  Ok(case_insensitive)
+}
+
+/// 提取所有单元变体，以及变体上的 `#[param(rename = "...")]`
+fn extract_variants(input: &DeriveInput, case_insensitive: bool) -> syn::Result<Vec<VariantInfo>> {
+  let data_enum = match &input.data {
+    Data::Enum(data_enum) => data_enum,
+    _ => return Err(syn::Error::new_spanned(input, "Param can only be derived for enums")),
+  };
+
+  let mut variants: Vec<VariantInfo> = Vec::new();
+
+  for variant in &data_enum.variants {
+    if !matches!(variant.fields, Fields::Unit) {
+      return Err(syn::Error::new_spanned(
+        variant,
+        "Param can only be derived for enums with unit variants",
+      ));
+    }
+
+    let mut param_name = variant.ident.to_string();
+
+    for attr in &variant.attrs {
+      if attr.path().is_ident("param") {
+        let parser = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
+        for meta in parser {
+          match meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("rename") => {
+              if let syn::Expr::Lit(expr_lit) = &name_value.value {
+                if let Lit::Str(lit_str) = &expr_lit.lit {
+                  if lit_str.value().is_empty() {
+                    return Err(syn::Error::new_spanned(lit_str, "rename must not be empty"));
+                  }
+                  param_name = lit_str.value();
+                  continue;
+                }
+              }
+              return Err(syn::Error::new_spanned(&name_value.value, "rename expects a string literal"));
+            }
+            other => {
+              return Err(syn::Error::new_spanned(
+                other,
+                "Unknown param attribute on variant, expected `rename`",
+              ));
+            }
+          }
+        }
+      }
+    }
+
+    // 大小写不敏感时，仅大小写不同的值同样视为重复
+    let is_duplicate = |v: &&VariantInfo| {
+      if case_insensitive {
+        v.param_name.eq_ignore_ascii_case(&param_name)
+      } else {
+        v.param_name == param_name
+      }
+    };
+    if let Some(existing) = variants.iter().find(is_duplicate) {
+      return Err(syn::Error::new_spanned(
+        &variant.ident,
+        format!("Duplicate param value '{}' (already used by {})", param_name, existing.ident),
+      ));
+    }
+
+    variants.push(VariantInfo {
+      ident: variant.ident.clone(),
+      param_name,
+    });
+  }
+
+  Ok(variants)
+}
@@ -0,0 +1,141 @@
+//! Compile-time validation of route patterns
+//!
+//! Mirrors the rules of `PathParser::parse_pattern` in `ruled-router`, so that a malformed
+//! pattern is reported while expanding the derive instead of on the first `parse` call.
+
+/// 路径模式中参数的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+  /// `:name`、`{name}` 以及带约束的 `:name(regex)`、`{name:regex}`
+  Required,
+  /// `?:name`
+  Optional,
+  /// `*name`
+  Wildcard,
+}
+
+/// 路径模式中的一个参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternParam {
+  pub name: String,
+  pub kind: ParamKind,
+}
+
+/// 解析路径模式中的参数，模式无效时返回错误原因
+///
+/// 错误原因与运行时 `PathParser::new` 返回的信息保持一致
+pub fn parse_pattern_params(pattern: &str) -> Result<Vec<PatternParam>, String> {
+  let mut params = Vec::new();
+
+  for segment in pattern.trim_start_matches('/').split('/').filter(|s| !s.is_empty()) {
+    // 带约束的参数段需要先处理，因为正则中可能出现 "?:" 这样的片段
+    if let Some(name) = parse_constrained_segment(segment)? {
+      params.push(required(name));
+      continue;
+    }
+
+    // 其余含反斜杠转义的段都是字面量
+    if segment.contains('\\') {
+      if segment.replace("\\\\", "").ends_with('\\') {
+        return Err(format!("Pattern segment '{segment}' ends with a dangling escape"));
+      }
+      continue;
+    }
+
+    // 复合段，如 ":id?:format"
+    if segment.starts_with(':') && segment.contains("?:") {
+      let parts: Vec<&str> = segment.split("?:").collect();
+      if parts.len() == 2 {
+        let name = &parts[0][1..];
+        if name.is_empty() {
+          return Err("Parameter must have a name".to_string());
+        }
+        if parts[1].is_empty() {
+          return Err("Optional parameter must have a name".to_string());
+        }
+        params.push(required(name));
+        params.push(PatternParam {
+          name: parts[1].to_string(),
+          kind: ParamKind::Optional,
+        });
+        continue;
+      }
+    }
+
+    if let Some(name) = segment.strip_prefix('*') {
+      if name.is_empty() {
+        return Err("Wildcard segment must have a name".to_string());
+      }
+      params.push(PatternParam {
+        name: name.to_string(),
+        kind: ParamKind::Wildcard,
+      });
+    } else if let Some(name) = segment.strip_prefix("?:") {
+      if name.is_empty() {
+        return Err("Optional parameter must have a name".to_string());
+      }
+      params.push(PatternParam {
+        name: name.to_string(),
+        kind: ParamKind::Optional,
+      });
+    } else if let Some(name) = segment.strip_prefix(':') {
+      if name.is_empty() {
+        return Err("Parameter must have a name".to_string());
+      }
+      params.push(required(name));
+    } else if segment.starts_with('{') && segment.ends_with('}') {
+      let name = &segment[1..segment.len() - 1];
+      if name.is_empty() {
+        return Err("Parameter must have a name".to_string());
+      }
+      params.push(required(name));
+    }
+  }
+
+  Ok(params)
+}
+
+/// 模式中是否包含带正则约束的参数段（需要 `regex` feature）
+///
+/// 只用于已经通过 [`parse_pattern_params`] 校验的模式
+pub fn has_constraints(pattern: &str) -> bool {
+  pattern
+    .trim_start_matches('/')
+    .split('/')
+    .any(|segment| matches!(parse_constrained_segment(segment), Ok(Some(_))))
+}
+
+/// 解析 ":name(regex)" 和 "{name:regex}" 形式的带约束参数段，返回参数名
+///
+/// 正则本身在运行时编译（需要 `regex` feature），这里只检查名称和约束是否为空
+fn parse_constrained_segment(segment: &str) -> Result<Option<&str>, String> {
+  let (name, regex) = if let Some(rest) = segment.strip_prefix(':') {
+    match (rest.find('('), rest.strip_suffix(')')) {
+      (Some(open), Some(inner)) => (&rest[..open], &inner[open + 1..]),
+      _ => return Ok(None),
+    }
+  } else if segment.starts_with('{') && segment.ends_with('}') {
+    match segment[1..segment.len() - 1].split_once(':') {
+      Some((name, regex)) => (name, regex),
+      None => return Ok(None),
+    }
+  } else {
+    return Ok(None);
+  };
+
+  if name.is_empty() {
+    return Err("Parameter must have a name".to_string());
+  }
+  if regex.is_empty() {
+    return Err(format!("Constraint for parameter '{name}' must not be empty"));
+  }
+
+  Ok(Some(name))
+}
+
+fn required(name: &str) -> PatternParam {
+  PatternParam {
+    name: name.to_string(),
+    kind: ParamKind::Required,
+  }
+}
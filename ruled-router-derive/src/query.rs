@@ -1,13 +1,15 @@
 //! Implementation of the Query derive macro
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{Data, DeriveInput, Fields, Lit, Meta, Type, TypePath};
 
 /// Expand the Query derive macro
 pub fn expand_query_derive(input: DeriveInput) -> syn::Result<TokenStream> {
   let struct_name = &input.ident;
-  let fields = extract_query_fields(&input.data)?;
+  let struct_attributes = extract_struct_attributes(&input.attrs)?;
+  let fields = extract_query_fields(&input.data, struct_attributes.rename_all)?;
 
   // 生成解析逻辑
   let parse_fields = generate_parse_fields(&fields)?;
@@ -18,68 +20,319 @@ pub fn expand_query_derive(input: DeriveInput) -> syn::Result<TokenStream> {
   // 生成 from_query_map 解析逻辑
   let from_query_map_fields = generate_from_query_map_fields(&fields)?;
 
-  // 生成 query_keys 方法
-  let query_keys: Vec<_> = fields.iter().map(|field| &field.query_name).collect();
+  // 生成 max_len / range 校验
+  let validate_fields = generate_validate_fields(&fields);
+
+  // 标注了 #[query(lenient)] 时逐字段宽松解析，否则使用 trait 的默认实现
+  let parse_lenient_impl = if struct_attributes.lenient {
+    generate_parse_lenient_impl(&fields)
+  } else {
+    quote! {}
+  };
+
+  // 生成 query_keys 方法，nested 字段只列出分组名，flatten 字段并入子类型的参数名，skip 字段不参与
+  let query_keys = generate_query_keys(&fields);
+
+  // 有分组字段时逐层检查未知参数名
+  let unexpected_query_keys = generate_unexpected_query_keys(&fields);
+
+  // 标注了 #[query(page)] 时实现 Paginated
+  let paginated_impl = generate_paginated_impl(struct_name, &fields)?;
 
   let expanded = quote! {
       impl ::ruled_router::traits::Query for #struct_name {
           fn parse(query: &str) -> Result<Self, ::ruled_router::error::ParseError> {
               let parser = ::ruled_router::parser::QueryParser::new(query)?;
 
-              Ok(Self {
+              let parsed = Self {
                   #(#parse_fields),*
-              })
+              };
+              #(#validate_fields)*
+              Ok(parsed)
           }
 
+          #parse_lenient_impl
+
           fn format(&self) -> String {
-              let mut formatter = ::ruled_router::formatter::QueryFormatter::new();
+              // 按字段声明顺序收集键值对，保证生成的 URL 稳定且易读
+              let mut pairs: Vec<(String, String)> = Vec::new();
 
               #(#format_fields)*
 
-              formatter.format()
+              ::ruled_router::utils::format_query_pairs(&pairs)
           }
 
           fn from_query_map(query_map: &std::collections::HashMap<String, Vec<String>>) -> Result<Self, ::ruled_router::error::ParseError> {
-              Ok(Self {
+              let parsed = Self {
                   #(#from_query_map_fields),*
-              })
+              };
+              #(#validate_fields)*
+              Ok(parsed)
           }
 
           fn to_query_string(&self) -> String {
-              self.format()
+              ::ruled_router::traits::Query::format(self)
           }
 
           fn query_keys() -> Vec<&'static str> {
-              vec![#(#query_keys),*]
+              #query_keys
           }
+
+          #unexpected_query_keys
       }
+
+      #paginated_impl
   };
 
   Ok(expanded)
 }
 
+/// 结构体上 #[query(...)] 属性的解析结果
+struct StructAttributes {
+  rename_all: Option<RenameRule>,
+  /// 生成逐字段的 parse_lenient，要求所有参与解析的字段类型实现 `Default`
+  lenient: bool,
+}
+
 /// 字段信息结构
 struct FieldInfo {
   name: syn::Ident,
   ty: Type,
   query_name: String,
   default_value: Option<String>,
+  /// `Vec<T>` 字段的分隔符，设置后使用单个键的分隔值而不是重复键
+  delimiter: Option<String>,
+  /// 字段本身实现 Query，其参数以 `name[key]` 的形式分组
+  nested: bool,
+  /// 字段本身实现 Query，其参数直接合并到当前层级
+  flatten: bool,
+  /// 字段是 Paginated 使用的页码
+  page: bool,
+  /// 字段不参与查询参数的解析和格式化，解析时使用 `Default::default()`
+  skip: bool,
+  /// 自定义解析/格式化模块，使用 `module::parse` 和 `module::format` 代替 FromParam/ToParam
+  with: Option<syn::Path>,
+  /// `bool_style = "flag"`：只有键名的参数表示 true，缺失表示 false
+  flag: bool,
+  /// 字符串值的最大字符数
+  max_len: Option<usize>,
+  /// 数值的允许范围和原始写法，例如 `1..=100`
+  range: Option<(syn::ExprRange, String)>,
+}
+
+/// 字段上 #[query(...)] 属性的解析结果
+struct QueryAttributes {
+  query_name: String,
+  default_value: Option<String>,
+  delimiter: Option<String>,
+  nested: bool,
+  flatten: bool,
+  page: bool,
+  skip: bool,
+  with: Option<syn::Path>,
+  flag: bool,
+  max_len: Option<usize>,
+  range: Option<(syn::ExprRange, String)>,
+}
+
+/// 结构体级别 `#[query(rename_all = "...")]` 支持的命名规则
+#[derive(Clone, Copy)]
+enum RenameRule {
+  /// `sort_by` -> `sort-by`
+  Kebab,
+  /// `sort_by` -> `sortBy`
+  Camel,
+  /// `sort_by` -> `SORT_BY`
+  ScreamingSnake,
+}
+
+impl RenameRule {
+  fn parse(value: &str) -> Option<Self> {
+    match value {
+      "kebab-case" => Some(RenameRule::Kebab),
+      "camelCase" => Some(RenameRule::Camel),
+      "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnake),
+      _ => None,
+    }
+  }
+
+  /// 把 snake_case 的字段名转换为目标命名规则
+  fn apply(self, field_name: &str) -> String {
+    match self {
+      RenameRule::Kebab => field_name.replace('_', "-"),
+      RenameRule::ScreamingSnake => field_name.to_ascii_uppercase(),
+      RenameRule::Camel => {
+        let mut result = String::with_capacity(field_name.len());
+        let mut capitalize = false;
+        for c in field_name.chars() {
+          if c == '_' {
+            // 开头的下划线不算单词分隔
+            capitalize = !result.is_empty();
+          } else if capitalize {
+            result.push(c.to_ascii_uppercase());
+            capitalize = false;
+          } else {
+            result.push(c);
+          }
+        }
+        result
+      }
+    }
+  }
+}
+
+/// 提取结构体级别的 `#[query(rename_all = "...")]` 和 `#[query(lenient)]`
+fn extract_struct_attributes(attrs: &[syn::Attribute]) -> syn::Result<StructAttributes> {
+  let mut rename_all = None;
+  let mut lenient = false;
+
+  for attr in attrs {
+    if attr.path().is_ident("query") {
+      let parser = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
+      for meta in parser {
+        match meta {
+          Meta::NameValue(name_value) if name_value.path.is_ident("rename_all") => match &name_value.value {
+            syn::Expr::Lit(syn::ExprLit {
+              lit: Lit::Str(lit_str), ..
+            }) => match RenameRule::parse(&lit_str.value()) {
+              Some(rule) => rename_all = Some(rule),
+              None => {
+                return Err(syn::Error::new_spanned(
+                  lit_str,
+                  "Unknown rename_all rule, expected one of: kebab-case, camelCase, SCREAMING_SNAKE_CASE",
+                ))
+              }
+            },
+            other => return Err(syn::Error::new_spanned(other, "rename_all expects a string literal")),
+          },
+          Meta::Path(path) if path.is_ident("lenient") => lenient = true,
+          other => {
+            return Err(syn::Error::new_spanned(
+              other,
+              "Unknown query attribute on struct, expected `rename_all` or `lenient`",
+            ))
+          }
+        }
+      }
+    }
+  }
+
+  Ok(StructAttributes { rename_all, lenient })
 }
 
 /// 提取查询字段信息（包括属性）
-fn extract_query_fields(data: &Data) -> syn::Result<Vec<FieldInfo>> {
+///
+/// 设置了 `rename_all` 时，未显式指定 `name`/`rename` 的字段按规则转换参数名
+fn extract_query_fields(data: &Data, rename_all: Option<RenameRule>) -> syn::Result<Vec<FieldInfo>> {
   match data {
     Data::Struct(data_struct) => match &data_struct.fields {
       Fields::Named(fields_named) => {
         let mut field_info = Vec::new();
         for field in &fields_named.named {
           if let Some(ident) = &field.ident {
-            let (query_name, default_value) = extract_query_attributes(field, ident)?;
+            let field_name = ident.to_string();
+            let default_name = rename_all.map_or_else(|| field_name.clone(), |rule| rule.apply(&field_name));
+            let attributes = extract_query_attributes(field, default_name)?;
+            if attributes.delimiter.is_some() && !is_vec_type(&field.ty) {
+              return Err(syn::Error::new_spanned(
+                &field.ty,
+                "#[query(delimiter = \"...\")] and #[query(csv)] are only supported on Vec<T> fields",
+              ));
+            }
+            // 分组、展开、跳过和自定义模块的字段不经过 FromParam，不限制类型
+            if !attributes.nested && !attributes.flatten && !attributes.skip && attributes.with.is_none() {
+              check_query_field_type(&field.ty)?;
+            }
+            if attributes.page && !is_option_type(&field.ty) {
+              return Err(syn::Error::new_spanned(
+                &field.ty,
+                "#[query(page)] is only supported on Option<u32> fields",
+              ));
+            }
+            if attributes.skip
+              && (attributes.default_value.is_some()
+                || attributes.delimiter.is_some()
+                || attributes.nested
+                || attributes.flatten
+                || attributes.page
+                || attributes.with.is_some()
+                || attributes.flag)
+            {
+              return Err(syn::Error::new_spanned(
+                field,
+                "#[query(skip)] cannot be combined with other query attributes",
+              ));
+            }
+            // 两个字段映射到同一个参数名时，格式化结果会互相覆盖；展开字段的参数名来自子类型，无法在这里检查
+            let duplicate = field_info.iter().find(|info: &&FieldInfo| {
+              !attributes.skip && !attributes.flatten && !info.skip && !info.flatten && info.query_name == attributes.query_name
+            });
+            if let Some(existing) = duplicate {
+              return Err(syn::Error::new_spanned(
+                field,
+                format!(
+                  "Duplicate query key '{}' (already used by field `{}`)",
+                  attributes.query_name, existing.name
+                ),
+              ));
+            }
+            if attributes.nested && (attributes.default_value.is_some() || attributes.delimiter.is_some()) {
+              return Err(syn::Error::new_spanned(
+                field,
+                "#[query(nested)] cannot be combined with `default` or `delimiter`",
+              ));
+            }
+            if attributes.flatten && (attributes.nested || attributes.default_value.is_some() || attributes.delimiter.is_some()) {
+              return Err(syn::Error::new_spanned(
+                field,
+                "#[query(flatten)] cannot be combined with `nested`, `default` or `delimiter`",
+              ));
+            }
+            if attributes.with.is_some()
+              && (attributes.delimiter.is_some() || attributes.nested || attributes.flatten || attributes.page)
+            {
+              return Err(syn::Error::new_spanned(
+                field,
+                "#[query(with = \"...\")] cannot be combined with `delimiter`, `nested`, `flatten` or `page`",
+              ));
+            }
+            if attributes.flag {
+              if !is_bool_type(&field.ty) {
+                return Err(syn::Error::new_spanned(
+                  &field.ty,
+                  "#[query(bool_style = \"flag\")] is only supported on bool fields",
+                ));
+              }
+              if attributes.default_value.is_some() || attributes.with.is_some() {
+                return Err(syn::Error::new_spanned(
+                  field,
+                  "#[query(bool_style = \"flag\")] cannot be combined with `default` or `with`",
+                ));
+              }
+            }
+            // 校验属性只作用于单个值，Vec 字段在宽松模式下无法只丢弃违规的元素
+            if (attributes.max_len.is_some() || attributes.range.is_some())
+              && (attributes.skip || attributes.nested || attributes.flatten || attributes.flag || is_vec_type(&field.ty))
+            {
+              return Err(syn::Error::new_spanned(
+                field,
+                "`max_len` and `range` are only supported on single-value or Option<T> fields",
+              ));
+            }
             field_info.push(FieldInfo {
               name: ident.clone(),
               ty: field.ty.clone(),
-              query_name,
-              default_value,
+              query_name: attributes.query_name,
+              default_value: attributes.default_value,
+              delimiter: attributes.delimiter,
+              nested: attributes.nested,
+              flatten: attributes.flatten,
+              page: attributes.page,
+              skip: attributes.skip,
+              with: attributes.with,
+              flag: attributes.flag,
+              max_len: attributes.max_len,
+              range: attributes.range,
             });
           }
         }
@@ -91,10 +344,19 @@ fn extract_query_fields(data: &Data) -> syn::Result<Vec<FieldInfo>> {
   }
 }
 
-/// 提取字段的查询属性（支持 rename 和 default 属性）
-fn extract_query_attributes(field: &syn::Field, default_name: &syn::Ident) -> syn::Result<(String, Option<String>)> {
-  let mut query_name = default_name.to_string();
+/// 提取字段的查询属性（支持 rename、default、delimiter、csv、with、bool_style、max_len、range、nested、flatten、page 和 skip 属性）
+fn extract_query_attributes(field: &syn::Field, default_name: String) -> syn::Result<QueryAttributes> {
+  let mut query_name = default_name;
   let mut default_value = None;
+  let mut delimiter = None;
+  let mut nested = false;
+  let mut flatten = false;
+  let mut page = false;
+  let mut skip = false;
+  let mut with = None;
+  let mut flag = false;
+  let mut max_len = None;
+  let mut range = None;
 
   for attr in &field.attrs {
     if attr.path().is_ident("query") {
@@ -102,7 +364,26 @@ fn extract_query_attributes(field: &syn::Field, default_name: &syn::Ident) -> sy
         let parser = meta_list.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
 
         for meta in parser {
-          if let Meta::NameValue(name_value) = meta {
+          if let Meta::Path(path) = &meta {
+            if path.is_ident("nested") {
+              nested = true;
+            } else if path.is_ident("flatten") {
+              flatten = true;
+            } else if path.is_ident("page") {
+              page = true;
+            } else if path.is_ident("skip") {
+              skip = true;
+            } else if path.is_ident("csv") {
+              // `csv` 等价于 `delimiter = ","`
+              if delimiter.as_deref().is_some_and(|existing| existing != ",") {
+                return Err(syn::Error::new_spanned(
+                  path,
+                  "#[query(csv)] conflicts with a different `delimiter`",
+                ));
+              }
+              delimiter = Some(",".to_string());
+            }
+          } else if let Meta::NameValue(name_value) = meta {
             if name_value.path.is_ident("rename") || name_value.path.is_ident("name") {
               if let syn::Expr::Lit(expr_lit) = &name_value.value {
                 if let Lit::Str(lit_str) = &expr_lit.lit {
@@ -115,13 +396,68 @@ fn extract_query_attributes(field: &syn::Field, default_name: &syn::Ident) -> sy
                   default_value = Some(lit_str.value());
                 }
               }
+            } else if name_value.path.is_ident("delimiter") {
+              if let syn::Expr::Lit(expr_lit) = &name_value.value {
+                if let Lit::Str(lit_str) = &expr_lit.lit {
+                  if lit_str.value().is_empty() {
+                    return Err(syn::Error::new_spanned(lit_str, "delimiter must not be empty"));
+                  }
+                  if delimiter.as_deref().is_some_and(|existing| existing != lit_str.value()) {
+                    return Err(syn::Error::new_spanned(lit_str, "`delimiter` conflicts with #[query(csv)]"));
+                  }
+                  delimiter = Some(lit_str.value());
+                }
+              }
+            } else if name_value.path.is_ident("with") {
+              match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                  lit: Lit::Str(lit_str), ..
+                }) => with = Some(lit_str.parse::<syn::Path>()?),
+                other => return Err(syn::Error::new_spanned(other, "with expects a module path string like \"my_mod\"")),
+              }
+            } else if name_value.path.is_ident("bool_style") {
+              match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                  lit: Lit::Str(lit_str), ..
+                }) if lit_str.value() == "flag" => flag = true,
+                other => return Err(syn::Error::new_spanned(other, "bool_style expects \"flag\"")),
+              }
+            } else if name_value.path.is_ident("max_len") {
+              match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                  lit: Lit::Int(lit_int), ..
+                }) => max_len = Some(lit_int.base10_parse::<usize>()?),
+                other => return Err(syn::Error::new_spanned(other, "max_len expects an integer like 100")),
+              }
+            } else if name_value.path.is_ident("range") {
+              match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                  lit: Lit::Str(lit_str), ..
+                }) => match lit_str.parse::<syn::ExprRange>() {
+                  Ok(parsed) if parsed.start.is_some() || parsed.end.is_some() => range = Some((parsed, lit_str.value())),
+                  _ => return Err(syn::Error::new_spanned(lit_str, "range expects a range like \"1..=100\"")),
+                },
+                other => return Err(syn::Error::new_spanned(other, "range expects a range string like \"1..=100\"")),
+              }
             }
           }
         }
       }
     }
   }
-  Ok((query_name, default_value))
+  Ok(QueryAttributes {
+    query_name,
+    default_value,
+    delimiter,
+    nested,
+    flatten,
+    page,
+    skip,
+    with,
+    flag,
+    max_len,
+    range,
+  })
 }
 
 /// 生成解析字段的代码
@@ -134,21 +470,49 @@ fn generate_parse_fields(fields: &[FieldInfo]) -> syn::Result<Vec<TokenStream>>
     let query_name = &field_info.query_name;
     let default_value = &field_info.default_value;
 
-    let parse_code = if is_option_type(field_type) {
-      // Option<T> 类型使用 get_optional
+    let parse_code = if field_info.skip {
+      generate_skipped_field(field_name, field_type)
+    } else if field_info.flag {
+      // 只有键名的布尔标志
+      generate_parse_flag_field(field_name, quote! { parser.get(#query_name) })
+    } else if let Some(with) = &field_info.with {
+      // 自定义模块解析
+      let value = quote! { parser.get(#query_name) };
+      generate_parse_with_field(field_name, with, value, query_name, default_value.as_deref())
+    } else if field_info.nested {
+      // 分组字段，取出 `name[key]` 形式的参数交给子类型解析
       quote! {
-          #field_name: parser.get_optional(#query_name)?
+          #field_name: <#field_type as ::ruled_router::traits::Query>::from_query_map(
+              &::ruled_router::utils::nested_query_map(parser.params(), #query_name)
+          )?
+      }
+    } else if field_info.flatten {
+      // 展开字段，子类型从同一组参数中读取自己的参数名
+      quote! {
+          #field_name: <#field_type as ::ruled_router::traits::Query>::from_query_map(parser.params())?
       }
+    } else if let Some(delimiter) = &field_info.delimiter {
+      // 分隔值的 Vec<T> 类型，拆分后逐个通过 FromParam 解析
+      let values = quote! { parser.get_all(#query_name) };
+      generate_parse_delimited_field(field_name, field_type, values, delimiter)
     } else if is_vec_type(field_type) {
-      // Vec<T> 类型使用 get_all
+      // Vec<T> 类型使用 get_all_parsed，逐个通过 FromParam 解析
       quote! {
-          #field_name: parser.get_all(#query_name).iter().map(|s| s.to_string()).collect()
+          #field_name: parser.get_all_parsed(#query_name)?
       }
     } else if let Some(default_val) = default_value {
-      // 有默认值的类型，先尝试解析，失败则使用默认值
+      // 有默认值的类型，参数缺失时解析默认值
+      let default_expr = generate_default_value_expr(field_type, query_name, default_val);
+      quote! {
+          #field_name: match parser.get(#query_name) {
+              Some(value) => <#field_type as ::ruled_router::traits::FromParam>::from_param(value)?,
+              None => #default_expr,
+          }
+      }
+    } else if is_option_type(field_type) {
+      // Option<T> 类型使用 get_optional
       quote! {
           #field_name: parser.get_optional(#query_name)?
-              .unwrap_or_else(|| #default_val.parse().unwrap())
       }
     } else {
       // 其他类型使用 get_parsed
@@ -173,37 +537,65 @@ fn generate_from_query_map_fields(fields: &[FieldInfo]) -> syn::Result<Vec<Token
     let query_name = &field_info.query_name;
     let default_value = &field_info.default_value;
 
-    let parse_code = if is_option_type(field_type) {
-      // Option<T> 类型
+    let parse_code = if field_info.skip {
+      generate_skipped_field(field_name, field_type)
+    } else if field_info.flag {
+      // 只有键名的布尔标志
+      generate_parse_flag_field(field_name, quote! { query_map.get(#query_name).and_then(|values| values.first()) })
+    } else if let Some(with) = &field_info.with {
+      // 自定义模块解析
+      let value = quote! { query_map.get(#query_name).and_then(|values| values.first()) };
+      generate_parse_with_field(field_name, with, value, query_name, default_value.as_deref())
+    } else if field_info.nested {
+      // 分组字段
       quote! {
-          #field_name: query_map.get(#query_name)
-              .and_then(|values| values.first())
-              .and_then(|s| s.parse().ok())
+          #field_name: <#field_type as ::ruled_router::traits::Query>::from_query_map(
+              &::ruled_router::utils::nested_query_map(query_map, #query_name)
+          )?
+      }
+    } else if field_info.flatten {
+      // 展开字段
+      quote! {
+          #field_name: <#field_type as ::ruled_router::traits::Query>::from_query_map(query_map)?
       }
+    } else if let Some(delimiter) = &field_info.delimiter {
+      // 分隔值的 Vec<T> 类型
+      let values = quote! { query_map.get(#query_name).map(|values| values.as_slice()).unwrap_or_default() };
+      generate_parse_delimited_field(field_name, field_type, values, delimiter)
     } else if is_vec_type(field_type) {
       // Vec<T> 类型
       quote! {
           #field_name: query_map.get(#query_name)
-              .map(|values| values.iter().map(|s| s.to_string()).collect())
+              .map(|values| values.as_slice())
               .unwrap_or_default()
+              .iter()
+              .map(|value| ::ruled_router::traits::FromParam::from_param(value))
+              .collect::<Result<#field_type, ::ruled_router::error::ParseError>>()?
       }
     } else if let Some(default_val) = default_value {
       // 有默认值的类型
+      let default_expr = generate_default_value_expr(field_type, query_name, default_val);
       quote! {
-          #field_name: query_map.get(#query_name)
-              .and_then(|values| values.first())
-              .map(|s| s.parse())
-              .unwrap_or_else(|| #default_val.parse())
-              .map_err(|_| ::ruled_router::error::ParseError::type_conversion(format!("Failed to parse parameter: {}", #query_name)))?
+          #field_name: match query_map.get(#query_name).and_then(|values| values.first()) {
+              Some(value) => <#field_type as ::ruled_router::traits::FromParam>::from_param(value)?,
+              None => #default_expr,
+          }
+      }
+    } else if is_option_type(field_type) {
+      // Option<T> 类型
+      quote! {
+          #field_name: match query_map.get(#query_name).and_then(|values| values.first()) {
+              Some(value) => Some(::ruled_router::traits::FromParam::from_param(value)?),
+              None => None,
+          }
       }
     } else {
       // 其他类型
       quote! {
           #field_name: query_map.get(#query_name)
               .and_then(|values| values.first())
-              .ok_or_else(|| ::ruled_router::error::ParseError::missing_parameter(#query_name))?
-              .parse()
-              .map_err(|_| ::ruled_router::error::ParseError::type_conversion(format!("Failed to parse parameter: {}", #query_name)))?
+              .map(|value| <#field_type as ::ruled_router::traits::FromParam>::from_param(value))
+              .ok_or_else(|| ::ruled_router::error::ParseError::missing_parameter(#query_name))??
       }
     };
 
@@ -213,6 +605,443 @@ fn generate_from_query_map_fields(fields: &[FieldInfo]) -> syn::Result<Vec<Token
   Ok(parse_fields)
 }
 
+/// 生成逐字段的 parse_lenient 方法（`#[query(lenient)]`）
+///
+/// 回退值来自字段类型的 `Default`，因此只在显式开启时生成；
+/// max_len / range 校验违规的字段同样回退到默认值
+fn generate_parse_lenient_impl(fields: &[FieldInfo]) -> TokenStream {
+  let parse_lenient_fields = generate_parse_lenient_fields(fields);
+  let validate_lenient_fields = generate_validate_lenient_fields(fields);
+  let lenient_binding = if validate_lenient_fields.is_empty() {
+    quote! { parsed }
+  } else {
+    quote! { mut parsed }
+  };
+
+  quote! {
+      fn parse_lenient(query: &str) -> (Self, Vec<::ruled_router::error::QueryWarning>) {
+          let mut warnings = Vec::new();
+          let query_map = match ::ruled_router::utils::parse_query_string(query) {
+              Ok(query_map) => query_map,
+              Err(err) => {
+                  warnings.push(::ruled_router::error::QueryWarning::new("", query, err.to_string()));
+                  ::std::collections::HashMap::new()
+              }
+          };
+
+          let #lenient_binding = Self {
+              #(#parse_lenient_fields),*
+          };
+          #(#validate_lenient_fields)*
+          (parsed, warnings)
+      }
+  }
+}
+
+/// 生成宽松解析字段的代码
+///
+/// 无法解析的值记录到 `warnings` 中，字段回退到属性默认值或类型的 `Default`
+fn generate_parse_lenient_fields(fields: &[FieldInfo]) -> Vec<TokenStream> {
+  let mut parse_fields = Vec::new();
+
+  for field_info in fields {
+    let field_name = &field_info.name;
+    let field_type = &field_info.ty;
+    let query_name = &field_info.query_name;
+
+    // 逐个解析值，失败的值记录警告后跳过
+    let parse_value = quote! {
+        |value: &str| match ::ruled_router::traits::FromParam::from_param(value) {
+            Ok(parsed) => Some(parsed),
+            Err(err) => {
+                warnings.push(::ruled_router::error::QueryWarning::new(#query_name, value, err.to_string()));
+                None
+            }
+        }
+    };
+
+    let parse_code = if field_info.skip {
+      generate_skipped_field(field_name, field_type)
+    } else if field_info.flag {
+      // 布尔标志宽松解析，无法解析的值视为 false
+      quote! {
+          #field_name: match query_map.get(#query_name).and_then(|values| values.first()) {
+              Some(value) if value.is_empty() => true,
+              Some(value) => (#parse_value)(value).unwrap_or(false),
+              None => false,
+          }
+      }
+    } else if let Some(with) = &field_info.with {
+      // 自定义模块宽松解析，失败时回退到默认值
+      let fallback = generate_lenient_fallback(field_info);
+      quote! {
+          #field_name: match query_map.get(#query_name).and_then(|values| values.first()) {
+              Some(value) => match #with::parse(value) {
+                  Ok(parsed) => parsed,
+                  Err(err) => {
+                      warnings.push(::ruled_router::error::QueryWarning::new(#query_name, value.as_str(), err.to_string()));
+                      #fallback
+                  }
+              },
+              None => #fallback,
+          }
+      }
+    } else if field_info.flatten {
+      // 展开字段宽松解析，子类型的警告原样保留
+      quote! {
+          #field_name: {
+              let (value, nested_warnings) = <#field_type as ::ruled_router::traits::Query>::parse_lenient(
+                  &::ruled_router::utils::format_query_string(&query_map)
+              );
+              warnings.extend(nested_warnings);
+              value
+          }
+      }
+    } else if field_info.nested {
+      // 分组字段宽松解析，警告中的参数名补上分组前缀
+      quote! {
+          #field_name: {
+              let nested_map = ::ruled_router::utils::nested_query_map(&query_map, #query_name);
+              let (value, nested_warnings) = <#field_type as ::ruled_router::traits::Query>::parse_lenient(
+                  &::ruled_router::utils::format_query_string(&nested_map)
+              );
+              warnings.extend(nested_warnings.into_iter().map(|warning| {
+                  ::ruled_router::error::QueryWarning::new(
+                      ::ruled_router::utils::nest_query_key(#query_name, &warning.key),
+                      warning.value,
+                      warning.reason,
+                  )
+              }));
+              value
+          }
+      }
+    } else if is_vec_type(field_type) {
+      let segments = match &field_info.delimiter {
+        Some(delimiter) => quote! {
+            .flat_map(|value| value.split(#delimiter))
+            .filter(|segment| !segment.is_empty())
+        },
+        None => quote! {},
+      };
+      quote! {
+          #field_name: query_map.get(#query_name)
+              .map(|values| values.as_slice())
+              .unwrap_or_default()
+              .iter()
+              .map(|value| value.as_str())
+              #segments
+              .filter_map(#parse_value)
+              .collect()
+      }
+    } else {
+      let fallback = generate_lenient_fallback(field_info);
+      quote! {
+          #field_name: query_map.get(#query_name)
+              .and_then(|values| values.first())
+              .and_then(|value| (#parse_value)(value))
+              .unwrap_or_else(|| #fallback)
+      }
+    };
+
+    parse_fields.push(parse_code);
+  }
+
+  parse_fields
+}
+
+/// 宽松解析失败时字段的回退值：属性默认值，没有默认值或默认值无法解析时使用 `Default`
+fn generate_lenient_fallback(field_info: &FieldInfo) -> TokenStream {
+  let field_type = &field_info.ty;
+  match (&field_info.default_value, &field_info.with) {
+    (Some(default_val), Some(with)) => quote! { #with::parse(#default_val).unwrap_or_default() },
+    (Some(default_val), None) => quote! {
+        <#field_type as ::ruled_router::traits::FromParam>::from_param(#default_val).unwrap_or_default()
+    },
+    (None, _) => quote! { ::std::default::Default::default() },
+  }
+}
+
+/// 生成 `max_len` / `range` 的校验闭包，闭包接收字段引用，违规时返回原因
+///
+/// `Option<T>` 字段只校验存在的值
+fn generate_validation_check(field_info: &FieldInfo) -> Option<TokenStream> {
+  if field_info.max_len.is_none() && field_info.range.is_none() {
+    return None;
+  }
+
+  let field_type = &field_info.ty;
+  let query_name = &field_info.query_name;
+  let (value_type, values) = match option_inner_type(field_type) {
+    Some(inner_type) => (inner_type, quote! { field.iter() }),
+    None => (field_type, quote! { ::std::iter::once(field) }),
+  };
+
+  let max_len_check = field_info.max_len.map(|max_len| {
+    quote! {
+        let length = value.chars().count();
+        if length > #max_len {
+            return Some(format!(
+                "Query parameter '{}' is {} characters long, exceeding max_len {}",
+                #query_name, length, #max_len
+            ));
+        }
+    }
+  });
+  let range_check = field_info.range.as_ref().map(|(range, range_text)| {
+    quote! {
+        if !::std::ops::RangeBounds::<#value_type>::contains(&(#range), value) {
+            return Some(format!(
+                "Query parameter '{}' value {} is outside the range {}",
+                #query_name, value, #range_text
+            ));
+        }
+    }
+  });
+
+  Some(quote! {
+      |field: &#field_type| -> Option<String> {
+          for value in #values {
+              #max_len_check
+              #range_check
+          }
+          None
+      }
+  })
+}
+
+/// 生成严格解析的校验代码，违规时返回 `InvalidQuery` 错误
+fn generate_validate_fields(fields: &[FieldInfo]) -> Vec<TokenStream> {
+  fields
+    .iter()
+    .filter_map(|field_info| {
+      let field_name = &field_info.name;
+      let check = generate_validation_check(field_info)?;
+      Some(quote! {
+          if let Some(reason) = (#check)(&parsed.#field_name) {
+              return Err(::ruled_router::error::ParseError::invalid_query(reason));
+          }
+      })
+    })
+    .collect()
+}
+
+/// 生成宽松解析的校验代码，违规时记录警告并把字段重置为回退值
+fn generate_validate_lenient_fields(fields: &[FieldInfo]) -> Vec<TokenStream> {
+  fields
+    .iter()
+    .filter_map(|field_info| {
+      let field_name = &field_info.name;
+      let query_name = &field_info.query_name;
+      let check = generate_validation_check(field_info)?;
+      let fallback = generate_lenient_fallback(field_info);
+      Some(quote! {
+          if let Some(reason) = (#check)(&parsed.#field_name) {
+              let value = query_map.get(#query_name).and_then(|values| values.first()).map(String::as_str).unwrap_or_default();
+              warnings.push(::ruled_router::error::QueryWarning::new(#query_name, value, reason));
+              parsed.#field_name = #fallback;
+          }
+      })
+    })
+    .collect()
+}
+
+/// 生成 Paginated 实现
+///
+/// 没有 `#[query(page)]` 字段时不生成任何代码，最多只能标注一个字段
+fn generate_paginated_impl(struct_name: &syn::Ident, fields: &[FieldInfo]) -> syn::Result<TokenStream> {
+  let mut page_fields = fields.iter().filter(|field| field.page);
+  let Some(page_field) = page_fields.next() else {
+    return Ok(quote! {});
+  };
+  if let Some(duplicate) = page_fields.next() {
+    return Err(syn::Error::new_spanned(
+      &duplicate.name,
+      "Only one field can be marked with #[query(page)]",
+    ));
+  }
+
+  let field_name = &page_field.name;
+  Ok(quote! {
+      impl ::ruled_router::traits::Paginated for #struct_name {
+          fn page(&self) -> Option<u32> {
+              self.#field_name
+          }
+
+          fn with_page(&self, page: u32) -> Self {
+              let mut paged = ::std::clone::Clone::clone(self);
+              paged.#field_name = Some(page);
+              paged
+          }
+      }
+  })
+}
+
+/// 生成 query_keys 方法体
+///
+/// 没有展开字段时直接列出参数名；展开字段按声明顺序插入子类型的 `query_keys()`
+fn generate_query_keys(fields: &[FieldInfo]) -> TokenStream {
+  let fields: Vec<_> = fields.iter().filter(|field| !field.skip).collect();
+  if !fields.iter().any(|field| field.flatten) {
+    let query_keys = fields.iter().map(|field| &field.query_name);
+    return quote! { vec![#(#query_keys),*] };
+  }
+
+  let push_keys = fields.iter().map(|field| {
+    if field.flatten {
+      let field_type = &field.ty;
+      quote! { keys.extend(<#field_type as ::ruled_router::traits::Query>::query_keys()); }
+    } else {
+      let query_name = &field.query_name;
+      quote! { keys.push(#query_name); }
+    }
+  });
+  quote! {
+      let mut keys: Vec<&'static str> = Vec::new();
+      #(#push_keys)*
+      keys
+  }
+}
+
+/// 生成 unexpected_query_keys 方法
+///
+/// 没有分组或展开字段时使用 trait 的默认实现；否则普通参数名按字段直接比较，
+/// 分组内的参数名交给子类型检查后再补上分组前缀，
+/// 当前层级无法识别的参数名只有在所有展开字段的子类型也无法识别时才算未知
+fn generate_unexpected_query_keys(fields: &[FieldInfo]) -> TokenStream {
+  if !fields.iter().any(|field| field.nested || field.flatten) {
+    return quote! {};
+  }
+
+  let flat_keys: Vec<_> = fields
+    .iter()
+    .filter(|field| !field.nested && !field.flatten && !field.skip)
+    .map(|field| &field.query_name)
+    .collect();
+  let group_names: Vec<_> = fields.iter().filter(|field| field.nested).map(|field| &field.query_name).collect();
+  let group_types: Vec<_> = fields.iter().filter(|field| field.nested).map(|field| &field.ty).collect();
+  let flatten_types: Vec<_> = fields.iter().filter(|field| field.flatten).map(|field| &field.ty).collect();
+
+  quote! {
+      fn unexpected_query_keys(query_map: &std::collections::HashMap<String, Vec<String>>) -> Vec<String> {
+          let flat_keys: &[&str] = &[#(#flat_keys),*];
+          let group_names: &[&str] = &[#(#group_names),*];
+
+          let mut unexpected: Vec<String> = query_map
+              .keys()
+              .filter(|key| {
+                  !flat_keys.contains(&key.as_str())
+                      && !group_names
+                          .iter()
+                          .any(|group| ::ruled_router::utils::nested_query_key(key, group).is_some())
+              })
+              .cloned()
+              .collect();
+
+          #(
+              let rejected = <#flatten_types as ::ruled_router::traits::Query>::unexpected_query_keys(query_map);
+              unexpected.retain(|key| rejected.contains(key));
+          )*
+
+          #(
+              let nested_map = ::ruled_router::utils::nested_query_map(query_map, #group_names);
+              unexpected.extend(
+                  <#group_types as ::ruled_router::traits::Query>::unexpected_query_keys(&nested_map)
+                      .into_iter()
+                      .map(|key| ::ruled_router::utils::nest_query_key(#group_names, &key)),
+              );
+          )*
+
+          unexpected
+      }
+  }
+}
+
+/// 生成跳过字段的初始化代码
+///
+/// 错误信息指向字段类型，便于发现没有实现 Default 的类型
+fn generate_skipped_field(field_name: &syn::Ident, field_type: &Type) -> TokenStream {
+  let default_value = quote_spanned! {field_type.span()=>
+      <#field_type as ::std::default::Default>::default()
+  };
+  quote! {
+      #field_name: #default_value
+  }
+}
+
+/// 生成解析分隔值字段的代码
+///
+/// `values` 为该参数的所有原始值，每个值按分隔符拆分，空段会被丢弃
+fn generate_parse_delimited_field(field_name: &syn::Ident, field_type: &Type, values: TokenStream, delimiter: &str) -> TokenStream {
+  quote! {
+      #field_name: #values
+          .iter()
+          .flat_map(|value| value.split(#delimiter))
+          .filter(|segment| !segment.is_empty())
+          .map(::ruled_router::traits::FromParam::from_param)
+          .collect::<Result<#field_type, ::ruled_router::error::ParseError>>()?
+  }
+}
+
+/// 生成解析布尔标志字段的代码
+///
+/// `value` 为该参数的第一个原始值（`Option<&String>`）：空值表示 true，缺失表示 false，
+/// 其余值按 `bool` 的 FromParam 解析，因此 `flag=true` 和 `flag=false` 同样有效
+fn generate_parse_flag_field(field_name: &syn::Ident, value: TokenStream) -> TokenStream {
+  quote! {
+      #field_name: match #value {
+          Some(value) if value.is_empty() => true,
+          Some(value) => <bool as ::ruled_router::traits::FromParam>::from_param(value)?,
+          None => false,
+      }
+  }
+}
+
+/// 生成使用自定义模块解析字段的代码
+///
+/// `value` 为该参数的第一个原始值（`Option<&String>`），缺失时解析默认值，没有默认值则返回缺少参数错误
+fn generate_parse_with_field(
+  field_name: &syn::Ident,
+  with: &syn::Path,
+  value: TokenStream,
+  query_name: &str,
+  default_value: Option<&str>,
+) -> TokenStream {
+  let missing = match default_value {
+    Some(default_val) => quote! {
+        #with::parse(#default_val).map_err(|err| {
+            ::ruled_router::error::ParseError::type_conversion(format!(
+                "Invalid default value '{}' for query parameter '{}': {}",
+                #default_val, #query_name, err
+            ))
+        })?
+    },
+    None => quote! {
+        return Err(::ruled_router::error::ParseError::missing_parameter(#query_name))
+    },
+  };
+  quote! {
+      #field_name: match #value {
+          Some(value) => #with::parse(value)?,
+          None => #missing,
+      }
+  }
+}
+
+/// 生成解析默认值的表达式
+///
+/// 默认值通过 `FromParam` 解析，`Option<T>` 字段得到 `Some(default)`；
+/// 默认值本身无法解析时返回 `ParseError::TypeConversion`
+fn generate_default_value_expr(field_type: &Type, query_name: &str, default_value: &str) -> TokenStream {
+  quote! {
+    <#field_type as ::ruled_router::traits::FromParam>::from_param(#default_value).map_err(|err| {
+      ::ruled_router::error::ParseError::type_conversion(format!(
+        "Invalid default value '{}' for query parameter '{}': {}",
+        #default_value, #query_name, err
+      ))
+    })?
+  }
+}
+
 /// 生成格式化字段的代码
 fn generate_format_fields(fields: &[FieldInfo]) -> Vec<TokenStream> {
   let mut format_fields = Vec::new();
@@ -222,24 +1051,65 @@ fn generate_format_fields(fields: &[FieldInfo]) -> Vec<TokenStream> {
     let field_type = &field_info.ty;
     let query_name = &field_info.query_name;
 
-    let format_code = if is_option_type(field_type) {
+    let format_code = if field_info.skip {
+      // 跳过的字段不写入查询字符串
+      quote! {}
+    } else if field_info.flag {
+      // 布尔标志为 true 时只输出键名，false 时不输出
+      quote! {
+          if self.#field_name {
+              pairs.push((#query_name.to_string(), String::new()));
+          }
+      }
+    } else if let Some(with) = &field_info.with {
+      // 自定义模块格式化
+      quote! {
+          pairs.push((#query_name.to_string(), #with::format(&self.#field_name)));
+      }
+    } else if field_info.flatten {
+      // 展开字段，子类型的参数按原名追加
+      quote! {
+          let nested_query = ::ruled_router::traits::Query::format(&self.#field_name);
+          pairs.extend(::ruled_router::utils::parse_query_pairs(&nested_query).unwrap_or_default());
+      }
+    } else if field_info.nested {
+      // 分组字段，子类型的每个参数名加上 `name[...]` 前缀
+      quote! {
+          let nested_query = ::ruled_router::traits::Query::format(&self.#field_name);
+          for (key, value) in ::ruled_router::utils::parse_query_pairs(&nested_query).unwrap_or_default() {
+              pairs.push((::ruled_router::utils::nest_query_key(#query_name, &key), value));
+          }
+      }
+    } else if let Some(delimiter) = &field_info.delimiter {
+      // 分隔值的 Vec<T> 类型，用分隔符连接为单个值
+      quote! {
+          if !self.#field_name.is_empty() {
+              let joined = self.#field_name
+                  .iter()
+                  .map(::ruled_router::traits::ToParam::to_param)
+                  .collect::<Vec<_>>()
+                  .join(#delimiter);
+              pairs.push((#query_name.to_string(), joined));
+          }
+      }
+    } else if is_option_type(field_type) {
       // Option<T> 类型
       quote! {
           if let Some(ref value) = self.#field_name {
-              formatter.set(#query_name, ::ruled_router::traits::ToParam::to_param(value));
+              pairs.push((#query_name.to_string(), ::ruled_router::traits::ToParam::to_param(value)));
           }
       }
     } else if is_vec_type(field_type) {
       // Vec<T> 类型
       quote! {
           for value in &self.#field_name {
-              formatter.add(#query_name, ::ruled_router::traits::ToParam::to_param(value));
+              pairs.push((#query_name.to_string(), ::ruled_router::traits::ToParam::to_param(value)));
           }
       }
     } else {
       // 其他类型
       quote! {
-          formatter.set(#query_name, ::ruled_router::traits::ToParam::to_param(&self.#field_name));
+          pairs.push((#query_name.to_string(), ::ruled_router::traits::ToParam::to_param(&self.#field_name)));
       }
     };
 
@@ -249,6 +1119,51 @@ fn generate_format_fields(fields: &[FieldInfo]) -> Vec<TokenStream> {
   format_fields
 }
 
+/// 检查查询字段的类型是否是支持的形式
+///
+/// 无法判断类型是否实现了 FromParam，这里只拒绝明显不可能的形式（元组、引用、数组、映射等），
+/// 避免在生成的代码里报出难以理解的类型错误；`Option<T>` 和 `Vec<T>` 递归检查内部类型，
+/// 内部的 `Vec` 没有 ToParam 实现，同样拒绝
+fn check_query_field_type(ty: &Type) -> syn::Result<()> {
+  check_query_field_type_at(ty, false)
+}
+
+fn check_query_field_type_at(ty: &Type, nested: bool) -> syn::Result<()> {
+  const UNSUPPORTED_COLLECTIONS: &[&str] = &["HashMap", "BTreeMap", "HashSet", "BTreeSet", "VecDeque", "LinkedList", "BinaryHeap"];
+  let unsupported = || syn::Error::new_spanned(ty, "query fields must be `Option<T>`, `Vec<T>`, or a type implementing FromParam");
+
+  match ty {
+    Type::Paren(paren) => check_query_field_type_at(&paren.elem, nested),
+    Type::Group(group) => check_query_field_type_at(&group.elem, nested),
+    Type::Path(TypePath { qself: None, path }) => {
+      let Some(segment) = path.segments.last() else {
+        return Err(unsupported());
+      };
+      if UNSUPPORTED_COLLECTIONS.iter().any(|name| segment.ident == name) {
+        return Err(unsupported());
+      }
+      if nested && segment.ident == "Vec" {
+        return Err(syn::Error::new_spanned(
+          ty,
+          "nested `Vec` is not supported in query fields, use `CommaSeparated<T>` or #[query(delimiter = \",\")]",
+        ));
+      }
+      if segment.ident == "Option" || segment.ident == "Vec" {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+          for arg in &args.args {
+            if let syn::GenericArgument::Type(inner) = arg {
+              check_query_field_type_at(inner, true)?;
+            }
+          }
+        }
+      }
+      Ok(())
+    }
+    Type::Path(_) | Type::Macro(_) => Ok(()),
+    _ => Err(unsupported()),
+  }
+}
+
 /// 检查类型是否为 Option<T>
 fn is_option_type(ty: &Type) -> bool {
   if let Type::Path(TypePath { path, .. }) = ty {
@@ -259,6 +1174,21 @@ fn is_option_type(ty: &Type) -> bool {
   false
 }
 
+/// 获取 `Option<T>` 中的 T，字段不是 Option 时返回 None
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+  let Type::Path(TypePath { path, .. }) = ty else {
+    return None;
+  };
+  let segment = path.segments.last().filter(|segment| segment.ident == "Option")?;
+  let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+    return None;
+  };
+  match args.args.first()? {
+    syn::GenericArgument::Type(inner) => Some(inner),
+    _ => None,
+  }
+}
+
 /// 检查类型是否为 Vec<T>
 fn is_vec_type(ty: &Type) -> bool {
   if let Type::Path(TypePath { path, .. }) = ty {
@@ -268,3 +1198,8 @@ fn is_vec_type(ty: &Type) -> bool {
   }
   false
 }
+
+/// 检查类型是否为 bool
+fn is_bool_type(ty: &Type) -> bool {
+  matches!(ty, Type::Path(TypePath { qself: None, path }) if path.is_ident("bool"))
+}
@@ -0,0 +1,241 @@
+//! Implementation of the QuerySerde derive macro
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Fields, Lit, Meta};
+
+/// Expand the QuerySerde derive macro
+pub fn expand_query_serde_derive(input: DeriveInput) -> syn::Result<TokenStream> {
+  let struct_name = &input.ident;
+  let query_keys = extract_serde_query_keys(&input)?;
+
+  let expanded = quote! {
+      impl ::ruled_router::traits::Query for #struct_name {
+          fn parse(query: &str) -> Result<Self, ::ruled_router::error::ParseError> {
+              ::ruled_router::serde_query::parse(query)
+          }
+
+          fn format(&self) -> String {
+              // Query::format 无法返回错误，serde 无法格式化时在所有构建中都输出空字符串，
+              // 需要错误信息时直接调用 serde_query::format
+              ::ruled_router::serde_query::format(self).unwrap_or_default()
+          }
+
+          fn from_query_map(query_map: &std::collections::HashMap<String, Vec<String>>) -> Result<Self, ::ruled_router::error::ParseError> {
+              ::ruled_router::serde_query::from_query_map(query_map)
+          }
+
+          fn to_query_string(&self) -> String {
+              ::ruled_router::traits::Query::format(self)
+          }
+
+          fn query_keys() -> Vec<&'static str> {
+              vec![#(#query_keys),*]
+          }
+      }
+  };
+
+  Ok(expanded)
+}
+
+/// 提取序列化后的查询参数名
+///
+/// 识别结构体上的 `#[serde(rename_all = "...")]` 和字段上的 `#[serde(rename = "...")]`（字段优先），
+/// 跳过 `skip` 和 `flatten` 字段；其余字段不能是 `Vec<T>` 等序列类型，`serde_urlencoded` 无法解析或格式化它们
+fn extract_serde_query_keys(input: &DeriveInput) -> syn::Result<Vec<String>> {
+  let rename_all = extract_serde_rename_all(&input.attrs);
+  let fields = match &input.data {
+    Data::Struct(data_struct) => match &data_struct.fields {
+      Fields::Named(fields_named) => &fields_named.named,
+      _ => return Err(syn::Error::new_spanned(&data_struct.fields, "Only named fields are supported")),
+    },
+    _ => return Err(syn::Error::new_spanned(input, "QuerySerde can only be derived for structs")),
+  };
+
+  let mut keys = Vec::new();
+
+  'fields: for field in fields {
+    let Some(ident) = &field.ident else {
+      continue;
+    };
+    let field_name = ident.unraw().to_string();
+    let mut key = match rename_all {
+      Some(rule) => rule.apply(&field_name),
+      None => field_name,
+    };
+
+    for attr in &field.attrs {
+      if !attr.path().is_ident("serde") {
+        continue;
+      }
+      // 无法识别的 serde 属性交给 serde 自己报错
+      let Ok(metas) = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated) else {
+        continue;
+      };
+      for meta in metas {
+        match meta {
+          Meta::Path(path) if path.is_ident("skip") || path.is_ident("skip_serializing") || path.is_ident("flatten") => {
+            continue 'fields;
+          }
+          Meta::NameValue(name_value) if name_value.path.is_ident("rename") => {
+            if let syn::Expr::Lit(expr_lit) = &name_value.value {
+              if let Lit::Str(lit_str) = &expr_lit.lit {
+                key = lit_str.value();
+              }
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+
+    if is_sequence_type(&field.ty) {
+      return Err(syn::Error::new_spanned(
+        &field.ty,
+        "QuerySerde does not support sequence fields like `Vec<T>`: serde_urlencoded cannot parse or format repeated keys, use QueryDerive instead",
+      ));
+    }
+
+    keys.push(key);
+  }
+
+  Ok(keys)
+}
+
+/// 判断字段类型是否为序列（`Vec`、`VecDeque`、`HashSet`、`BTreeSet`，包括包在 `Option` 中的情况）
+fn is_sequence_type(ty: &syn::Type) -> bool {
+  let syn::Type::Path(type_path) = ty else {
+    return false;
+  };
+  let Some(segment) = type_path.path.segments.last() else {
+    return false;
+  };
+  match segment.ident.to_string().as_str() {
+    "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => true,
+    "Option" => match &segment.arguments {
+      syn::PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+        syn::GenericArgument::Type(inner) => is_sequence_type(inner),
+        _ => false,
+      }),
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+/// serde 的 `rename_all` 命名规则，转换方式与 serde 对字段名的处理一致
+#[derive(Clone, Copy)]
+enum SerdeRenameRule {
+  /// `lowercase` 和 `snake_case`：字段名本身就是 snake_case
+  Unchanged,
+  /// `UPPERCASE` 和 `SCREAMING_SNAKE_CASE`
+  Uppercase,
+  /// `PascalCase`
+  Pascal,
+  /// `camelCase`
+  Camel,
+  /// `kebab-case`
+  Kebab,
+  /// `SCREAMING-KEBAB-CASE`
+  ScreamingKebab,
+}
+
+impl SerdeRenameRule {
+  fn parse(value: &str) -> Option<Self> {
+    match value {
+      "lowercase" | "snake_case" => Some(SerdeRenameRule::Unchanged),
+      "UPPERCASE" | "SCREAMING_SNAKE_CASE" => Some(SerdeRenameRule::Uppercase),
+      "PascalCase" => Some(SerdeRenameRule::Pascal),
+      "camelCase" => Some(SerdeRenameRule::Camel),
+      "kebab-case" => Some(SerdeRenameRule::Kebab),
+      "SCREAMING-KEBAB-CASE" => Some(SerdeRenameRule::ScreamingKebab),
+      _ => None,
+    }
+  }
+
+  fn apply(self, field_name: &str) -> String {
+    match self {
+      SerdeRenameRule::Unchanged => field_name.to_string(),
+      SerdeRenameRule::Uppercase => field_name.to_ascii_uppercase(),
+      SerdeRenameRule::Pascal => {
+        let mut pascal = String::with_capacity(field_name.len());
+        let mut capitalize = true;
+        for c in field_name.chars() {
+          if c == '_' {
+            capitalize = true;
+          } else if capitalize {
+            pascal.push(c.to_ascii_uppercase());
+            capitalize = false;
+          } else {
+            pascal.push(c);
+          }
+        }
+        pascal
+      }
+      SerdeRenameRule::Camel => {
+        let pascal = SerdeRenameRule::Pascal.apply(field_name);
+        let mut chars = pascal.chars();
+        match chars.next() {
+          Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+          None => pascal,
+        }
+      }
+      SerdeRenameRule::Kebab => field_name.replace('_', "-"),
+      SerdeRenameRule::ScreamingKebab => field_name.to_ascii_uppercase().replace('_', "-"),
+    }
+  }
+}
+
+/// 提取结构体上的 `#[serde(rename_all = "...")]`
+///
+/// `rename_all(serialize = "...", deserialize = "...")` 取 `deserialize` 的规则，因为 query_keys 描述的是解析时接受的参数名；
+/// 无法识别的规则交给 serde 自己报错
+fn extract_serde_rename_all(attrs: &[syn::Attribute]) -> Option<SerdeRenameRule> {
+  let mut rename_all = None;
+
+  for attr in attrs {
+    if !attr.path().is_ident("serde") {
+      continue;
+    }
+    let Ok(metas) = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated) else {
+      continue;
+    };
+    for meta in metas {
+      match meta {
+        Meta::NameValue(name_value) if name_value.path.is_ident("rename_all") => {
+          if let Some(rule) = string_literal(&name_value.value).and_then(|value| SerdeRenameRule::parse(&value)) {
+            rename_all = Some(rule);
+          }
+        }
+        Meta::List(meta_list) if meta_list.path.is_ident("rename_all") => {
+          let Ok(nested) = meta_list.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated) else {
+            continue;
+          };
+          for nested_meta in nested {
+            if let Meta::NameValue(name_value) = nested_meta {
+              if name_value.path.is_ident("deserialize") {
+                if let Some(rule) = string_literal(&name_value.value).and_then(|value| SerdeRenameRule::parse(&value)) {
+                  rename_all = Some(rule);
+                }
+              }
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  rename_all
+}
+
+/// 取出字符串字面量的值
+fn string_literal(expr: &syn::Expr) -> Option<String> {
+  match expr {
+    syn::Expr::Lit(syn::ExprLit {
+      lit: Lit::Str(lit_str), ..
+    }) => Some(lit_str.value()),
+    _ => None,
+  }
+}
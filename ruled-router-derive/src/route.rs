@@ -1,32 +1,17 @@
 //! Implementation of the Router derive macro
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{Data, DeriveInput, Fields, Type};
 
-use crate::extract_route_config;
+use crate::pattern::{has_constraints, parse_pattern_params, ParamKind};
+use crate::{extract_route_config, RouteConfig};
 
-/// 从路径模式中提取参数名
-fn extract_path_params(pattern: &str) -> Vec<String> {
-  let mut params = Vec::new();
-  let segments: Vec<&str> = pattern.split('/').collect();
-
-  for segment in segments {
-    if segment.starts_with(':') {
-      // 支持 :param 格式
-      params.push(segment.strip_prefix(':').unwrap().to_string());
-    } else if segment.starts_with('{') && segment.ends_with('}') {
-      // 支持 {param} 格式
-      params.push(segment[1..segment.len() - 1].to_string());
-    }
-  }
-
-  params
-}
-
-/// 分离路径字段和查询字段
 /// 提取字段信息（包括属性）
-fn extract_route_fields(data: &Data) -> syn::Result<Vec<(syn::Ident, Type, bool, bool)>> {
+///
+/// 元组结构体的字段按位置绑定到模式参数，因此不能带有 #[query] 或 #[sub_router]
+fn extract_route_fields(data: &Data) -> syn::Result<Vec<RouteField>> {
   match data {
     Data::Struct(data_struct) => match &data_struct.fields {
       Fields::Named(fields_named) => {
@@ -35,12 +20,29 @@ fn extract_route_fields(data: &Data) -> syn::Result<Vec<(syn::Ident, Type, bool,
           if let Some(ident) = &field.ident {
             let is_query = has_query_attribute(field);
             let is_sub_router = has_sub_router_attribute(field);
-            field_info.push((ident.clone(), field.ty.clone(), is_query, is_sub_router));
+            field_info.push((syn::Member::Named(ident.clone()), field.ty.clone(), is_query, is_sub_router));
           }
         }
         Ok(field_info)
       }
-      _ => Err(syn::Error::new_spanned(&data_struct.fields, "Only named fields are supported")),
+      Fields::Unnamed(fields_unnamed) => {
+        let mut field_info = Vec::new();
+        for (index, field) in fields_unnamed.unnamed.iter().enumerate() {
+          if has_query_attribute(field) || has_sub_router_attribute(field) {
+            return Err(syn::Error::new_spanned(
+              field,
+              "#[query] and #[sub_router] are only supported on named fields",
+            ));
+          }
+          let member = syn::Member::Unnamed(syn::Index {
+            index: index as u32,
+            span: field.ty.span(),
+          });
+          field_info.push((member, field.ty.clone(), false, false));
+        }
+        Ok(field_info)
+      }
+      Fields::Unit => Err(syn::Error::new_spanned(data_struct.struct_token, "Unit structs are not supported")),
     },
     _ => Err(syn::Error::new(proc_macro2::Span::call_site(), "Only structs are supported")),
   }
@@ -67,61 +69,79 @@ fn has_sub_router_attribute(field: &syn::Field) -> bool {
 }
 
 // Define type aliases to improve readability and reduce complexity
-type RouteField = (syn::Ident, Type, bool, bool);
-type ParsedField = (syn::Ident, Type);
+type RouteField = (syn::Member, Type, bool, bool);
+type ParsedField = (syn::Member, Type);
+/// 路径字段及其绑定的模式参数名
+type PathField = (syn::Member, Type, String);
+
+/// 字段绑定的模式参数名：命名字段使用字段名，元组字段按位置取第几个参数
+fn bound_param_name(member: &syn::Member, param_names: &[String]) -> Option<String> {
+  match member {
+    syn::Member::Named(ident) => Some(ident.to_string()),
+    syn::Member::Unnamed(index) => param_names.get(index.index as usize).cloned(),
+  }
+}
 
-fn separate_fields(fields: &[RouteField], param_names: &[String]) -> (Vec<ParsedField>, Vec<ParsedField>) {
+/// 分离路径字段和查询字段
+fn separate_fields(fields: &[RouteField], param_names: &[String]) -> (Vec<PathField>, Vec<ParsedField>) {
   let mut path_fields = Vec::new();
   let mut query_fields = Vec::new();
 
   for (field_name, field_type, is_query, is_sub_router) in fields {
-    let field_name_str = field_name.to_string();
+    let param_name = bound_param_name(field_name, param_names).filter(|name| param_names.contains(name));
 
     if *is_query {
       // 这是查询字段（有 #[query] 属性）
       query_fields.push((field_name.clone(), field_type.clone()));
-    } else if param_names.contains(&field_name_str) {
+    } else if let Some(param_name) = param_name {
       // 这是路径参数
-      path_fields.push((field_name.clone(), field_type.clone()));
+      path_fields.push((field_name.clone(), field_type.clone(), param_name));
     } else if *is_sub_router {
       // 忽略子路由字段，它们由 RouterMatch trait 处理
     }
-    // 忽略其他字段
+    // 其他字段在 expand_route_derive 中已经报错
   }
 
   (path_fields, query_fields)
 }
 
 /// 生成解析路径字段的代码
-fn generate_parse_path_fields(fields: &[(syn::Ident, Type)], param_names: &[String]) -> syn::Result<Vec<TokenStream>> {
+///
+/// 可选参数绑定到 `Option<T>` 字段，路径中没有该段时为 None
+fn generate_parse_path_fields(fields: &[PathField], optional_params: &[String]) -> Vec<TokenStream> {
   let mut parse_fields = Vec::new();
 
-  for (field_name, field_type) in fields {
-    let field_name_str = field_name.to_string();
-
-    if param_names.contains(&field_name_str) {
-      // 这是一个路径参数
-      let parse_code = quote! {
-          #field_name: {
-              let param_value = params.get(#field_name_str)
-                  .ok_or_else(|| ::ruled_router::error::ParseError::missing_parameter(#field_name_str))?;
-              <#field_type as ::ruled_router::traits::FromParam>::from_param(param_value)?
-          }
-      };
-      parse_fields.push(parse_code);
+  for (field_name, field_type, param_name) in fields {
+    if optional_params.contains(param_name) {
+      let inner_type = option_inner_type(field_type).unwrap_or(field_type);
+      parse_fields.push(quote! {
+          #field_name: params.get(#param_name)
+              .map(|param_value| <#inner_type as ::ruled_router::traits::FromParam>::from_param(param_value))
+              .transpose()?
+      });
+      continue;
     }
+
+    let parse_code = quote! {
+        #field_name: {
+            let param_value = params.get(#param_name)
+                .ok_or_else(|| ::ruled_router::error::ParseError::missing_parameter(#param_name))?;
+            <#field_type as ::ruled_router::traits::FromParam>::from_param(param_value)?
+        }
+    };
+    parse_fields.push(parse_code);
   }
 
-  Ok(parse_fields)
+  parse_fields
 }
 
 /// 生成解析查询字段的代码
-fn generate_parse_query_fields(fields: &[(syn::Ident, Type)]) -> Vec<TokenStream> {
+fn generate_parse_query_fields(fields: &[ParsedField]) -> Vec<TokenStream> {
   let mut parse_fields = Vec::new();
 
   for (field_name, field_type) in fields {
     let parse_code = quote! {
-        #field_name: <#field_type>::parse(query_part.unwrap_or(""))?
+        #field_name: <#field_type as ::ruled_router::traits::Query>::parse(query_part.unwrap_or(""))?
     };
     parse_fields.push(parse_code);
   }
@@ -130,13 +150,23 @@ fn generate_parse_query_fields(fields: &[(syn::Ident, Type)]) -> Vec<TokenStream
 }
 
 /// 生成格式化路径字段的代码
-fn generate_format_path_fields(fields: &[(syn::Ident, Type)]) -> Vec<TokenStream> {
+///
+/// 可选参数只在字段为 Some 时写入，格式化时省略对应的段
+fn generate_format_path_fields(fields: &[PathField], optional_params: &[String]) -> Vec<TokenStream> {
   let mut format_fields = Vec::new();
 
-  for (field_name, _) in fields {
-    let field_name_str = field_name.to_string();
+  for (field_name, _, param_name) in fields {
+    if optional_params.contains(param_name) {
+      format_fields.push(quote! {
+          if let Some(param_value) = &self.#field_name {
+              params.insert(#param_name.to_string(), ::ruled_router::traits::ToParam::to_param(param_value));
+          }
+      });
+      continue;
+    }
+
     let format_code = quote! {
-        params.insert(#field_name_str.to_string(), ::ruled_router::traits::ToParam::to_param(&self.#field_name));
+        params.insert(#param_name.to_string(), ::ruled_router::traits::ToParam::to_param(&self.#field_name));
     };
     format_fields.push(format_code);
   }
@@ -144,90 +174,118 @@ fn generate_format_path_fields(fields: &[(syn::Ident, Type)]) -> Vec<TokenStream
   format_fields
 }
 
+/// 子路由字段的声明方式
+#[derive(Clone, Copy, PartialEq)]
+enum SubRouterKind {
+  /// `Option<T>`：子路由可选
+  Option,
+  /// `RouteState<T>`：子路由可选，并保留解析失败信息
+  RouteState,
+  /// 直接声明为 `T`：子路由必须存在，否则解析失败
+  Required,
+}
+
+/// 根据字段类型判断子路由字段的声明方式
+fn sub_router_kind(field_type: &Type) -> SubRouterKind {
+  if let Type::Path(type_path) = field_type {
+    if let Some(segment) = type_path.path.segments.last() {
+      if segment.ident == "Option" {
+        return SubRouterKind::Option;
+      } else if segment.ident == "RouteState" {
+        return SubRouterKind::RouteState;
+      }
+    }
+  }
+  SubRouterKind::Required
+}
+
+/// 查找有 #[sub_router] 属性的字段及其声明方式
+fn find_sub_router_field(fields: &[RouteField]) -> Option<(&syn::Member, SubRouterKind)> {
+  fields
+    .iter()
+    .find(|(_, _, _, is_sub_router)| *is_sub_router)
+    .map(|(field_name, field_type, _, _)| (field_name, sub_router_kind(field_type)))
+}
+
+/// 生成 sub_route_info 方法体
+///
+/// 直接读取 `#[sub_router]` 字段，避免先格式化再重新解析
+fn generate_sub_route_info_body(fields: &[RouteField]) -> TokenStream {
+  let Some((field_name, kind)) = find_sub_router_field(fields) else {
+    return quote! { None };
+  };
+
+  let to_info = quote! { Box::new(::ruled_router::traits::ToRouteInfo::to_route_info(sub_router)) };
+  match kind {
+    SubRouterKind::Option => quote! { self.#field_name.as_ref().map(|sub_router| #to_info) },
+    SubRouterKind::RouteState => quote! {
+      match &self.#field_name {
+        RouteState::SubRoute(sub_router) => Some(#to_info),
+        _ => None,
+      }
+    },
+    SubRouterKind::Required => quote! {
+      let sub_router = &self.#field_name;
+      Some(#to_info)
+    },
+  }
+}
+
 /// 生成格式化子路由逻辑的代码
 fn generate_format_sub_router_logic(fields: &[RouteField]) -> TokenStream {
-  // 查找有 #[sub_router] 属性的字段
-  for (field_name, field_type, _, is_sub_router) in fields {
-    if *is_sub_router {
-      // 检查字段类型是 Option 还是 RouteState
-      if let Type::Path(type_path) = field_type {
-        if let Some(segment) = type_path.path.segments.last() {
-          if segment.ident == "Option" {
-            // 对于 Option 类型，生成不同的匹配逻辑
-            return quote! {
-              if let Some(ref sub_router) = &self.#field_name {
-                let sub_url = sub_router.format();
-                if !sub_url.is_empty() {
-                  // 移除子路由URL中的查询部分，因为我们将在最后添加基础路由的查询
-                  let (sub_path_part, sub_query_part) = ::ruled_router::utils::split_path_query(&sub_url);
-                  url.push_str(sub_path_part);
-                  // 如果子路由有查询参数，则使用子路由的查询参数而不是基础路由的
-                  if let Some(sub_query) = sub_query_part {
-                    if !sub_query.is_empty() {
-                      url.push('?');
-                      url.push_str(sub_query);
-                      return url; // 提前返回，避免添加基础路由的查询参数
-                    }
-                  }
-                }
-              }
-            };
-          } else if segment.ident == "RouteState" {
-            // 对于 RouteState 类型，使用原来的匹配逻辑
-            return quote! {
-              match &self.#field_name {
-                RouteState::SubRoute(ref sub_router) => {
-                  let sub_url = sub_router.format();
-                  if !sub_url.is_empty() {
-                    // 移除子路由URL中的查询部分，因为我们将在最后添加基础路由的查询
-                    let (sub_path_part, sub_query_part) = ::ruled_router::utils::split_path_query(&sub_url);
-                    url.push_str(sub_path_part);
-                    // 如果子路由有查询参数，则使用子路由的查询参数而不是基础路由的
-                    if let Some(sub_query) = sub_query_part {
-                      if !sub_query.is_empty() {
-                        url.push('?');
-                        url.push_str(sub_query);
-                        return url; // 提前返回，避免添加基础路由的查询参数
-                      }
-                    }
-                  }
-                }
-                _ => {} // NoSubRoute 或 ParseFailed 情况下不添加任何内容
-              }
-            };
-          }
+  let Some((field_name, kind)) = find_sub_router_field(fields) else {
+    // 如果没有子路由字段，返回空代码
+    return quote! {};
+  };
+
+  let append_sub_url = quote! {
+    let sub_url = ::ruled_router::traits::RouteMatcher::format(sub_router);
+    if !sub_url.is_empty() {
+      // 移除子路由URL中的查询部分，因为我们将在最后添加基础路由的查询
+      let (sub_path_part, sub_query_part) = ::ruled_router::utils::split_path_query(&sub_url);
+      url.push_str(sub_path_part);
+      // 如果子路由有查询参数，则使用子路由的查询参数而不是基础路由的
+      if let Some(sub_query) = sub_query_part {
+        if !sub_query.is_empty() {
+          url.push('?');
+          url.push_str(sub_query);
+          return url; // 提前返回，避免添加基础路由的查询参数
         }
       }
-      // 默认情况，假设是 RouteState
-      return quote! {
-        match &self.#field_name {
-          RouteState::SubRoute(ref sub_router) => {
-            let sub_url = sub_router.format();
-            if !sub_url.is_empty() {
-              // 移除子路由URL中的查询部分，因为我们将在最后添加基础路由的查询
-              let (sub_path_part, sub_query_part) = ::ruled_router::utils::split_path_query(&sub_url);
-              url.push_str(sub_path_part);
-              // 如果子路由有查询参数，则使用子路由的查询参数而不是基础路由的
-              if let Some(sub_query) = sub_query_part {
-                if !sub_query.is_empty() {
-                  url.push('?');
-                  url.push_str(sub_query);
-                  return url; // 提前返回，避免添加基础路由的查询参数
-                }
-              }
-            }
-          }
-          _ => {} // NoSubRoute 或 ParseFailed 情况下不添加任何内容
-        }
-      };
     }
+  };
+
+  match kind {
+    SubRouterKind::Option => quote! {
+      if let Some(ref sub_router) = &self.#field_name {
+        #append_sub_url
+      }
+    },
+    // NoSubRoute 或 ParseFailed 情况下不添加任何内容
+    SubRouterKind::RouteState => quote! {
+      if let RouteState::SubRoute(ref sub_router) = &self.#field_name {
+        #append_sub_url
+      }
+    },
+    // 必需的子路由总是存在，无条件追加
+    SubRouterKind::Required => quote! {
+      {
+        let sub_router = &self.#field_name;
+        #append_sub_url
+      }
+    },
   }
-  // 如果没有子路由字段，返回空代码
-  quote! {}
 }
 
 /// 生成查询参数字段名称的实现
-fn generate_query_keys_impl(fields: &[RouteField]) -> Vec<TokenStream> {
+///
+/// `#[router(query = Type)]` 声明的外部查询类型与 `#[query]` 字段一样提供参数名
+fn generate_query_keys_impl(fields: &[RouteField], query_type: Option<&syn::Type>) -> Vec<TokenStream> {
+  if let Some(query_type) = query_type {
+    return vec![quote! {
+      <#query_type as ::ruled_router::traits::Query>::query_keys()
+    }];
+  }
   for (_field_name, field_type, is_query, _) in fields {
     if *is_query {
       return vec![quote! {
@@ -239,11 +297,11 @@ fn generate_query_keys_impl(fields: &[RouteField]) -> Vec<TokenStream> {
 }
 
 /// 生成格式化查询逻辑的代码
-fn generate_format_query_logic(fields: &[(syn::Ident, Type)]) -> TokenStream {
+fn generate_format_query_logic(fields: &[ParsedField]) -> TokenStream {
   if !fields.is_empty() {
     let field_name = &fields[0].0;
     return quote! {
-        let query_string = self.#field_name.format();
+        let query_string = ::ruled_router::traits::Query::format(&self.#field_name);
         if !query_string.is_empty() {
             url.push('?');
             url.push_str(&query_string);
@@ -257,140 +315,350 @@ fn generate_format_query_logic(fields: &[(syn::Ident, Type)]) -> TokenStream {
 }
 
 /// 查找子路由字段的类型
+///
+/// 只根据 `#[sub_router]` 字段声明的类型决定 `SubRouterMatch`，与结构体的命名无关
 fn find_sub_router_type(fields: &[RouteField]) -> TokenStream {
-  // 首先检查是否有 #[sub_router] 字段
-  for (_field_name, field_type, _, is_sub_router) in fields {
-    if *is_sub_router {
-      // 如果有子路由字段，从字段类型中提取内部类型
-      if let Type::Path(type_path) = field_type {
-        if let Some(segment) = type_path.path.segments.last() {
-          if segment.ident == "Option" {
-            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-              if let Some(syn::GenericArgument::Type(inner_type)) = args.args.first() {
-                return quote! { #inner_type };
-              }
-            }
-          } else if segment.ident == "RouteState" {
-            // 处理 RouteState<T> 类型，提取内部的 T
-            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-              if let Some(syn::GenericArgument::Type(inner_type)) = args.args.first() {
-                return quote! { #inner_type };
-              }
-            }
-          } else {
-            // 不是 Option 或 RouteState 类型，直接返回类型
-            return quote! { #field_type };
-          }
-        }
-      }
-      // 如果无法推断，使用默认值
-      return quote! { ::ruled_router::traits::NoSubRouter };
-    }
+  let Some((_, field_type, _, _)) = fields.iter().find(|(_, _, _, is_sub_router)| *is_sub_router) else {
+    // 没有子路由字段
+    return quote! { ::ruled_router::traits::NoSubRouter };
+  };
+
+  // 直接使用字段声明的类型，Option<T> 和 RouteState<T> 取出其中的 T
+  match sub_router_kind(field_type) {
+    SubRouterKind::Required => quote! { #field_type },
+    SubRouterKind::Option | SubRouterKind::RouteState => match first_generic_argument(field_type) {
+      Some(inner_type) => quote! { #inner_type },
+      None => quote! { ::ruled_router::traits::NoSubRouter },
+    },
   }
+}
 
-  // 如果没有子路由字段，使用默认值
-  quote! { ::ruled_router::traits::NoSubRouter }
+/// 获取 `Option<T>` 中的 T，字段不是 Option 时返回 None
+fn option_inner_type(field_type: &Type) -> Option<&Type> {
+  match field_type {
+    Type::Path(type_path) if type_path.path.segments.last()?.ident == "Option" => first_generic_argument(field_type),
+    _ => None,
+  }
+}
+
+/// 取出 `Wrapper<T>` 中的第一个类型参数 T
+fn first_generic_argument(field_type: &Type) -> Option<&Type> {
+  let Type::Path(type_path) = field_type else {
+    return None;
+  };
+  let syn::PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments else {
+    return None;
+  };
+  match args.args.first()? {
+    syn::GenericArgument::Type(inner_type) => Some(inner_type),
+    _ => None,
+  }
 }
 
 /// 生成子路由字段的解析代码
+///
+/// 生成的代码位于 parse_with_sub 中，`sub_router_state` 已经计算完成
 fn generate_parse_sub_router_field(fields: &[RouteField]) -> TokenStream {
-  for (field_name, field_type, _, is_sub_router) in fields {
-    if *is_sub_router {
-      // 检查字段类型是 Option 还是 RouteState
-      if let Type::Path(type_path) = field_type {
-        if let Some(segment) = type_path.path.segments.last() {
-          if segment.ident == "Option" {
-            // 对于 Option 类型，使用 None
-            return quote! {
-              #field_name: None,
-            };
-          } else if segment.ident == "RouteState" {
-            // 对于 RouteState 类型，使用 no_sub_route()
-            return quote! {
-              #field_name: RouteState::no_sub_route(),
-            };
-          }
+  let Some((field_name, kind)) = find_sub_router_field(fields) else {
+    return quote! {};
+  };
+
+  match kind {
+    // 可选的子路由字段先置空，由 parse 写回解析结果
+    SubRouterKind::Option => quote! {
+      #field_name: None,
+    },
+    SubRouterKind::RouteState => quote! {
+      #field_name: RouteState::no_sub_route(),
+    },
+    // 必需的子路由字段只能在子路由解析成功时构造
+    SubRouterKind::Required => quote! {
+      #field_name: match &sub_router_state {
+        RouteState::SubRoute(sub_match) => ::std::clone::Clone::clone(sub_match),
+        RouteState::NoSubRoute => {
+          return Err(::ruled_router::error::ParseError::invalid_path(format!(
+            "Missing required sub route for path: {}",
+            path
+          )));
         }
-      }
-      // 默认情况，假设是 RouteState
-      return quote! {
-        #field_name: RouteState::no_sub_route(),
+        RouteState::ParseFailed { remaining_path, .. } => {
+          return Err(::ruled_router::error::ParseError::invalid_path(format!(
+            "No matching sub route found for path: {}",
+            remaining_path
+          )));
+        }
+      },
+    },
+  }
+}
+
+/// 生成 parse 方法体
+///
+/// 没有子路由字段时严格匹配完整路径；有子路由字段时复用 parse_with_sub，
+/// 并把递归解析得到的子路由状态写回字段
+fn generate_parse_body(fields: &[RouteField], parse_fields: &[TokenStream]) -> TokenStream {
+  let Some((field_name, kind)) = find_sub_router_field(fields) else {
+    return quote! {
+      let (path_part, query_part) = ::ruled_router::utils::split_path_query(path);
+      let (_, params) = match_route_patterns(path_part, true)?;
+
+      // 解析查询参数
+      let query_map = if let Some(query_str) = query_part {
+          ::ruled_router::utils::parse_query_string(query_str)?
+      } else {
+          ::std::collections::HashMap::new()
       };
-    }
+
+      Ok(Self {
+          #(#parse_fields,)*
+      })
+    };
+  };
+
+  match kind {
+    // Option 类型无法表达解析失败，子路径无法匹配时返回错误
+    SubRouterKind::Option => quote! {
+      let (mut router, sub_router_state) = Self::parse_with_sub(path)?;
+      router.#field_name = match sub_router_state {
+        RouteState::SubRoute(sub_match) => Some(sub_match),
+        RouteState::NoSubRoute => None,
+        RouteState::ParseFailed { remaining_path, .. } => {
+          return Err(::ruled_router::error::ParseError::invalid_path(format!(
+            "No matching sub route found for path: {}",
+            remaining_path
+          )));
+        }
+      };
+      Ok(router)
+    },
+    SubRouterKind::RouteState => quote! {
+      let (mut router, sub_router_state) = Self::parse_with_sub(path)?;
+      router.#field_name = sub_router_state;
+      Ok(router)
+    },
+    // 必需的子路由已经在 parse_with_sub 中填充
+    SubRouterKind::Required => quote! {
+      Self::parse_with_sub(path).map(|(router, _)| router)
+    },
+  }
+}
+
+/// 生成 parse_nested 方法体
+///
+/// 剩余路径交给子路由的 try_parse_nested 严格解析，任何错误都直接返回
+fn generate_parse_nested_body(fields: &[RouteField], parse_fields: &[TokenStream]) -> TokenStream {
+  let Some((field_name, kind)) = find_sub_router_field(fields) else {
+    return quote! { Self::parse(path) };
+  };
+
+  let sub_router_value = match kind {
+    SubRouterKind::Option => quote! { sub_match },
+    SubRouterKind::RouteState => quote! { RouteState::from_option(sub_match) },
+    SubRouterKind::Required => quote! {
+      match sub_match {
+        Some(sub_match) => sub_match,
+        None => {
+          return Err(::ruled_router::error::ParseError::invalid_path(format!(
+            "Missing required sub route for path: {}",
+            path
+          )));
+        }
+      }
+    },
+  };
+
+  quote! {
+    let (path_part, query_part) = ::ruled_router::utils::split_path_query(path);
+    let (consumed, params) = match_route_patterns(path_part, false)?;
+
+    // 解析查询参数
+    let query_map = if let Some(query_str) = query_part {
+        ::ruled_router::utils::parse_query_string(query_str)?
+    } else {
+        ::std::collections::HashMap::new()
+    };
+
+    // 剩余路径必须被子路由完整消费
+    let remaining_path = &path[consumed..];
+    let (remaining_path_part, _) = ::ruled_router::utils::split_path_query(remaining_path);
+    let sub_match = if !remaining_path_part.trim_matches('/').is_empty() {
+        Some(<Self::SubRouterMatch as ::ruled_router::traits::RouteMatcher>::try_parse_nested(remaining_path)?)
+    } else {
+        None
+    };
+
+    Ok(Self {
+        #(#parse_fields,)*
+        #field_name: #sub_router_value,
+    })
   }
-  quote! {}
 }
 
 /// Expand the Router derive macro
 pub fn expand_route_derive(input: DeriveInput) -> syn::Result<TokenStream> {
   let struct_name = &input.ident;
-  let (pattern, _query_type) = extract_route_config(&input)?;
+  let RouteConfig {
+    pattern: pattern_lit,
+    aliases,
+    std_traits,
+    query_type,
+  } = extract_route_config(&input)?;
+  let pattern = pattern_lit.value();
   let fields = extract_route_fields(&input.data)?;
 
-  // 分析路径模式，提取参数名
-  let param_names = extract_path_params(&pattern);
+  // 在宏展开时校验路径模式，错误指向 pattern 字面量
+  let pattern_params = parse_pattern_params(&pattern)
+    .map_err(|reason| syn::Error::new_spanned(&pattern_lit, format!("Invalid route pattern \"{pattern}\": {reason}")))?;
+  // 别名与主模式绑定同一组参数，解析结果才能填充同一组字段
+  for alias_lit in &aliases {
+    let alias = alias_lit.value();
+    let alias_params = parse_pattern_params(&alias)
+      .map_err(|reason| syn::Error::new_spanned(alias_lit, format!("Invalid alias pattern \"{alias}\": {reason}")))?;
+    let same_params = alias_params.len() == pattern_params.len() && alias_params.iter().all(|param| pattern_params.contains(param));
+    if !same_params {
+      return Err(syn::Error::new_spanned(
+        alias_lit,
+        format!("Alias pattern \"{alias}\" must bind the same parameters as \"{pattern}\""),
+      ));
+    }
+  }
+  // 带约束的模式只能在启用 `regex` feature 时解析，引用只在该 feature 下存在的类型，未启用时在编译期报错
+  let require_regex: Vec<TokenStream> = std::iter::once(&pattern_lit)
+    .chain(&aliases)
+    .filter(|lit| has_constraints(&lit.value()))
+    .map(|lit| {
+      quote_spanned! {lit.span()=>
+        const _: ::core::option::Option<::ruled_router::__private::RequireRegexFeature> = ::core::option::Option::None;
+      }
+    })
+    .collect();
+  // 所有参数都绑定到同名字段：通配符捕获剩余的全部路径（包括 '/'），可选参数对应 Option<T> 字段
+  let optional_params: Vec<String> = pattern_params
+    .iter()
+    .filter(|param| param.kind == ParamKind::Optional)
+    .map(|param| param.name.clone())
+    .collect();
+  let param_names: Vec<String> = pattern_params.into_iter().map(|param| param.name).collect();
+
+  if matches!(fields.first(), Some((syn::Member::Unnamed(_), ..))) {
+    // 元组结构体按位置绑定，字段数必须与模式参数数一致
+    if fields.len() != param_names.len() {
+      return Err(syn::Error::new_spanned(
+        &pattern_lit,
+        format!(
+          "Pattern \"{pattern}\" has {} parameter(s) but `{struct_name}` has {} tuple field(s)",
+          param_names.len(),
+          fields.len()
+        ),
+      ));
+    }
+  } else {
+    // 既不是查询参数也不是子路由的字段必须对应模式中的参数，否则它永远不会被填充
+    for (field_name, _, is_query, is_sub_router) in &fields {
+      let syn::Member::Named(field_ident) = field_name else { continue };
+      if !is_query && !is_sub_router && !param_names.iter().any(|name| field_ident == name) {
+        return Err(syn::Error::new_spanned(
+          field_ident,
+          format!(
+            "Field `{field_ident}` does not match any parameter in pattern \"{pattern}\"; \
+             add `:{field_ident}` to the pattern or mark the field with #[query] or #[sub_router]"
+          ),
+        ));
+      }
+    }
+
+    // 模式中的每个参数都需要一个同名的路径字段
+    for param_name in &param_names {
+      let has_field = fields.iter().any(|(field_name, _, is_query, is_sub_router)| {
+        matches!(field_name, syn::Member::Named(ident) if ident == param_name) && !is_query && !is_sub_router
+      });
+      if !has_field {
+        return Err(syn::Error::new_spanned(
+          &pattern_lit,
+          format!("Pattern parameter `{param_name}` has no matching field in `{struct_name}`"),
+        ));
+      }
+    }
+  }
 
   // 分离路径字段和查询字段
   let (path_fields, query_fields) = separate_fields(&fields, &param_names);
 
+  // 外部查询类型独占查询字符串，与 #[query] 字段同时使用时两者会争夺同一组键
+  if let (Some(query_type), false) = (&query_type, query_fields.is_empty()) {
+    return Err(syn::Error::new_spanned(
+      query_type,
+      "`query = Type` cannot be combined with #[query] fields; move those fields into the query type",
+    ));
+  }
+
+  for (_, field_type, param_name) in &path_fields {
+    if optional_params.contains(param_name) && option_inner_type(field_type).is_none() {
+      return Err(syn::Error::new_spanned(
+        field_type,
+        format!("Optional parameter `{param_name}` must be bound to an `Option<T>` field"),
+      ));
+    }
+  }
+
   // 查找子路由字段
   let sub_router_type = find_sub_router_type(&fields);
 
   // 生成解析逻辑
-  let parse_path_fields = generate_parse_path_fields(&path_fields, &param_names)?;
+  let parse_path_fields = generate_parse_path_fields(&path_fields, &optional_params);
   let parse_query_fields = generate_parse_query_fields(&query_fields);
   let parse_sub_router_field = generate_parse_sub_router_field(&fields);
+  let parse_body = generate_parse_body(&fields, &[parse_path_fields.clone(), parse_query_fields.clone()].concat());
+  let parse_nested_body = generate_parse_nested_body(&fields, &[parse_path_fields.clone(), parse_query_fields.clone()].concat());
 
   // 生成格式化逻辑
-  let format_path_fields = generate_format_path_fields(&path_fields);
+  let format_path_fields = generate_format_path_fields(&path_fields, &optional_params);
   let format_query_logic = generate_format_query_logic(&query_fields);
   let format_sub_router_logic = generate_format_sub_router_logic(&fields);
-  let query_keys_impl = generate_query_keys_impl(&fields);
+  let query_keys_impl = generate_query_keys_impl(&fields, query_type.as_ref());
+  let sub_route_info_body = generate_sub_route_info_body(&fields);
+  let (match_patterns_fn, aliases_impl) = generate_match_patterns_fn(&pattern, &aliases);
+  let std_traits_impl = if std_traits {
+    generate_std_traits_impl(struct_name)
+  } else {
+    quote! {}
+  };
+  let (validate_query_type, query_type_impl) = match &query_type {
+    Some(query_type) => generate_query_type_impl(struct_name, query_type),
+    None => (quote! {}, quote! {}),
+  };
+  // 没有子路由时 parse_nested 直接调用 parse，已经包含校验
+  let validate_query_type_nested = if find_sub_router_field(&fields).is_some() {
+    validate_query_type.clone()
+  } else {
+    quote! {}
+  };
 
   let expanded = quote! {
       const _: () = {
           use ::ruled_router::error::RouteState;
 
+          #(#require_regex)*
+
+          #match_patterns_fn
+
           impl ::ruled_router::traits::RouterData for #struct_name {
           type SubRouterMatch = #sub_router_type;
 
           fn parse(path: &str) -> Result<Self, ::ruled_router::error::ParseError> {
-              let (path_part, query_part) = ::ruled_router::utils::split_path_query(path);
-              let parser = ::ruled_router::parser::PathParser::new(#pattern)?;
-              let params = parser.match_path(path_part)?;
-
-              // 解析查询参数
-              let query_map = if let Some(query_str) = query_part {
-                  ::ruled_router::utils::parse_query_string(query_str)?
-              } else {
-                  ::std::collections::HashMap::new()
-              };
-
-              Ok(Self {
-                  #(#parse_path_fields,)*
-                  #(#parse_query_fields,)*
-                  #parse_sub_router_field
-              })
+              // 空路径和只有查询参数的 URL 按根路径 "/" 解析
+              let path = &*::ruled_router::utils::normalize_empty_path(path);
+              #validate_query_type
+              #parse_body
           }
 
           fn parse_with_sub(path: &str) -> Result<(Self, RouteState<Self::SubRouterMatch>), ::ruled_router::error::ParseError> {
+              let path = &*::ruled_router::utils::normalize_empty_path(path);
+              #validate_query_type
               let (path_part, query_part) = ::ruled_router::utils::split_path_query(path);
-              let parser = ::ruled_router::parser::PathParser::new(#pattern)?;
 
-              // 计算当前模式应该消费的路径长度
-              let consumed = parser.consumed_length(path_part)?;
-
-              // 只解析当前路由模式匹配的部分
-              let current_path_part = &path_part[..consumed.min(path_part.len())];
-
-              // 尝试匹配当前路由的模式
-              let params = match parser.match_path(current_path_part) {
-                  Ok(params) => params,
-                  Err(_) => {
-                      // 如果无法匹配，尝试用完整路径解析（向后兼容）
-                      return Self::parse(path).map(|router| (router, RouteState::no_sub_route()));
-                  }
-              };
+              // 只解析当前路由模式匹配的开头部分，consumed 为其长度
+              let (consumed, params) = match_route_patterns(path_part, false)?;
 
               // 解析查询参数
               let query_map = if let Some(query_str) = query_part {
@@ -399,22 +667,24 @@ pub fn expand_route_derive(input: DeriveInput) -> syn::Result<TokenStream> {
                   ::std::collections::HashMap::new()
               };
 
-              let router = Self {
-                  #(#parse_path_fields,)*
-                  #(#parse_query_fields,)*
-                  #parse_sub_router_field
-              };
-
-              // 尝试解析子路由
+              // 尝试解析子路由，剩余部分只有查询参数或尾部斜杠时视为没有子路由
               let remaining_path = &path[consumed..];
-              let sub_router_state = if !remaining_path.is_empty() {
-                  match Self::SubRouterMatch::try_parse(remaining_path) {
+              let (remaining_path_part, _) = ::ruled_router::utils::split_path_query(remaining_path);
+              let sub_router_state = if !remaining_path_part.trim_matches('/').is_empty() {
+                  match <Self::SubRouterMatch as ::ruled_router::traits::RouteMatcher>::try_parse(remaining_path) {
                       Ok(sub_match) => RouteState::sub_route(sub_match),
                       Err(parse_error) => {
+                          // 记录尝试过的子路由模式，并找出字面量前缀最接近的一个
+                          let patterns = <Self::SubRouterMatch as ::ruled_router::traits::RouteMatcher>::patterns();
+                          let closest_match = ::ruled_router::error::ClosestMatch::find(
+                              remaining_path_part,
+                              &patterns,
+                              parse_error.to_string(),
+                          );
                           RouteState::parse_failed(
                               remaining_path.to_string(),
-                              vec![], // TODO: 可以在后续版本中添加更详细的路由信息
-                              None // TODO: 可以在后续版本中添加最接近匹配信息
+                              patterns.iter().map(|pattern| pattern.to_string()).collect(),
+                              closest_match,
                           )
                       }
                   }
@@ -422,9 +692,21 @@ pub fn expand_route_derive(input: DeriveInput) -> syn::Result<TokenStream> {
                   RouteState::no_sub_route()
               };
 
+              let router = Self {
+                  #(#parse_path_fields,)*
+                  #(#parse_query_fields,)*
+                  #parse_sub_router_field
+              };
+
               Ok((router, sub_router_state))
           }
 
+          fn parse_nested(path: &str) -> Result<Self, ::ruled_router::error::ParseError> {
+              let path = &*::ruled_router::utils::normalize_empty_path(path);
+              #validate_query_type_nested
+              #parse_nested_body
+          }
+
           fn format(&self) -> String {
               // format 方法现在调用 format_sub_router，保持向后兼容性
               self.format_sub_router()
@@ -434,8 +716,8 @@ pub fn expand_route_derive(input: DeriveInput) -> syn::Result<TokenStream> {
               let mut params = ::std::collections::HashMap::new();
               #(#format_path_fields)*
 
-              let formatter = ::ruled_router::formatter::PathFormatter::new(#pattern).unwrap();
-              let mut url = formatter.format(&params).unwrap();
+              // 所有参数都由字段填充，格式化不会缺少参数；即使失败也返回模式本身，而不是 panic
+              let mut url = PATH_PARSERS[0].format_path(&params).unwrap_or_else(|_| #pattern.to_string());
 
               #format_sub_router_logic
 
@@ -448,25 +730,146 @@ pub fn expand_route_derive(input: DeriveInput) -> syn::Result<TokenStream> {
               #pattern
           }
 
+          #aliases_impl
+
+          fn path_parser_caches() -> &'static [::ruled_router::parser::PathParserCache] {
+              &PATH_PARSERS
+          }
+
           fn query_keys() -> Vec<&'static str> {
                #(#query_keys_impl)*
            }
+
+          fn sub_route_info(&self) -> Option<Box<::ruled_router::traits::RouteInfo>> {
+              #sub_route_info_body
+          }
       }
 
           impl ::ruled_router::traits::ToRouteInfo for #struct_name {
               fn to_route_info(&self) -> ::ruled_router::traits::RouteInfo {
-                  // 对于没有子路由的情况，直接返回 None
-                  let sub_route_info = None;
-
                   ::ruled_router::traits::RouteInfo {
-                      pattern: Self::pattern(),
-                      formatted: self.format(),
-                      sub_route_info,
+                      pattern: <Self as ::ruled_router::traits::RouterData>::pattern(),
+                      formatted: ::ruled_router::traits::RouterData::format(self),
+                      sub_route_info: ::ruled_router::traits::RouterData::sub_route_info(self),
                   }
               }
           }
+
+          #std_traits_impl
+
+          #query_type_impl
       };
   };
 
   Ok(expanded)
 }
+
+/// 生成主模式和别名的解析器缓存、`match_route_patterns` 辅助函数和 `aliases` 方法
+///
+/// 所有模式的解析器放在同一个静态数组中，主模式在前，`RouterData::path_parser_caches` 直接返回该数组。
+/// `match_route_patterns(path_part, whole)` 先尝试主模式，失败后按声明顺序尝试别名，
+/// 返回第一个匹配的模式消费的长度和参数；都不匹配时返回主模式的错误。
+/// `whole` 为 true 时要求匹配整个路径，否则只匹配模式覆盖的开头部分
+fn generate_match_patterns_fn(pattern: &str, aliases: &[syn::LitStr]) -> (TokenStream, TokenStream) {
+  let parser_count = aliases.len() + 1;
+
+  let match_patterns_fn = quote! {
+      // 模式是编译期常量，编译后的解析器在所有解析和格式化调用之间共享
+      static PATH_PARSERS: [::ruled_router::parser::PathParserCache; #parser_count] = [
+          ::ruled_router::parser::PathParserCache::new(#pattern),
+          #(::ruled_router::parser::PathParserCache::new(#aliases),)*
+      ];
+
+      fn match_route_patterns(
+          path_part: &str,
+          whole: bool,
+      ) -> Result<(usize, ::std::collections::HashMap<String, String>), ::ruled_router::error::ParseError> {
+          let match_parser = |parser: &::ruled_router::parser::PathParser| {
+              let consumed = if whole {
+                  path_part.len()
+              } else {
+                  parser.consumed_length(path_part)?.min(path_part.len())
+              };
+              parser.match_path(&path_part[..consumed]).map(|params| (consumed, params))
+          };
+
+          let primary = match_parser(PATH_PARSERS[0].get()?);
+          if primary.is_err() {
+              for cache in &PATH_PARSERS[1..] {
+                  if let Ok(matched) = match_parser(cache.get()?) {
+                      return Ok(matched);
+                  }
+              }
+          }
+          primary
+      }
+  };
+
+  let aliases_impl = if aliases.is_empty() {
+    quote! {}
+  } else {
+    quote! {
+        fn aliases() -> &'static [&'static str] {
+            &[#(#aliases),*]
+        }
+    }
+  };
+
+  (match_patterns_fn, aliases_impl)
+}
+
+/// 生成 `#[router(query = Type)]` 的查询校验语句和访问查询的固有方法
+///
+/// 校验语句放在 parse/parse_with_sub/parse_nested 开头：查询字符串无法解析为外部查询类型时整个路由解析失败
+fn generate_query_type_impl(struct_name: &syn::Ident, query_type: &syn::Type) -> (TokenStream, TokenStream) {
+  let validate = quote! {
+      let (_, query_part) = ::ruled_router::utils::split_path_query(path);
+      <#query_type as ::ruled_router::traits::Query>::parse(query_part.unwrap_or(""))?;
+  };
+
+  let methods = quote! {
+      impl #struct_name {
+          /// 解析 URL 中的查询部分，得到路由声明的查询类型
+          pub fn parse_query(url: &str) -> Result<#query_type, ::ruled_router::error::ParseError> {
+              let (_, query, _) = ::ruled_router::utils::split_url(url);
+              <#query_type as ::ruled_router::traits::Query>::parse(query.unwrap_or(""))
+          }
+
+          /// 同时解析路由和查询参数
+          ///
+          /// 路由部分使用完整的 URL 解析，查询校验因此看到的是实际的查询字符串
+          pub fn parse_with_query(url: &str) -> Result<(Self, #query_type), ::ruled_router::error::ParseError> {
+              let route = <Self as ::ruled_router::traits::RouterData>::parse(url)?;
+              Ok((route, Self::parse_query(url)?))
+          }
+
+          /// 格式化路由并追加查询参数
+          ///
+          /// 不使用 `format_with_query` 这个名字，避免遮蔽 `RouterData::format_with_query`
+          pub fn format_with(&self, query: &#query_type) -> String {
+              ::ruled_router::utils::build_url(self, query)
+          }
+      }
+  };
+
+  (validate, methods)
+}
+
+/// 生成 `Display` 和 `FromStr` 实现（`#[router(std_traits)]`）
+fn generate_std_traits_impl(struct_name: &syn::Ident) -> TokenStream {
+  quote! {
+      impl ::std::fmt::Display for #struct_name {
+          fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+              f.write_str(&::ruled_router::traits::RouterData::format(self))
+          }
+      }
+
+      impl ::std::str::FromStr for #struct_name {
+          type Err = ::ruled_router::error::ParseError;
+
+          fn from_str(s: &str) -> Result<Self, Self::Err> {
+              <Self as ::ruled_router::traits::RouterData>::parse(s)
+          }
+      }
+  }
+}
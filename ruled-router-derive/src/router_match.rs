@@ -2,7 +2,7 @@
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, Variant};
+use syn::{Data, DeriveInput, Fields, Lit, Meta, Variant};
 
 /// 提取枚举变体信息
 fn extract_enum_variants(data: &Data) -> syn::Result<Vec<&Variant>> {
@@ -27,67 +27,191 @@ fn extract_route_type(variant: &Variant) -> syn::Result<&syn::Type> {
   }
 }
 
+/// 提取变体上的 `#[route(prefix = "...")]`
+///
+/// 多个变体的模式共享前缀时，可以用显式前缀决定哪个变体负责哪些路径
+fn extract_explicit_prefix(variant: &Variant) -> syn::Result<Option<syn::LitStr>> {
+  let mut prefix = None;
+
+  for attr in &variant.attrs {
+    if attr.path().is_ident("route") {
+      let parser = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
+      for meta in parser {
+        match meta {
+          Meta::NameValue(name_value) if name_value.path.is_ident("prefix") => {
+            let lit_str = match &name_value.value {
+              syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit_str), ..
+              }) => lit_str.clone(),
+              other => return Err(syn::Error::new_spanned(other, "prefix expects a string literal")),
+            };
+            if !lit_str.value().starts_with('/') {
+              return Err(syn::Error::new_spanned(&lit_str, "Route prefix must start with '/'"));
+            }
+            prefix = Some(lit_str);
+          }
+          Meta::Path(path) if path.is_ident("matcher") => {}
+          other => {
+            return Err(syn::Error::new_spanned(
+              other,
+              "Unknown route attribute on variant, expected `prefix = \"...\"` or `matcher`",
+            ))
+          }
+        }
+      }
+    }
+  }
+
+  Ok(prefix)
+}
+
+/// 变体是否用 `#[route(matcher)]` 标记为包装另一个 RouteMatcher
+///
+/// 宏展开时无法得知字段类型实现的是 RouterData 还是 RouteMatcher，因此嵌套的匹配器需要显式标记。
+/// 被包装的匹配器不消耗路径，它的所有路由直接参与当前层级的匹配
+fn is_matcher_variant(variant: &Variant) -> syn::Result<bool> {
+  for attr in &variant.attrs {
+    if attr.path().is_ident("route") {
+      let parser = attr.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)?;
+      if parser
+        .iter()
+        .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident("matcher")))
+      {
+        return Ok(true);
+      }
+    }
+  }
+  Ok(false)
+}
+
+/// 生成嵌套匹配器的得分表达式
+///
+/// 在匹配器的模式中找出能够匹配路径开头的模式，取其中最长的字面量前缀；都不匹配时为 None
+fn matcher_score_expr(route_type: &syn::Type, path: TokenStream) -> TokenStream {
+  quote! {
+    <#route_type as ::ruled_router::traits::RouteMatcher>::match_score(#path)
+  }
+}
+
+/// 生成 match_score 方法的实现
+///
+/// 路由变体使用缓存的解析器逐个计算，嵌套匹配器交给它自己的 match_score
+fn generate_match_score_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
+  let mut scores = Vec::new();
+
+  for variant in variants {
+    let route_type = extract_route_type(variant)?;
+    let score = if is_matcher_variant(variant)? {
+      matcher_score_expr(route_type, quote! { path })
+    } else {
+      quote! {
+        ::ruled_router::__private::route_parsers::<#route_type>()
+          .filter_map(|parser| parser.ok()?.prefix_match_score(path))
+          .max()
+      }
+    };
+    scores.push(score);
+  }
+
+  Ok(quote! {
+    fn match_score(path: &str) -> Option<usize> {
+      [#(#scores),*].into_iter().flatten().max()
+    }
+  })
+}
+
 /// 自动从路由结构体的 RouterData trait 实现中获取 pattern
-/// 不再支持手动指定 #[route] 或 #[route_prefix] 属性，完全依赖自动提取
-fn extract_route_prefix(variant: &Variant) -> syn::Result<Option<TokenStream>> {
-  // 直接从路由结构体的 RouterData trait 获取 pattern
+fn extract_route_pattern(variant: &Variant) -> syn::Result<Option<TokenStream>> {
   let route_type = extract_route_type(variant)?;
   Ok(Some(quote! { <#route_type as ::ruled_router::traits::RouterData>::pattern() }))
 }
 
-/// 生成 try_parse 方法的实现
-/// 这个实现会根据 route_prefix 属性进行前缀匹配，然后尝试解析
+/// 获取变体的路由前缀
+///
+/// 优先使用 `#[route(prefix = "...")]`，否则回退到路由结构体的 pattern
+fn extract_route_prefix(variant: &Variant) -> syn::Result<Option<TokenStream>> {
+  match extract_explicit_prefix(variant)? {
+    Some(prefix) => Ok(Some(quote! { #prefix })),
+    None => extract_route_pattern(variant),
+  }
+}
+
+/// 生成显式前缀的检查条件，前缀必须在段边界结束；没有显式前缀时恒为 true
+fn generate_explicit_prefix_guard(variant: &Variant, path: TokenStream) -> syn::Result<TokenStream> {
+  Ok(match extract_explicit_prefix(variant)? {
+    Some(prefix) => quote! {
+      (#path.starts_with(#prefix) && matches!(#path.as_bytes().get(#prefix.len()), None | Some(b'/') | Some(b'?')))
+    },
+    None => quote! { true },
+  })
+}
+
+/// 生成 match_with_params 与 try_parse 方法的实现
+///
+/// 依次尝试所有变体，在能够解析的变体中选择模式开头字面量最长的一个，
+/// 长度相同时按声明顺序取第一个，因此 `/users/new` 总是优先于 `/users/:id`。
+/// 胜出变体的路径参数在匹配时一并保留，try_parse 直接复用这一结果
 fn generate_try_parse_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
   let mut match_arms = Vec::new();
 
   for variant in variants {
     let variant_name = &variant.ident;
     let route_type = extract_route_type(variant)?;
-    let route_prefix = extract_route_prefix(variant)?;
-
-    let match_arm = if let Some(prefix_expr) = route_prefix {
-      // 如果有 route_prefix 或 route 属性，先检查前缀匹配，然后解析
-      quote! {
-        {
-          let prefix = #prefix_expr;
-          if path.starts_with(prefix) {
-            // 分离路径和查询参数
-            let (path_part, query_part) = ::ruled_router::utils::split_path_query(path);
-
-            if path_part.starts_with(prefix) {
-              // 构造完整的路径用于解析（前缀 + 查询参数）
-              let full_path = if let Some(query) = query_part {
-                format!("{}?{}", prefix, query)
-              } else {
-                prefix.to_string()
-              };
-
-              // 尝试使用 parse_with_sub 进行递归解析
-              if let Ok((route, sub_router_state)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(path) {
-                // 无论是否有子路由，都直接返回解析结果
-                // 子路由信息已经包含在 parse_with_sub 的结果中
-                return Ok(Self::#variant_name(route));
-              }
-              // 如果递归解析失败，回退到普通解析
-              if let Ok(route) = <#route_type as ::ruled_router::traits::RouterData>::parse(&full_path) {
-                return Ok(Self::#variant_name(route));
-              }
-            }
+    let prefix_guard = generate_explicit_prefix_guard(variant, quote! { path_part })?;
+
+    if is_matcher_variant(variant)? {
+      // 嵌套匹配器按其中最具体的可匹配模式参与比较，解析交给它自己的 match_with_params
+      let score_expr = matcher_score_expr(route_type, quote! { path_part });
+      match_arms.push(quote! {
+        'variant: {
+          let (path_part, _) = ::ruled_router::utils::split_path_query(path);
+          if !#prefix_guard {
+            break 'variant;
+          }
+          let Some(score) = #score_expr else {
+            break 'variant;
+          };
+          if best.as_ref().is_some_and(|(best_score, _, _)| *best_score >= score) {
+            break 'variant;
+          }
+          if let Ok((route, params)) = <#route_type as ::ruled_router::traits::RouteMatcher>::match_with_params(path) {
+            best = Some((score, Self::#variant_name(route), params));
           }
         }
-      }
-    } else {
-      // 这个分支现在不会被执行，因为我们总是返回 Some
-      quote! {
-        // 尝试使用 parse_with_sub 进行递归解析
-          if let Ok((route, sub_router_state)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(path) {
-            // 无论是否有子路由，都直接返回解析结果
-            // 子路由信息已经包含在 parse_with_sub 的结果中
-            return Ok(Self::#variant_name(route));
+      });
+      continue;
+    }
+
+    // 先检查前缀匹配，然后解析
+    let match_arm = quote! {
+      'variant: {
+        // 分离路径和查询参数
+        let (path_part, _) = ::ruled_router::utils::split_path_query(path);
+        if !#prefix_guard {
+          break 'variant;
+        }
+
+        // 依次尝试主模式和别名，按模式计算当前路由消耗的前缀，并确认前缀能够匹配（支持参数段）
+        for parser in ::ruled_router::__private::route_parsers::<#route_type>() {
+          let parser = parser?;
+          let consumed = parser.consumed_length(path_part)?;
+          let route_path = &path_part[..consumed];
+
+          if let Ok(params) = parser.match_path(route_path) {
+            // 已有同样具体或更具体的候选时跳过
+            let score = parser.literal_prefix_len();
+            if best.as_ref().is_some_and(|(best_score, _, _)| *best_score >= score) {
+              break 'variant;
+            }
+
+            // 使用 parse 解析完整路径，子路由字段会被自动填充；
+            // 剩余路径无法被子路由消费时整个变体不匹配，不会丢弃剩余路径
+            if let Ok(route) = <#route_type as ::ruled_router::traits::RouterData>::parse(path) {
+              best = Some((score, Self::#variant_name(route), params));
+            }
+            // parse 自身会尝试所有模式，不再用其余别名重复解析
+            break 'variant;
           }
-        // 如果递归解析失败，回退到普通解析
-        if let Ok(route) = <#route_type as ::ruled_router::traits::RouterData>::parse(path) {
-          return Ok(Self::#variant_name(route));
         }
       }
     };
@@ -96,7 +220,17 @@ fn generate_try_parse_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
 
   Ok(quote! {
     fn try_parse(path: &str) -> Result<Self, ::ruled_router::error::ParseError> {
+      <Self as ::ruled_router::traits::RouteMatcher>::match_with_params(path).map(|(route, _)| route)
+    }
+
+    fn match_with_params(
+      path: &str,
+    ) -> Result<(Self, ::std::collections::HashMap<String, String>), ::ruled_router::error::ParseError> {
+      let mut best: Option<(usize, Self, ::std::collections::HashMap<String, String>)> = None;
       #(#match_arms)*
+      if let Some((_, route, params)) = best {
+        return Ok((route, params));
+      }
       Err(::ruled_router::error::ParseError::invalid_path(
         format!("No matching route found for path: {}", path)
       ))
@@ -104,48 +238,160 @@ fn generate_try_parse_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
   })
 }
 
+/// 生成 try_parse_nested 方法的实现
+///
+/// 按顺序找到前缀匹配的变体后调用 parse_nested 严格解析所有层级，
+/// 返回第一个前缀匹配变体的错误，便于定位无法消费的剩余路径
+fn generate_try_parse_nested_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
+  let mut match_arms = Vec::new();
+
+  for variant in variants {
+    let variant_name = &variant.ident;
+    let route_type = extract_route_type(variant)?;
+    let prefix_guard = generate_explicit_prefix_guard(variant, quote! { path_part })?;
+
+    if is_matcher_variant(variant)? {
+      let score_expr = matcher_score_expr(route_type, quote! { path_part });
+      match_arms.push(quote! {
+        if #prefix_guard && (#score_expr).is_some() {
+          match <#route_type as ::ruled_router::traits::RouteMatcher>::try_parse_nested(path) {
+            Ok(route) => return Ok(Self::#variant_name(route)),
+            Err(err) => {
+              first_error.get_or_insert(err);
+            }
+          }
+        }
+      });
+      continue;
+    }
+
+    match_arms.push(quote! {
+      if #prefix_guard {
+        // 主模式或任一别名匹配路径开头时才交给 parse_nested
+        let mut prefix_matched = false;
+        for parser in ::ruled_router::__private::route_parsers::<#route_type>() {
+          let parser = parser?;
+          if let Ok(consumed) = parser.consumed_length(path_part) {
+            if parser.match_path(&path_part[..consumed]).is_ok() {
+              prefix_matched = true;
+              break;
+            }
+          }
+        }
+        if prefix_matched {
+          match <#route_type as ::ruled_router::traits::RouterData>::parse_nested(path) {
+            Ok(route) => return Ok(Self::#variant_name(route)),
+            Err(err) => {
+              first_error.get_or_insert(err);
+            }
+          }
+        }
+      }
+    });
+  }
+
+  Ok(quote! {
+    fn try_parse_nested(path: &str) -> Result<Self, ::ruled_router::error::ParseError> {
+      let (path_part, _) = ::ruled_router::utils::split_path_query(path);
+      let mut first_error: Option<::ruled_router::error::ParseError> = None;
+      #(#match_arms)*
+      Err(first_error.unwrap_or_else(|| {
+        ::ruled_router::error::ParseError::invalid_path(format!("No matching route found for path: {}", path))
+      }))
+    }
+  })
+}
+
 /// 生成 format 方法的实现
-fn generate_format_impl(variants: &[&Variant]) -> TokenStream {
+fn generate_format_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
   let mut match_arms = Vec::new();
 
   for variant in variants {
     let variant_name = &variant.ident;
 
-    let match_arm = quote! {
-      Self::#variant_name(route) => route.format(),
+    let match_arm = if is_matcher_variant(variant)? {
+      quote! {
+        Self::#variant_name(route) => ::ruled_router::traits::RouteMatcher::format(route),
+      }
+    } else {
+      quote! {
+        Self::#variant_name(route) => ::ruled_router::traits::RouterData::format(route),
+      }
     };
     match_arms.push(match_arm);
   }
 
-  quote! {
+  Ok(quote! {
     fn format(&self) -> String {
       match self {
         #(#match_arms)*
       }
     }
-  }
+  })
 }
 
 /// 生成 patterns 方法的实现
-/// 这个实现假设所有变体都实现了 Router trait
-/// 生成 patterns 方法的实现
+///
+/// 路由变体贡献自己的 pattern，嵌套匹配器变体贡献它的全部 patterns
 fn generate_patterns_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
-  let mut pattern_calls = Vec::new();
+  let mut pattern_groups = Vec::new();
 
   for variant in variants {
     let route_type = extract_route_type(variant)?;
 
-    let pattern_call = quote! {
-      <#route_type as ::ruled_router::traits::RouterData>::pattern()
+    let pattern_group = if is_matcher_variant(variant)? {
+      quote! { <#route_type as ::ruled_router::traits::RouteMatcher>::patterns() }
+    } else {
+      quote! { vec![<#route_type as ::ruled_router::traits::RouterData>::pattern()] }
     };
-    pattern_calls.push(pattern_call);
+    pattern_groups.push(pattern_group);
   }
 
   Ok(quote! {
     fn patterns() -> Vec<&'static str> {
-      vec![
-        #(#pattern_calls,)*
-      ]
+      let groups: Vec<Vec<&'static str>> = vec![
+        #(#pattern_groups,)*
+      ];
+      groups.concat()
+    }
+  })
+}
+
+/// 生成 describe 方法的实现
+///
+/// 每个变体的节点由其模式和 `SubRouterMatch::describe()` 的子节点组成，
+/// 没有子路由的变体使用 `NoSubRouter`，子节点为空
+fn generate_describe_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
+  let mut child_nodes = Vec::new();
+
+  for variant in variants {
+    let route_type = extract_route_type(variant)?;
+
+    // 嵌套匹配器的节点直接并入当前层级
+    if is_matcher_variant(variant)? {
+      child_nodes.push(quote! {
+        <#route_type as ::ruled_router::traits::RouteMatcher>::describe().children
+      });
+      continue;
+    }
+
+    child_nodes.push(quote! {
+      vec![::ruled_router::traits::RouteTree {
+        pattern: <#route_type as ::ruled_router::traits::RouterData>::pattern(),
+        children: <<#route_type as ::ruled_router::traits::RouterData>::SubRouterMatch as ::ruled_router::traits::RouteMatcher>::describe().children,
+      }]
+    });
+  }
+
+  Ok(quote! {
+    fn describe() -> ::ruled_router::traits::RouteTree {
+      let groups: Vec<Vec<::ruled_router::traits::RouteTree>> = vec![
+        #(#child_nodes,)*
+      ];
+      ::ruled_router::traits::RouteTree {
+        pattern: "",
+        children: groups.concat(),
+      }
     }
   })
 }
@@ -170,6 +416,42 @@ fn generate_try_parse_with_remaining_impl(input: &DeriveInput, variants: &[&Vari
   for variant in variants {
     let variant_name = &variant.ident;
     let route_type = extract_route_type(variant)?;
+
+    if is_matcher_variant(variant)? {
+      // 嵌套匹配器自己决定消耗多少路径
+      let match_arm = match &enum_route_prefix {
+        Some(enum_prefix) => {
+          let prefix_guard = generate_explicit_prefix_guard(variant, quote! { remaining_after_enum_prefix })?;
+          quote! {
+            if path.starts_with(#enum_prefix) && path.len() > #enum_prefix.len() {
+              let remaining_after_enum_prefix = &path[#enum_prefix.len()..];
+              if #prefix_guard {
+                if let Ok((route, remaining_path)) =
+                  <#route_type as ::ruled_router::traits::RouteMatcher>::try_parse_with_remaining(remaining_after_enum_prefix, 0)
+                {
+                  return Ok((Self::#variant_name(route), remaining_path));
+                }
+              }
+            }
+          }
+        }
+        None => {
+          let prefix_guard = generate_explicit_prefix_guard(variant, quote! { path })?;
+          quote! {
+            if #prefix_guard {
+              if let Ok((route, remaining_path)) =
+                <#route_type as ::ruled_router::traits::RouteMatcher>::try_parse_with_remaining(path, 0)
+              {
+                return Ok((Self::#variant_name(route), remaining_path));
+              }
+            }
+          }
+        }
+      };
+      match_arms.push(match_arm);
+      continue;
+    }
+
     let route_prefix = extract_route_prefix(variant)?;
 
     let match_arm = if let Some(prefix) = route_prefix {
@@ -181,11 +463,10 @@ fn generate_try_parse_with_remaining_impl(input: &DeriveInput, variants: &[&Vari
 
             // 检查剩余路径是否匹配变体的 route
             if remaining_after_enum_prefix.starts_with(#prefix) {
-              // 计算子路由 pattern 应该消耗的路径长度
-              let parser = ::ruled_router::parser::PathParser::new(<#route_type as ::ruled_router::traits::RouterData>::pattern())?;
-              if let Ok(consumed) = parser.consumed_length(remaining_after_enum_prefix) {
-                let route_path = &remaining_after_enum_prefix[..consumed];
-                let final_remaining_path = &remaining_after_enum_prefix[consumed..];
+              // 只匹配子路由 pattern 覆盖的开头部分
+              let parser = ::ruled_router::__private::route_parser::<#route_type>()?;
+              if let Ok((_, final_remaining_path)) = parser.match_prefix(remaining_after_enum_prefix) {
+                let route_path = &remaining_after_enum_prefix[..remaining_after_enum_prefix.len() - final_remaining_path.len()];
 
                 // 尝试解析匹配的路径部分
                 if let Ok((route, _)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(route_path) {
@@ -196,18 +477,21 @@ fn generate_try_parse_with_remaining_impl(input: &DeriveInput, variants: &[&Vari
           }
         }
       } else {
-        // 没有 enum 级别的 route_prefix，variant 的 route 属性就是完整路径
+        // 没有 enum 级别的 route_prefix，按变体前缀匹配完整路径
+        // 没有显式前缀时由下面的模式解析判断是否匹配，带参数的模式无法用 starts_with 比较
+        let prefix_guard = generate_explicit_prefix_guard(variant, quote! { path })?;
         quote! {
-          if path.starts_with(#prefix) {
-            // 计算路由 pattern 应该消耗的路径长度
-            let parser = ::ruled_router::parser::PathParser::new(<#route_type as ::ruled_router::traits::RouterData>::pattern())?;
-            if let Ok(consumed) = parser.consumed_length(path) {
-              let route_path = &path[..consumed];
-              let remaining_path = &path[consumed..];
-
-              // 尝试解析匹配的路径部分
-              if let Ok((route, _)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(route_path) {
-                return Ok((Self::#variant_name(route), remaining_path));
+          if #prefix_guard {
+            // 只匹配路由 pattern（或别名）覆盖的开头部分
+            for parser in ::ruled_router::__private::route_parsers::<#route_type>() {
+              let parser = parser?;
+              if let Ok((_, remaining_path)) = parser.match_prefix(path) {
+                let route_path = &path[..path.len() - remaining_path.len()];
+
+                // 尝试解析匹配的路径部分
+                if let Ok((route, _)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(route_path) {
+                  return Ok((Self::#variant_name(route), remaining_path));
+                }
               }
             }
           }
@@ -241,30 +525,19 @@ fn generate_to_route_info_impl(variants: &[&Variant]) -> syn::Result<TokenStream
     let variant_name = &variant.ident;
     let route_type = extract_route_type(variant)?;
 
-    let match_arm = quote! {
-      Self::#variant_name(route) => {
-        let sub_route_info = if let Ok((_, sub_route_state)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(&route.format()) {
-          match sub_route_state {
-            ::ruled_router::error::RouteState::SubRoute(sub_match) => {
-              // 只有当 SubRouterMatch 不是 NoSubRouter 时才调用 to_route_info
-              if std::any::type_name::<<#route_type as ::ruled_router::traits::RouterData>::SubRouterMatch>() != std::any::type_name::<::ruled_router::traits::NoSubRouter>() {
-                Some(Box::new(sub_match.to_route_info()))
-              } else {
-                None
-              }
-            },
-            _ => None,
-          }
-        } else {
-          None
-        };
+    if is_matcher_variant(variant)? {
+      match_arms.push(quote! {
+        Self::#variant_name(route) => ::ruled_router::traits::ToRouteInfo::to_route_info(route),
+      });
+      continue;
+    }
 
-        ::ruled_router::traits::RouteInfo {
-          pattern: <#route_type as ::ruled_router::traits::RouterData>::pattern(),
-          formatted: route.format(),
-          sub_route_info,
-        }
-      }
+    let match_arm = quote! {
+      Self::#variant_name(route) => ::ruled_router::traits::RouteInfo {
+        pattern: <#route_type as ::ruled_router::traits::RouterData>::pattern(),
+        formatted: ::ruled_router::traits::RouterData::format(route),
+        sub_route_info: ::ruled_router::traits::RouterData::sub_route_info(route),
+      },
     };
     match_arms.push(match_arm);
   }
@@ -289,6 +562,22 @@ fn generate_debug_format_impl(input: &DeriveInput, variants: &[&Variant]) -> syn
     let variant_name = &variant.ident;
     let route_type = extract_route_type(variant)?;
 
+    if is_matcher_variant(variant)? {
+      match_arms.push(quote! {
+        Self::#variant_name(route) => {
+          let indent_str = "  ".repeat(indent);
+          format!(
+            "{}{}::{}\n{}",
+            indent_str,
+            stringify!(#enum_name),
+            stringify!(#variant_name),
+            ::ruled_router::traits::RouteMatcher::debug_format(route, indent + 1)
+          )
+        }
+      });
+      continue;
+    }
+
     let match_arm = quote! {
       Self::#variant_name(route) => {
         let indent_str = "  ".repeat(indent);
@@ -298,7 +587,7 @@ fn generate_debug_format_impl(input: &DeriveInput, variants: &[&Variant]) -> syn
         result.push_str(&format!("\n{}├─ Pattern: {}", indent_str, <#route_type as ::ruled_router::traits::RouterData>::pattern()));
 
         // 添加格式化的路径
-        let formatted = route.format();
+        let formatted = ::ruled_router::traits::RouterData::format(route);
         result.push_str(&format!("\n{}├─ Formatted: {}", indent_str, formatted));
 
         // 检查是否有查询参数，如果有则显示参数名称
@@ -313,13 +602,13 @@ fn generate_debug_format_impl(input: &DeriveInput, variants: &[&Variant]) -> syn
         }
 
         // 尝试获取子路由信息
-        if let Ok((_, sub_route_state)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(&route.format()) {
+        if let Ok((_, sub_route_state)) = <#route_type as ::ruled_router::traits::RouterData>::parse_with_sub(&::ruled_router::traits::RouterData::format(route)) {
           match sub_route_state {
             ::ruled_router::error::RouteState::SubRoute(sub_match) => {
               // 只有当 SubRouterMatch 不是 NoSubRouter 时才调用 debug_format
               if std::any::type_name::<<#route_type as ::ruled_router::traits::RouterData>::SubRouterMatch>() != std::any::type_name::<::ruled_router::traits::NoSubRouter>() {
                 result.push_str(&format!("\n{}└─ Sub:", indent_str));
-                result.push_str(&format!("\n{}", sub_match.debug_format(indent + 1)));
+                result.push_str(&format!("\n{}", ::ruled_router::traits::RouteMatcher::debug_format(&sub_match, indent + 1)));
               } else {
                 result.push_str(&format!("\n{}└─ ◉", indent_str));
               }
@@ -347,6 +636,39 @@ fn generate_debug_format_impl(input: &DeriveInput, variants: &[&Variant]) -> syn
   })
 }
 
+/// 生成 format_pretty 方法的实现
+///
+/// 每个变体用路由自身的 format() 和子路由信息中的 formatted 拼出单行描述
+fn generate_format_pretty_impl(variants: &[&Variant]) -> syn::Result<TokenStream> {
+  let mut match_arms = Vec::new();
+
+  for variant in variants {
+    let variant_name = &variant.ident;
+    let match_arm = if is_matcher_variant(variant)? {
+      quote! {
+        Self::#variant_name(route) => ::ruled_router::traits::RouteMatcher::format_pretty(route),
+      }
+    } else {
+      quote! {
+        Self::#variant_name(route) => {
+          let formatted = ::ruled_router::traits::RouterData::format(route);
+          let sub_formatted = ::ruled_router::traits::RouterData::sub_route_info(route).map(|sub_info| sub_info.formatted);
+          ::ruled_router::utils::format_pretty_url(&formatted, sub_formatted.as_deref())
+        }
+      }
+    };
+    match_arms.push(match_arm);
+  }
+
+  Ok(quote! {
+    fn format_pretty(&self) -> String {
+      match self {
+        #(#match_arms)*
+      }
+    }
+  })
+}
+
 /// 主要的 RouterMatch 派生宏实现
 pub fn expand_router_match_derive(input: DeriveInput) -> syn::Result<TokenStream> {
   let name = &input.ident;
@@ -358,23 +680,35 @@ pub fn expand_router_match_derive(input: DeriveInput) -> syn::Result<TokenStream
   }
 
   let try_parse_impl = generate_try_parse_impl(&variants)?;
-  let format_impl = generate_format_impl(&variants);
+  let try_parse_nested_impl = generate_try_parse_nested_impl(&variants)?;
+  let format_impl = generate_format_impl(&variants)?;
   let patterns_impl = generate_patterns_impl(&variants)?;
+  let match_score_impl = generate_match_score_impl(&variants)?;
+  let describe_impl = generate_describe_impl(&variants)?;
   let try_parse_with_remaining_impl = generate_try_parse_with_remaining_impl(&input, &variants)?;
   let to_route_info_impl = generate_to_route_info_impl(&variants)?;
   let debug_format_impl = generate_debug_format_impl(&input, &variants)?;
+  let format_pretty_impl = generate_format_pretty_impl(&variants)?;
 
   let expanded = quote! {
     impl ::ruled_router::traits::RouteMatcher for #name {
       #try_parse_impl
 
+      #try_parse_nested_impl
+
       #format_impl
 
       #patterns_impl
 
+      #match_score_impl
+
+      #describe_impl
+
       #try_parse_with_remaining_impl
 
       #debug_format_impl
+
+      #format_pretty_impl
     }
 
     impl ::ruled_router::traits::ToRouteInfo for #name {
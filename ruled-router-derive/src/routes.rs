@@ -0,0 +1,153 @@
+//! Implementation of the routes! function-like macro
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, parenthesized, Attribute, Field, Ident, LitStr, Token, Visibility};
+
+/// `routes! { ... }` 的输入：顶层 RouterMatch 枚举及其路由树
+struct RoutesInput {
+  attrs: Vec<Attribute>,
+  vis: Visibility,
+  name: Ident,
+  entries: Vec<RouteEntry>,
+}
+
+/// 路由树中的一个节点：`"/pattern" => Name(fields) { children }`
+struct RouteEntry {
+  attrs: Vec<Attribute>,
+  pattern: LitStr,
+  name: Ident,
+  /// 括号中声明的字段，与手写 RouterData 结构体的字段写法相同
+  fields: Vec<Field>,
+  /// 子路由；为 None 时节点没有子路由
+  children: Option<Vec<RouteEntry>>,
+}
+
+impl Parse for RoutesInput {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let attrs = input.call(Attribute::parse_outer)?;
+    let vis = input.parse()?;
+    input.parse::<Token![enum]>()?;
+    let name = input.parse()?;
+    let entries = parse_entries(input)?;
+    if !input.is_empty() {
+      return Err(input.error("Unexpected tokens after the route tree"));
+    }
+    Ok(Self { attrs, vis, name, entries })
+  }
+}
+
+impl Parse for RouteEntry {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let attrs = input.call(Attribute::parse_outer)?;
+    let pattern = input.parse()?;
+    input.parse::<Token![=>]>()?;
+    let name = input.parse()?;
+
+    let fields = if input.peek(syn::token::Paren) {
+      let content;
+      parenthesized!(content in input);
+      Punctuated::<Field, Token![,]>::parse_terminated_with(&content, Field::parse_named)?
+        .into_iter()
+        .collect()
+    } else {
+      Vec::new()
+    };
+
+    let children = if input.peek(syn::token::Brace) {
+      Some(parse_entries(input)?)
+    } else {
+      None
+    };
+
+    Ok(Self {
+      attrs,
+      pattern,
+      name,
+      fields,
+      children,
+    })
+  }
+}
+
+/// 解析 `{ entry, entry, ... }`，允许末尾逗号
+fn parse_entries(input: ParseStream) -> syn::Result<Vec<RouteEntry>> {
+  let content;
+  let brace = braced!(content in input);
+  let entries: Vec<RouteEntry> = Punctuated::<RouteEntry, Token![,]>::parse_terminated(&content)?
+    .into_iter()
+    .collect();
+  if entries.is_empty() {
+    return Err(syn::Error::new(brace.span.join(), "A route group must contain at least one route"));
+  }
+  Ok(entries)
+}
+
+/// Expand the routes! macro
+pub fn expand_routes(input: TokenStream) -> syn::Result<TokenStream> {
+  let RoutesInput { attrs, vis, name, entries } = syn::parse2(input)?;
+
+  let mut items = Vec::new();
+  items.push(generate_match_enum(&attrs, &vis, &name, &entries));
+  for entry in &entries {
+    generate_entry(&vis, entry, &mut items);
+  }
+
+  Ok(quote! { #(#items)* })
+}
+
+/// 生成一组路由对应的 RouterMatch 枚举，每个变体包装同名的路由结构体
+fn generate_match_enum(attrs: &[Attribute], vis: &Visibility, name: &Ident, entries: &[RouteEntry]) -> TokenStream {
+  let variants = entries.iter().map(|entry| {
+    let variant = &entry.name;
+    quote! { #variant(#variant) }
+  });
+
+  quote! {
+      #(#attrs)*
+      #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::ruled_router::RouterMatch)]
+      #vis enum #name {
+          #(#variants),*
+      }
+  }
+}
+
+/// 生成节点的 RouterData 结构体；有子路由时再生成 `{Name}Match` 枚举并递归生成子节点
+fn generate_entry(vis: &Visibility, entry: &RouteEntry, items: &mut Vec<TokenStream>) {
+  let RouteEntry {
+    attrs,
+    pattern,
+    name,
+    fields,
+    children,
+  } = entry;
+
+  // 子路由可选：路径恰好停在当前节点时为 None
+  let sub_router = children.as_ref().map(|_| {
+    let match_name = format_ident!("{}Match", name);
+    quote! {
+        #[sub_router]
+        #vis sub_router: ::core::option::Option<#match_name>,
+    }
+  });
+
+  items.push(quote! {
+      #(#attrs)*
+      #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::ruled_router::RouterData)]
+      #[router(pattern = #pattern)]
+      #vis struct #name {
+          #(#fields,)*
+          #sub_router
+      }
+  });
+
+  if let Some(children) = children {
+    let match_name = format_ident!("{}Match", name);
+    items.push(generate_match_enum(&[], vis, &match_name, children));
+    for child in children {
+      generate_entry(vis, child, items);
+    }
+  }
+}
@@ -0,0 +1,70 @@
+//! 缓存与不缓存 PathParser 的解析性能对比，以及借用方式提取参数的开销
+//!
+//! 运行：`cargo bench -p ruled-router --bench path_parser_cache`
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ruled_router::parser::{PathParser, PathParserCache};
+use ruled_router::prelude::*;
+
+const PATTERN: &str = "/api/:version/users/:id/posts/:post_id";
+const PATH: &str = "/api/v1/users/42/posts/7";
+const ITERATIONS: u32 = 200_000;
+
+#[derive(Debug, RouterData)]
+#[router(pattern = "/api/:version/users/:id/posts/:post_id")]
+struct PostRoute {
+  version: String,
+  id: u32,
+  post_id: u32,
+}
+
+/// 运行 ITERATIONS 次并返回总耗时
+fn measure(mut f: impl FnMut()) -> Duration {
+  // 预热，同时让缓存完成初始化
+  for _ in 0..1_000 {
+    f();
+  }
+  let start = Instant::now();
+  for _ in 0..ITERATIONS {
+    f();
+  }
+  start.elapsed()
+}
+
+fn report(name: &str, elapsed: Duration) {
+  println!("{name:<32} {:>10.1} ns/iter", elapsed.as_nanos() as f64 / f64::from(ITERATIONS));
+}
+
+fn main() {
+  static CACHE: PathParserCache = PathParserCache::new(PATTERN);
+
+  let uncached = measure(|| {
+    let parser = PathParser::new(black_box(PATTERN)).unwrap();
+    black_box(parser.match_path(black_box(PATH)).unwrap());
+  });
+  report("PathParser::new + match_path", uncached);
+
+  let cached = measure(|| {
+    let parser = CACHE.get().unwrap();
+    black_box(parser.match_path(black_box(PATH)).unwrap());
+  });
+  report("PathParserCache + match_path", cached);
+
+  let borrowed = measure(|| {
+    let parser = CACHE.get().unwrap();
+    black_box(parser.match_path_borrowed(black_box(PATH)).unwrap());
+  });
+  report("PathParserCache + borrowed", borrowed);
+
+  let derived = measure(|| {
+    black_box(PostRoute::parse(black_box(PATH)).unwrap());
+  });
+  report("derived RouterData::parse", derived);
+
+  println!(
+    "cached speedup: {:.2}x",
+    uncached.as_secs_f64() / cached.as_secs_f64().max(f64::MIN_POSITIVE)
+  );
+}
@@ -4,7 +4,6 @@
 //! 来打印复杂的多层嵌套路由结构，用于开发过程中的验证。
 
 use ruled_router::error::RouteState;
-use ruled_router::prelude::*;
 use ruled_router::RouteMatcher;
 use ruled_router_derive::{QueryDerive, RouterData, RouterMatch};
 
@@ -421,3 +420,33 @@ fn main() {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ruled_router::traits::ToRouteInfo;
+
+  #[test]
+  fn test_route_info_flatten_three_levels() {
+    let route = AppRouterMatch::User(UserModuleRoute {
+      query: SimpleQuery::default(),
+      sub_router: RouteState::sub_route(UserSubRouterMatch::Profile(UserProfileCategoryRoute {
+        query: SimpleQuery::default(),
+        sub_router: RouteState::sub_route(UserProfileDetailRouterMatch::BasicInfo(UserBasicInfoRoute {
+          id: 123,
+          query: SimpleQuery::default(),
+        })),
+      })),
+    });
+
+    let info = route.to_route_info();
+    assert_eq!(info.segments(), vec!["/users", "/profile", "/basic/:id"]);
+
+    let levels = info.flatten();
+    assert_eq!(levels.len(), 3);
+    assert_eq!(levels[0].formatted, "/users/profile/basic/123");
+    assert_eq!(levels[1].formatted, "/profile/basic/123");
+    assert_eq!(levels[2].formatted, "/basic/123");
+    assert!(levels[2].sub_route_info.is_none());
+  }
+}
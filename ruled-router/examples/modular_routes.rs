@@ -1,5 +1,4 @@
 use ruled_router::error::RouteState;
-use ruled_router::prelude::*;
 use ruled_router::RouteMatcher;
 use ruled_router_derive::{QueryDerive, RouterData, RouterMatch};
 
@@ -514,6 +514,16 @@ mod tests {
     assert_eq!(remaining, "");
   }
 
+  #[test]
+  fn test_try_parse_nested_matches_manual_recursion() {
+    // 一次调用即可得到手动逐层解析构建的完整路由树
+    let nested = AppRouterMatch::try_parse_nested("/users/profile/basic/123").unwrap();
+    assert_eq!(nested, AppRouterMatch::try_parse("/users/profile/basic/123").unwrap());
+    assert_eq!(nested.format(), "/users/profile/basic/123");
+
+    assert!(AppRouterMatch::try_parse_nested("/users/profile/unknown/123").is_err());
+  }
+
   #[test]
   fn test_user_basic_info_route() {
     let route = UserBasicInfoRoute::parse("/basic/123?format=json").unwrap();
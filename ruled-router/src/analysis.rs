@@ -0,0 +1,220 @@
+//! 路由模式分析
+//!
+//! 在启动时检查一组路由模式，给出模式的具体程度并找出可能匹配同一路径的模式对，
+//! 用于发现 `/users/:id` 遮蔽 `/users/new` 这类问题
+
+use std::collections::HashSet;
+
+use crate::error::ParseResult;
+use crate::parser::{PathParser, PathSegment};
+
+/// 单个模式段的具体程度，按声明顺序从低到高
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SegmentRank {
+  /// 通配符段，例如 "*path"
+  Wildcard,
+  /// 可选参数段，例如 "?:format"
+  Optional,
+  /// 参数段，例如 ":id"
+  Parameter,
+  /// 带正则约束的参数段，例如 `:id(\d+)`
+  Constrained,
+  /// 字面量段，例如 "users"
+  Literal,
+}
+
+impl SegmentRank {
+  fn of(segment: &PathSegment) -> Self {
+    match segment {
+      PathSegment::Literal(_) => SegmentRank::Literal,
+      PathSegment::ConstrainedParameter { .. } => SegmentRank::Constrained,
+      PathSegment::Parameter(_) => SegmentRank::Parameter,
+      PathSegment::OptionalParameter(_) => SegmentRank::Optional,
+      PathSegment::Wildcard(_) => SegmentRank::Wildcard,
+    }
+  }
+}
+
+/// 路由模式的具体程度
+///
+/// 逐段比较 [`SegmentRank`]，第一个不同的段决定大小；前面的段都相同时，段更多的模式更具体。
+/// 因此 `/users/new` 比 `/users/:id` 更具体，`/users/:id` 又比 `/users/*rest` 更具体。
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RouteSpecificity {
+  ranks: Vec<SegmentRank>,
+}
+
+impl RouteSpecificity {
+  /// 获取每个模式段的具体程度
+  pub fn ranks(&self) -> &[SegmentRank] {
+    &self.ranks
+  }
+}
+
+/// 计算路由模式的具体程度
+///
+/// # 参数
+///
+/// * `pattern` - 路由模式
+///
+/// # 返回值
+///
+/// 模式的具体程度，模式无效时返回错误
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::analysis::specificity;
+///
+/// assert!(specificity("/users/new").unwrap() > specificity("/users/:id").unwrap());
+/// assert!(specificity("/users/:id").unwrap() > specificity("/users/*rest").unwrap());
+/// ```
+pub fn specificity(pattern: &str) -> ParseResult<RouteSpecificity> {
+  let parser = PathParser::new(pattern)?;
+  Ok(RouteSpecificity {
+    ranks: parser.segments().iter().map(SegmentRank::of).collect(),
+  })
+}
+
+/// 找出可能匹配同一路径的模式对
+///
+/// 返回的索引对满足 `i < j`，按 `(i, j)` 升序排列。判断是保守的：
+/// 可选参数视为可以匹配零个或一个段，两个正则约束之间总是视为可能重叠，
+/// 所以结果可能包含实际上不会冲突的模式对，但不会漏掉真正的冲突。
+///
+/// # 参数
+///
+/// * `patterns` - 路由模式列表
+///
+/// # 返回值
+///
+/// 冲突的模式索引对，任一模式无效时返回错误
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::analysis::conflicts;
+///
+/// let pairs = conflicts(&["/users/:id", "/users/new", "/posts/:id"]).unwrap();
+/// assert_eq!(pairs, vec![(0, 1)]);
+/// ```
+pub fn conflicts(patterns: &[&str]) -> ParseResult<Vec<(usize, usize)>> {
+  let parsers = patterns
+    .iter()
+    .map(|pattern| PathParser::new(pattern))
+    .collect::<ParseResult<Vec<_>>>()?;
+
+  let mut pairs = Vec::new();
+  for i in 0..parsers.len() {
+    for j in i + 1..parsers.len() {
+      if segments_overlap(parsers[i].segments(), parsers[j].segments()) {
+        pairs.push((i, j));
+      }
+    }
+  }
+  Ok(pairs)
+}
+
+/// 检查两组模式段是否可能匹配同一路径
+fn segments_overlap(left: &[PathSegment], right: &[PathSegment]) -> bool {
+  let mut visited = HashSet::new();
+  overlap_from(left, right, 0, 0, &mut visited)
+}
+
+/// 从 `(i, j)` 开始搜索能被两组模式段同时消费的路径
+///
+/// 可选参数和通配符可以不消费路径段；通配符消费一个段后停留在原位，
+/// 因此用 visited 记录已经搜索过的位置，避免两个通配符互相等待
+fn overlap_from(left: &[PathSegment], right: &[PathSegment], i: usize, j: usize, visited: &mut HashSet<(usize, usize)>) -> bool {
+  if !visited.insert((i, j)) {
+    return false;
+  }
+  if i == left.len() && j == right.len() {
+    return true;
+  }
+
+  let may_skip =
+    |segment: Option<&PathSegment>| matches!(segment, Some(PathSegment::OptionalParameter(_)) | Some(PathSegment::Wildcard(_)));
+  if may_skip(left.get(i)) && overlap_from(left, right, i + 1, j, visited) {
+    return true;
+  }
+  if may_skip(right.get(j)) && overlap_from(left, right, i, j + 1, visited) {
+    return true;
+  }
+
+  match (left.get(i), right.get(j)) {
+    (Some(a), Some(b)) if segments_compatible(a, b) => {
+      let next = |segment: &PathSegment, index: usize| match segment {
+        PathSegment::Wildcard(_) => index,
+        _ => index + 1,
+      };
+      overlap_from(left, right, next(a, i), next(b, j), visited)
+    }
+    _ => false,
+  }
+}
+
+/// 检查两个模式段是否可能匹配同一个路径段
+fn segments_compatible(a: &PathSegment, b: &PathSegment) -> bool {
+  match (a, b) {
+    (PathSegment::Literal(x), PathSegment::Literal(y)) => x == y,
+    (PathSegment::Literal(literal), PathSegment::ConstrainedParameter { regex, .. })
+    | (PathSegment::ConstrainedParameter { regex, .. }, PathSegment::Literal(literal)) => constraint_accepts(regex, literal),
+    _ => true,
+  }
+}
+
+/// 检查正则约束是否接受给定的字面量
+#[cfg(feature = "regex")]
+fn constraint_accepts(regex: &str, literal: &str) -> bool {
+  regex::Regex::new(&format!("^(?:{regex})$")).is_ok_and(|compiled| compiled.is_match(literal))
+}
+
+/// 没有 `regex` feature 时无法出现约束段，保守地视为接受
+#[cfg(not(feature = "regex"))]
+fn constraint_accepts(_regex: &str, _literal: &str) -> bool {
+  true
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_specificity_ordering() {
+    let literal = specificity("/users/new").unwrap();
+    let param = specificity("/users/:id").unwrap();
+    let optional = specificity("/users/?:id").unwrap();
+    let wildcard = specificity("/users/*rest").unwrap();
+
+    assert_eq!(literal.ranks(), &[SegmentRank::Literal, SegmentRank::Literal]);
+    assert!(literal > param);
+    assert!(param > optional);
+    assert!(optional > wildcard);
+    assert!(specificity("/users/:id/posts").unwrap() > param);
+    assert!(specificity("/users/:").is_err());
+  }
+
+  #[test]
+  fn test_conflicts_detects_shadowing_pair() {
+    assert_eq!(conflicts(&["/users/:id", "/users/new"]).unwrap(), vec![(0, 1)]);
+    assert_eq!(conflicts(&["/files/*path", "/files/a/b"]).unwrap(), vec![(0, 1)]);
+    assert_eq!(conflicts(&["/users/?:id", "/users"]).unwrap(), vec![(0, 1)]);
+    assert_eq!(conflicts(&["/*a", "/*b"]).unwrap(), vec![(0, 1)]);
+  }
+
+  #[test]
+  fn test_conflicts_ignores_disjoint_patterns() {
+    assert!(conflicts(&["/users/:id", "/posts/:id"]).unwrap().is_empty());
+    assert!(conflicts(&["/users/:id", "/users/:id/posts"]).unwrap().is_empty());
+    assert!(conflicts(&["/files/*path/download", "/files"]).unwrap().is_empty());
+    assert!(conflicts(&[]).unwrap().is_empty());
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn test_conflicts_checks_constraints_against_literals() {
+    assert!(conflicts(&[r"/users/:id(\d+)", "/users/new"]).unwrap().is_empty());
+    assert_eq!(conflicts(&[r"/users/:id(\d+)", "/users/42"]).unwrap(), vec![(0, 1)]);
+  }
+}
@@ -0,0 +1,101 @@
+//! axum 集成
+//!
+//! 提供 [`Route`] 提取器，把请求的路径和查询参数解析为任意 `RouterData` 类型。
+//! 需要启用 `axum` feature。
+
+use ::axum::extract::FromRequestParts;
+use ::axum::http::request::Parts;
+use ::axum::http::{header, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+
+use crate::error::ParseError;
+use crate::traits::RouterData;
+
+/// 路由提取器
+///
+/// 从请求中取出 `path?query` 并调用 `R::parse`，解析失败时返回 [`RouteRejection`]。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// use ruled_router::axum::Route;
+///
+/// async fn show_user(Route(route): Route<UserRoute>) -> String {
+///   format!("user {}", route.id)
+/// }
+///
+/// let app = axum::Router::new().route("/users/{id}", axum::routing::get(show_user));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route<R>(pub R);
+
+impl<R, S> FromRequestParts<S> for Route<R>
+where
+  R: RouterData + Send,
+  S: Send + Sync,
+{
+  type Rejection = RouteRejection;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    let path_and_query = parts.uri.path_and_query().map(|pq| pq.as_str()).unwrap_or_else(|| parts.uri.path());
+    R::parse(path_and_query).map(Route).map_err(RouteRejection)
+  }
+}
+
+/// 路由解析失败时的拒绝响应
+///
+/// 按 [`ParseError::http_status`] 映射状态码：
+/// - 路径结构不匹配（`InvalidPath`、`SegmentCountMismatch`、`SegmentMismatch`）返回 404
+/// - `TrailingSlashRedirect` 返回 308，并把规范路径写入 `Location`
+/// - 其他错误（参数缺失、类型转换、查询参数等）返回 400
+///
+/// 响应体是错误的 `Display` 文本。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteRejection(pub ParseError);
+
+impl RouteRejection {
+  /// 获取对应的 HTTP 状态码
+  pub fn status(&self) -> StatusCode {
+    StatusCode::from_u16(self.0.http_status()).unwrap_or(StatusCode::BAD_REQUEST)
+  }
+}
+
+impl IntoResponse for RouteRejection {
+  fn into_response(self) -> Response {
+    let status = self.status();
+    match &self.0 {
+      ParseError::TrailingSlashRedirect { canonical } => (status, [(header::LOCATION, canonical.clone())]).into_response(),
+      error => (status, error.to_string()).into_response(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rejection_status() {
+    assert_eq!(RouteRejection(ParseError::invalid_path("x")).status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      RouteRejection(ParseError::segment_count_mismatch(2, 3)).status(),
+      StatusCode::NOT_FOUND
+    );
+    assert_eq!(RouteRejection(ParseError::type_conversion("x")).status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      RouteRejection(ParseError::missing_parameter("id")).status(),
+      StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+      RouteRejection(ParseError::trailing_slash_redirect("/users")).status(),
+      StatusCode::PERMANENT_REDIRECT
+    );
+  }
+
+  #[test]
+  fn test_redirect_response_has_location() {
+    let response = RouteRejection(ParseError::trailing_slash_redirect("/users/1")).into_response();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(response.headers()[header::LOCATION], "/users/1");
+  }
+}
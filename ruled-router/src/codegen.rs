@@ -0,0 +1,120 @@
+//! 代码生成
+//!
+//! 根据路由模式生成其他语言的路由辅助代码，让前端链接与 Rust 中的路由定义保持一致
+
+use crate::parser::{PathParser, PathSegment};
+
+/// 生成 TypeScript 路由辅助函数
+///
+/// 每个 `(name, pattern)` 生成一个名为 `name` 的导出函数，参数名来自 [`PathParser::parameter_names`]，
+/// 类型均为 `string`。可选参数 `?:name` 生成为放在参数列表末尾的可选参数，未传入时省略该段。
+/// 参数值原样插入模板字符串，不做 URL 编码。无法解析的模式会被跳过。
+///
+/// # 参数
+///
+/// * `patterns` - 函数名与路由模式的列表，模式通常来自 [`RouteMatcher::patterns`](crate::traits::RouteMatcher::patterns)
+///
+/// # 返回值
+///
+/// TypeScript 源代码
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::codegen::typescript;
+///
+/// let source = typescript(&[("userRoute", "/users/:id")]);
+/// assert!(source.contains("export function userRoute(id: string) {\n  return `/users/${id}`;\n}"));
+/// ```
+pub fn typescript(patterns: &[(&str, &str)]) -> String {
+  let mut output = String::from("// Generated by ruled-router. Do not edit.\n");
+
+  for (name, pattern) in patterns {
+    let Ok(parser) = PathParser::new(pattern) else {
+      continue;
+    };
+
+    // TypeScript 要求可选参数位于必选参数之后
+    let optional: Vec<&str> = parser
+      .segments()
+      .iter()
+      .filter_map(|segment| match segment {
+        PathSegment::OptionalParameter(name) => Some(name.as_str()),
+        _ => None,
+      })
+      .collect();
+    let mut arguments: Vec<String> = parser
+      .parameter_names()
+      .into_iter()
+      .filter(|param| !optional.contains(param))
+      .map(|param| format!("{param}: string"))
+      .collect();
+    arguments.extend(optional.iter().map(|param| format!("{param}?: string")));
+
+    output.push_str(&format!(
+      "\nexport function {name}({}) {{\n  return `{}`;\n}}\n",
+      arguments.join(", "),
+      typescript_template(parser.segments())
+    ));
+  }
+
+  output
+}
+
+/// 把模式段转换为 TypeScript 模板字符串的内容（不含两侧的反引号）
+fn typescript_template(segments: &[PathSegment]) -> String {
+  if segments.is_empty() {
+    return "/".to_string();
+  }
+
+  segments
+    .iter()
+    .map(|segment| match segment {
+      PathSegment::Literal(literal) => format!("/{}", escape_template_literal(literal)),
+      PathSegment::Parameter(name) | PathSegment::ConstrainedParameter { name, .. } | PathSegment::Wildcard(name) => {
+        format!("/${{{name}}}")
+      }
+      PathSegment::OptionalParameter(name) => format!("${{{name} === undefined ? \"\" : `/${{{name}}}`}}"),
+    })
+    .collect()
+}
+
+/// 转义模板字符串中有特殊含义的字符
+fn escape_template_literal(literal: &str) -> String {
+  literal.replace('\\', "\\\\").replace('`', "\\`").replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_typescript_route_helpers() {
+    let source = typescript(&[
+      ("userRoute", "/users/:id"),
+      ("postRoute", "/users/:user_id/posts/:post_id"),
+      ("homeRoute", "/"),
+    ]);
+
+    assert_eq!(
+      source,
+      "// Generated by ruled-router. Do not edit.\n\
+       \n\
+       export function userRoute(id: string) {\n  return `/users/${id}`;\n}\n\
+       \n\
+       export function postRoute(user_id: string, post_id: string) {\n  return `/users/${user_id}/posts/${post_id}`;\n}\n\
+       \n\
+       export function homeRoute() {\n  return `/`;\n}\n"
+    );
+  }
+
+  #[test]
+  fn test_typescript_optional_and_wildcard() {
+    let source = typescript(&[("docsRoute", "/docs/?:page/*rest"), ("broken", "/users/:")]);
+
+    assert!(source.contains("export function docsRoute(rest: string, page?: string) {"));
+    assert!(source.contains("return `/docs${page === undefined ? \"\" : `/${page}`}/${rest}`;"));
+    // 无效模式被跳过
+    assert!(!source.contains("broken"));
+  }
+}
@@ -0,0 +1,54 @@
+//! 路由分发辅助
+//!
+//! 在不定义 `RouterMatch` 枚举的情况下，按顺序尝试多个 `RouterData` 类型
+
+/// 按顺序尝试多个路由类型，返回第一个解析成功的结果
+///
+/// 展开为 `if let Ok(route) = Route::parse(path)` 级联，每个分支用 `=>` 右侧的构造器包装结果。
+/// 所有路由都解析失败时返回 `None`。与 `RouterMatch` 不同，这里使用 `RouterData::parse`
+/// 做完整路径匹配，不提取前缀，也不区分失败原因。
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::prelude::*;
+/// use ruled_router::try_routes;
+///
+/// #[derive(Debug, PartialEq, RouterData)]
+/// #[router(pattern = "/users/:id")]
+/// struct UserRoute {
+///   id: u32,
+/// }
+///
+/// #[derive(Debug, PartialEq, RouterData)]
+/// #[router(pattern = "/blog/:slug")]
+/// struct BlogRoute {
+///   slug: String,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum AppRoute {
+///   User(UserRoute),
+///   Blog(BlogRoute),
+/// }
+///
+/// let route = try_routes!("/blog/hello", UserRoute => AppRoute::User, BlogRoute => AppRoute::Blog);
+/// assert_eq!(route, Some(AppRoute::Blog(BlogRoute { slug: "hello".to_string() })));
+///
+/// let missing = try_routes!("/unknown", UserRoute => AppRoute::User, BlogRoute => AppRoute::Blog);
+/// assert_eq!(missing, None);
+/// ```
+#[macro_export]
+macro_rules! try_routes {
+  ($path:expr, $($route:ty => $wrap:expr),+ $(,)?) => {{
+    let path: &str = $path;
+    $(
+      if let Ok(route) = <$route as $crate::traits::RouterData>::parse(path) {
+        Some($wrap(route))
+      } else
+    )+
+    {
+      None
+    }
+  }};
+}
@@ -8,7 +8,7 @@
 //! 只有在启用 `dom` feature 时才会编译此模块。
 
 use crate::error::ParseError;
-use crate::traits::RouterData;
+use crate::traits::{RouteMatcher, RouterData};
 use std::cell::RefCell;
 use std::marker::PhantomData;
 use std::rc::Rc;
@@ -237,17 +237,112 @@ impl<T: RouterData + Clone + 'static> DomRouter<T> {
   }
 }
 
+/// 基于 History API 的浏览器路由
+///
+/// 与 [`DomRouter`] 不同，这里的路由类型是实现了 [`RouteMatcher`] 的顶层路由枚举，
+/// 省去每个应用里重复编写的读取 location、`pushState` 和 `popstate` 监听代码
+pub struct BrowserRouter<R: RouteMatcher> {
+  history: History,
+  location: Location,
+  _phantom: PhantomData<R>,
+}
+
+impl<R: RouteMatcher + 'static> BrowserRouter<R> {
+  /// 创建新的浏览器路由
+  ///
+  /// # 返回值
+  ///
+  /// 成功时返回 BrowserRouter 实例，无法获取 window 或 history 时返回 JsValue 错误
+  pub fn new() -> Result<Self, JsValue> {
+    let window = window().ok_or("无法获取 window 对象")?;
+    Ok(BrowserRouter {
+      history: window.history()?,
+      location: window.location(),
+      _phantom: PhantomData,
+    })
+  }
+
+  /// 解析当前地址（pathname + search）为路由
+  ///
+  /// # 返回值
+  ///
+  /// 成功时返回匹配的路由，无法读取地址或没有路由匹配时返回 ParseError
+  pub fn current(&self) -> Result<R, ParseError> {
+    let path = current_location_path(&self.location).map_err(|_| ParseError::invalid_path("无法获取当前路径"))?;
+    R::try_parse(&path)
+  }
+
+  /// 格式化路由并通过 `pushState` 添加新的历史记录条目
+  ///
+  /// 浏览器不会为 `pushState` 触发 `popstate`，因此不会调用 [`on_change`](Self::on_change) 注册的回调
+  ///
+  /// # 参数
+  ///
+  /// * `route` - 要导航到的路由
+  pub fn navigate(&self, route: &R) -> Result<(), JsValue> {
+    self.history.push_state_with_url(&JsValue::NULL, "", Some(&route.format()))
+  }
+
+  /// 格式化路由并通过 `replaceState` 替换当前历史记录条目
+  ///
+  /// # 参数
+  ///
+  /// * `route` - 要替换成的路由
+  pub fn replace(&self, route: &R) -> Result<(), JsValue> {
+    self.history.replace_state_with_url(&JsValue::NULL, "", Some(&route.format()))
+  }
+
+  /// 监听浏览器的前进/后退（`popstate`），每次把新地址解析为路由后调用回调
+  ///
+  /// 无法解析的地址会被忽略。监听器在页面生命周期内一直有效。
+  ///
+  /// # 参数
+  ///
+  /// * `callback` - 接收新路由的回调
+  pub fn on_change(&self, mut callback: impl FnMut(R) + 'static) -> Result<(), JsValue> {
+    let current_window = window().ok_or("无法获取 window 对象")?;
+    let location = self.location.clone();
+
+    let closure = Closure::<dyn FnMut(Event)>::new(move |_event: Event| {
+      let Ok(path) = current_location_path(&location) else {
+        return;
+      };
+      if let Ok(route) = R::try_parse(&path) {
+        callback(route);
+      }
+    });
+
+    current_window.add_event_listener_with_callback("popstate", closure.as_ref().unchecked_ref())?;
+
+    // 防止闭包被垃圾回收
+    closure.forget();
+
+    Ok(())
+  }
+}
+
+/// 读取 location 的 pathname 和 search 并拼接为完整路径
+fn current_location_path(location: &Location) -> Result<String, JsValue> {
+  let pathname = location.pathname()?;
+  let search = location.search()?;
+  Ok(format!("{pathname}{search}"))
+}
+
 /// 路由辅助函数集合
 pub mod helpers {
   use super::*;
 
-  /// 获取当前页面的路径
-  pub fn get_current_path() -> Result<String, JsValue> {
+  /// 获取当前页面的 URL（pathname + search），可以直接交给路由解析
+  ///
+  /// 只使用 pathname 会丢掉查询参数，浏览器后退到带搜索条件的页面时条件就丢失了
+  pub fn current_url() -> Result<String, JsValue> {
     let window = window().ok_or("无法获取 window 对象")?;
-    let location = window.location();
-    let pathname = location.pathname()?;
-    let search = location.search()?;
-    Ok(format!("{pathname}{search}"))
+    current_location_path(&window.location())
+  }
+
+  /// 获取当前页面的路径（包含查询字符串），与 [`current_url`] 相同
+  pub fn get_current_path() -> Result<String, JsValue> {
+    current_url()
   }
 
   /// 设置页面标题
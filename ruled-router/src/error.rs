@@ -3,6 +3,9 @@
 //! 定义了路由解析过程中可能出现的各种错误类型
 
 use std::fmt;
+use std::sync::Arc;
+
+use crate::parser::{PathParser, PathSegment};
 
 /// 路由状态枚举
 ///
@@ -66,10 +69,46 @@ pub struct RouteDebugInfo {
   pub suggestion: Option<String>,
 }
 
+/// 查询参数解析警告
+///
+/// 由 [`Query::parse_lenient`](crate::traits::Query::parse_lenient) 收集，
+/// 表示某个参数值无法解析而被忽略
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueryWarning {
+  /// 参数名
+  pub key: String,
+  /// 被忽略的原始值
+  pub value: String,
+  /// 忽略的原因
+  pub reason: String,
+}
+
+impl QueryWarning {
+  /// 创建查询参数解析警告
+  pub fn new<S1: Into<String>, S2: Into<String>, S3: Into<String>>(key: S1, value: S2, reason: S3) -> Self {
+    QueryWarning {
+      key: key.into(),
+      value: value.into(),
+      reason: reason.into(),
+    }
+  }
+}
+
+impl fmt::Display for QueryWarning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "Ignored query parameter '{}' with value '{}': {}",
+      self.key, self.value, self.reason
+    )
+  }
+}
+
 /// 解析错误类型
 ///
 /// 表示在路由解析过程中可能出现的各种错误情况
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum ParseError {
   /// 无效的路径格式
   ///
@@ -86,6 +125,16 @@ pub enum ParseError {
   /// 当无法将字符串参数转换为目标类型时返回此错误
   TypeConversion(String),
 
+  /// 带有底层错误源的类型转换失败
+  ///
+  /// 与 `TypeConversion` 含义相同，`source` 保存底层的解析错误（例如 `ParseIntError`），
+  /// 可以通过 [`Error::source`](std::error::Error::source) 取得。两者的 [`ParseError::kind`]
+  /// 都是 [`ParseErrorKind::TypeConversion`]，按类别处理错误时不需要区分
+  TypeConversionWithSource {
+    message: String,
+    source: Arc<dyn std::error::Error + Send + Sync>,
+  },
+
   /// 无效的查询参数
   ///
   /// 当查询参数格式不正确时返回此错误
@@ -105,6 +154,44 @@ pub enum ParseError {
   ///
   /// 当路径段的字面量内容不匹配时返回此错误
   SegmentMismatch { expected: String, actual: String, position: usize },
+
+  /// 参数值不满足约束
+  ///
+  /// 当路径参数的值不匹配模式中声明的正则约束时返回此错误
+  ConstraintViolation { name: String, value: String, pattern: String },
+
+  /// 路径带有末尾斜杠，应重定向到规范路径
+  ///
+  /// 由 [`TrailingSlash::Redirect`](crate::parser::TrailingSlash::Redirect) 模式产生
+  TrailingSlashRedirect { canonical: String },
+}
+
+/// 解析错误的类别
+///
+/// 与 [`ParseError`] 的变体一一对应但不携带数据，便于按类别映射 HTTP 状态码或本地化文案，
+/// 而不必匹配 `Display` 文本。以后可能新增类别，匹配时需要保留通配分支。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+  /// 无效的路径格式
+  InvalidPath,
+  /// 缺少必需的参数
+  MissingParameter,
+  /// 类型转换失败
+  TypeConversion,
+  /// 无效的查询参数
+  InvalidQuery,
+  /// URL 编码/解码错误
+  UrlEncoding,
+  /// 路径段数量不匹配
+  SegmentCountMismatch,
+  /// 路径段内容不匹配
+  SegmentMismatch,
+  /// 参数值不满足约束
+  ConstraintViolation,
+  /// 末尾斜杠重定向
+  TrailingSlashRedirect,
 }
 
 impl fmt::Display for ParseError {
@@ -116,7 +203,7 @@ impl fmt::Display for ParseError {
       ParseError::MissingParameter(param) => {
         write!(f, "Missing required parameter: {param}")
       }
-      ParseError::TypeConversion(msg) => {
+      ParseError::TypeConversion(msg) | ParseError::TypeConversionWithSource { message: msg, .. } => {
         write!(f, "Type conversion error: {msg}")
       }
       ParseError::InvalidQuery(msg) => {
@@ -138,14 +225,73 @@ impl fmt::Display for ParseError {
           "Path segment mismatch at position {position}: expected '{expected}', found '{actual}'"
         )
       }
+      ParseError::ConstraintViolation { name, value, pattern } => {
+        write!(
+          f,
+          "Parameter constraint violation: '{value}' does not satisfy pattern '{pattern}' for parameter '{name}'"
+        )
+      }
+      ParseError::TrailingSlashRedirect { canonical } => {
+        write!(f, "Trailing slash redirect: canonical path is '{canonical}'")
+      }
     }
   }
 }
 
 impl std::error::Error for ParseError {
   fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-    // 目前没有嵌套的错误源
-    None
+    match self {
+      ParseError::TypeConversionWithSource { source, .. } => Some(source.as_ref()),
+      _ => None,
+    }
+  }
+}
+
+/// 错误源无法直接比较，`TypeConversionWithSource` 只比较消息
+impl PartialEq for ParseError {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (ParseError::InvalidPath(a), ParseError::InvalidPath(b)) => a == b,
+      (ParseError::MissingParameter(a), ParseError::MissingParameter(b)) => a == b,
+      (ParseError::TypeConversion(a), ParseError::TypeConversion(b)) => a == b,
+      (
+        ParseError::TypeConversionWithSource { message, .. },
+        ParseError::TypeConversionWithSource {
+          message: other_message, ..
+        },
+      ) => message == other_message,
+      (ParseError::InvalidQuery(a), ParseError::InvalidQuery(b)) => a == b,
+      (ParseError::UrlEncoding(a), ParseError::UrlEncoding(b)) => a == b,
+      (
+        ParseError::SegmentCountMismatch { expected, actual },
+        ParseError::SegmentCountMismatch {
+          expected: other_expected,
+          actual: other_actual,
+        },
+      ) => expected == other_expected && actual == other_actual,
+      (
+        ParseError::SegmentMismatch {
+          expected,
+          actual,
+          position,
+        },
+        ParseError::SegmentMismatch {
+          expected: other_expected,
+          actual: other_actual,
+          position: other_position,
+        },
+      ) => expected == other_expected && actual == other_actual && position == other_position,
+      (
+        ParseError::ConstraintViolation { name, value, pattern },
+        ParseError::ConstraintViolation {
+          name: other_name,
+          value: other_value,
+          pattern: other_pattern,
+        },
+      ) => name == other_name && value == other_value && pattern == other_pattern,
+      (ParseError::TrailingSlashRedirect { canonical }, ParseError::TrailingSlashRedirect { canonical: other }) => canonical == other,
+      _ => false,
+    }
   }
 }
 
@@ -156,6 +302,62 @@ pub type ParseResult<T> = Result<T, ParseError>;
 
 /// 错误构造辅助函数
 impl ParseError {
+  /// 获取错误类别
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::error::{ParseError, ParseErrorKind};
+  ///
+  /// let status = match ParseError::missing_parameter("id").kind() {
+  ///   ParseErrorKind::SegmentCountMismatch | ParseErrorKind::SegmentMismatch => 404,
+  ///   _ => 400,
+  /// };
+  /// assert_eq!(status, 400);
+  /// ```
+  pub fn kind(&self) -> ParseErrorKind {
+    match self {
+      ParseError::InvalidPath(_) => ParseErrorKind::InvalidPath,
+      ParseError::MissingParameter(_) => ParseErrorKind::MissingParameter,
+      ParseError::TypeConversion(_) | ParseError::TypeConversionWithSource { .. } => ParseErrorKind::TypeConversion,
+      ParseError::InvalidQuery(_) => ParseErrorKind::InvalidQuery,
+      ParseError::UrlEncoding(_) => ParseErrorKind::UrlEncoding,
+      ParseError::SegmentCountMismatch { .. } => ParseErrorKind::SegmentCountMismatch,
+      ParseError::SegmentMismatch { .. } => ParseErrorKind::SegmentMismatch,
+      ParseError::ConstraintViolation { .. } => ParseErrorKind::ConstraintViolation,
+      ParseError::TrailingSlashRedirect { .. } => ParseErrorKind::TrailingSlashRedirect,
+    }
+  }
+
+  /// 获取对应的 HTTP 状态码
+  ///
+  /// 按 [`ParseErrorKind`] 映射：
+  /// - 路径结构不匹配（`InvalidPath`、`SegmentCountMismatch`、`SegmentMismatch`）返回 404
+  /// - `TrailingSlashRedirect` 返回 308，调用方应把规范路径写入 `Location`
+  /// - 其他错误（参数缺失、类型转换、约束不满足、查询参数、URL 编码）返回 400
+  ///
+  /// `MissingParameter` 同时来自路径和查询参数，因此按请求格式错误处理。
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::error::ParseError;
+  ///
+  /// assert_eq!(ParseError::segment_mismatch("users", "posts", 0).http_status(), 404);
+  /// assert_eq!(ParseError::invalid_query("bad").http_status(), 400);
+  /// ```
+  pub fn http_status(&self) -> u16 {
+    match self.kind() {
+      ParseErrorKind::InvalidPath | ParseErrorKind::SegmentCountMismatch | ParseErrorKind::SegmentMismatch => 404,
+      ParseErrorKind::TrailingSlashRedirect => 308,
+      ParseErrorKind::MissingParameter
+      | ParseErrorKind::TypeConversion
+      | ParseErrorKind::InvalidQuery
+      | ParseErrorKind::UrlEncoding
+      | ParseErrorKind::ConstraintViolation => 400,
+    }
+  }
+
   /// 创建无效路径错误
   pub fn invalid_path<S: Into<String>>(msg: S) -> Self {
     ParseError::InvalidPath(msg.into())
@@ -171,6 +373,50 @@ impl ParseError {
     ParseError::TypeConversion(msg.into())
   }
 
+  /// 创建带有底层错误源的类型转换错误
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use std::error::Error;
+  /// use ruled_router::error::ParseError;
+  ///
+  /// let source = "abc".parse::<u32>().unwrap_err();
+  /// let err = ParseError::type_conversion_with_source("Cannot convert 'abc' to u32", source);
+  /// assert_eq!(err.to_string(), "Type conversion error: Cannot convert 'abc' to u32");
+  /// assert!(err.source().is_some());
+  /// ```
+  pub fn type_conversion_with_source<S, E>(msg: S, source: E) -> Self
+  where
+    S: Into<String>,
+    E: std::error::Error + Send + Sync + 'static,
+  {
+    ParseError::TypeConversionWithSource {
+      message: msg.into(),
+      source: Arc::new(source),
+    }
+  }
+
+  /// 获取类型转换错误的消息
+  ///
+  /// 同时覆盖 `TypeConversion` 与 `TypeConversionWithSource`，其他错误返回 `None`
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::prelude::*;
+  ///
+  /// let err = u32::from_param("abc").unwrap_err();
+  /// assert_eq!(err.type_conversion_message(), Some("Cannot convert 'abc' to u32"));
+  /// assert_eq!(ParseError::missing_parameter("id").type_conversion_message(), None);
+  /// ```
+  pub fn type_conversion_message(&self) -> Option<&str> {
+    match self {
+      ParseError::TypeConversion(msg) | ParseError::TypeConversionWithSource { message: msg, .. } => Some(msg),
+      _ => None,
+    }
+  }
+
   /// 创建无效查询错误
   pub fn invalid_query<S: Into<String>>(msg: S) -> Self {
     ParseError::InvalidQuery(msg.into())
@@ -194,6 +440,22 @@ impl ParseError {
       position,
     }
   }
+
+  /// 创建末尾斜杠重定向错误
+  pub fn trailing_slash_redirect<S: Into<String>>(canonical: S) -> Self {
+    ParseError::TrailingSlashRedirect {
+      canonical: canonical.into(),
+    }
+  }
+
+  /// 创建参数约束不满足错误
+  pub fn constraint_violation<S1: Into<String>, S2: Into<String>, S3: Into<String>>(name: S1, value: S2, pattern: S3) -> Self {
+    ParseError::ConstraintViolation {
+      name: name.into(),
+      value: value.into(),
+      pattern: pattern.into(),
+    }
+  }
 }
 
 /// RouteState 的实用方法实现
@@ -312,6 +574,32 @@ impl<T> RouteState<T> {
   }
 }
 
+/// 用于日志的简短表示：`NoSubRoute`、`SubRoute(<inner>)` 或解析失败的摘要
+impl<T: fmt::Display> fmt::Display for RouteState<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RouteState::NoSubRoute => write!(f, "NoSubRoute"),
+      RouteState::SubRoute(sub) => write!(f, "SubRoute({sub})"),
+      RouteState::ParseFailed {
+        remaining_path,
+        attempted_patterns,
+        closest_match,
+      } => {
+        write!(
+          f,
+          "ParseFailed: remaining='{}' tried=[{}]",
+          remaining_path,
+          attempted_patterns.join(", ")
+        )?;
+        if let Some(closest) = closest_match {
+          write!(f, " closest='{}'", closest.pattern)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
 /// ClosestMatch 的实用方法实现
 impl ClosestMatch {
   /// 创建一个新的最接近匹配信息
@@ -322,6 +610,46 @@ impl ClosestMatch {
       failure_reason: failure_reason.into(),
     }
   }
+
+  /// 在候选模式中找出与路径字面量前缀最长的一个
+  ///
+  /// 按路径解析器的规则拆分模式（转义的 `\:export` 视为字面量 `:export`），从头逐段比较字面量段与路径段，
+  /// 遇到参数、通配符或不相等的段时停止，`matched_length` 为匹配部分在路径中的字节长度。
+  /// 无法解析的模式会被跳过。长度相同时取先出现的模式，
+  /// 没有任何模式匹配到字面量段时返回 None。
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::error::ClosestMatch;
+  ///
+  /// let closest = ClosestMatch::find("/users/settings", &["/posts/:id", "/users/:id/profile"], "no match").unwrap();
+  /// assert_eq!(closest.pattern, "/users/:id/profile");
+  /// assert_eq!(closest.matched_length, "/users".len());
+  /// ```
+  pub fn find<S: Into<String>>(path: &str, patterns: &[&str], failure_reason: S) -> Option<Self> {
+    let path_segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+    let mut best: Option<(&str, usize)> = None;
+    for pattern in patterns {
+      let Ok(pattern_segments) = PathParser::parse_pattern(pattern) else {
+        continue;
+      };
+      let mut matched_length = 0;
+      for (pattern_segment, path_segment) in pattern_segments.iter().zip(&path_segments) {
+        match pattern_segment {
+          PathSegment::Literal(literal) if literal == path_segment => matched_length += 1 + path_segment.len(),
+          _ => break,
+        }
+      }
+
+      if matched_length > 0 && best.is_none_or(|(_, length)| matched_length > length) {
+        best = Some((pattern, matched_length));
+      }
+    }
+
+    best.map(|(pattern, matched_length)| ClosestMatch::new(pattern, matched_length, failure_reason))
+  }
 }
 
 /// RouteDebugInfo 的实用方法实现
@@ -382,6 +710,111 @@ mod tests {
       error.to_string(),
       "Path segment mismatch at position 1: expected 'user', found 'admin'"
     );
+
+    let error = ParseError::constraint_violation("id", "abc", r"\d+");
+    assert_eq!(
+      error.to_string(),
+      r"Parameter constraint violation: 'abc' does not satisfy pattern '\d+' for parameter 'id'"
+    );
+
+    let error = ParseError::trailing_slash_redirect("/users/1");
+    assert_eq!(error.to_string(), "Trailing slash redirect: canonical path is '/users/1'");
+  }
+
+  #[test]
+  fn test_error_kind() {
+    assert_eq!(ParseError::invalid_path("x").kind(), ParseErrorKind::InvalidPath);
+    assert_eq!(ParseError::missing_parameter("id").kind(), ParseErrorKind::MissingParameter);
+    assert_eq!(ParseError::type_conversion("x").kind(), ParseErrorKind::TypeConversion);
+    assert_eq!(ParseError::invalid_query("x").kind(), ParseErrorKind::InvalidQuery);
+    assert_eq!(ParseError::url_encoding("x").kind(), ParseErrorKind::UrlEncoding);
+    assert_eq!(
+      ParseError::segment_count_mismatch(3, 2).kind(),
+      ParseErrorKind::SegmentCountMismatch
+    );
+    assert_eq!(ParseError::segment_mismatch("a", "b", 0).kind(), ParseErrorKind::SegmentMismatch);
+    assert_eq!(
+      ParseError::constraint_violation("id", "abc", r"\d+").kind(),
+      ParseErrorKind::ConstraintViolation
+    );
+    assert_eq!(
+      ParseError::trailing_slash_redirect("/users").kind(),
+      ParseErrorKind::TrailingSlashRedirect
+    );
+  }
+
+  #[test]
+  fn test_http_status() {
+    assert_eq!(ParseError::invalid_path("x").http_status(), 404);
+    assert_eq!(ParseError::segment_count_mismatch(3, 2).http_status(), 404);
+    assert_eq!(ParseError::segment_mismatch("a", "b", 0).http_status(), 404);
+    assert_eq!(ParseError::missing_parameter("id").http_status(), 400);
+    assert_eq!(ParseError::type_conversion("x").http_status(), 400);
+    assert_eq!(ParseError::invalid_query("x").http_status(), 400);
+    assert_eq!(ParseError::url_encoding("x").http_status(), 400);
+    assert_eq!(ParseError::constraint_violation("id", "abc", r"\d+").http_status(), 400);
+    assert_eq!(ParseError::trailing_slash_redirect("/users").http_status(), 308);
+  }
+
+  #[test]
+  fn test_closest_match_find() {
+    let patterns = ["/users/:id", "/admin/users/list", "/admin/settings"];
+
+    let closest = ClosestMatch::find("/admin/users/edit", &patterns, "no route").unwrap();
+    assert_eq!(closest, ClosestMatch::new("/admin/users/list", "/admin/users".len(), "no route"));
+
+    // 参数段不计入字面量前缀
+    let closest = ClosestMatch::find("/users/1/extra", &patterns, "no route").unwrap();
+    assert_eq!(closest.pattern, "/users/:id");
+    assert_eq!(closest.matched_length, "/users".len());
+
+    // 长度相同时取先出现的模式
+    let closest = ClosestMatch::find("/admin/other", &patterns, "no route").unwrap();
+    assert_eq!(closest.pattern, "/admin/users/list");
+
+    assert_eq!(ClosestMatch::find("/unknown", &patterns, "no route"), None);
+    assert_eq!(ClosestMatch::find("/users", &[], "no route"), None);
+
+    // 转义的字面量按解析后的值比较，与路径解析器一致
+    let escaped = [r"/legacy/\:export/:format", "/legacy/:id"];
+    let closest = ClosestMatch::find("/legacy/:export", &escaped, "no route").unwrap();
+    assert_eq!(closest.pattern, r"/legacy/\:export/:format");
+    assert_eq!(closest.matched_length, "/legacy/:export".len());
+  }
+
+  #[test]
+  fn test_route_state_display() {
+    assert_eq!(RouteState::<u32>::NoSubRoute.to_string(), "NoSubRoute");
+    assert_eq!(RouteState::SubRoute(42).to_string(), "SubRoute(42)");
+
+    let failed = RouteState::<u32>::parse_failed("/userz/1", vec!["/users".to_string(), "/posts".to_string()], None);
+    assert_eq!(failed.to_string(), "ParseFailed: remaining='/userz/1' tried=[/users, /posts]");
+
+    let failed = RouteState::<u32>::parse_failed(
+      "/users/1/x",
+      vec!["/users/:id".to_string()],
+      Some(ClosestMatch::new("/users/:id", 6, "extra segment")),
+    );
+    assert_eq!(
+      failed.to_string(),
+      "ParseFailed: remaining='/users/1/x' tried=[/users/:id] closest='/users/:id'"
+    );
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_route_state_serde_roundtrip() {
+    let failed = RouteState::<u32>::parse_failed(
+      "/users/1/x",
+      vec!["/users/:id".to_string(), "/posts".to_string()],
+      Some(ClosestMatch::new("/users/:id", 6, "extra segment")),
+    );
+    let json = serde_json::to_string(&failed).unwrap();
+    assert_eq!(serde_json::from_str::<RouteState<u32>>(&json).unwrap(), failed);
+
+    let sub = RouteState::SubRoute(7u32);
+    let json = serde_json::to_string(&sub).unwrap();
+    assert_eq!(serde_json::from_str::<RouteState<u32>>(&json).unwrap(), sub);
   }
 
   #[test]
@@ -3,9 +3,9 @@
 //! 提供将结构化数据格式化为路径和查询字符串的功能
 
 use crate::error::ParseError;
-use crate::parser::{PathParser, QueryParser};
+use crate::parser::{PathParser, PathSegment, QueryParser};
 use crate::traits::ToParam;
-use crate::utils::{format_query_string, normalize_path};
+use crate::utils::{format_query_string, format_query_string_sorted, join_url, normalize_path};
 use std::collections::HashMap;
 
 /// 路径格式化器
@@ -52,6 +52,57 @@ impl PathFormatter {
     self.parser.format_path(params)
   }
 
+  /// 部分格式化路径，缺少的参数保留为占位符
+  ///
+  /// 与 [`PathFormatter::format`] 不同，缺少参数时不会报错，而是按模式语法原样保留
+  /// `:name`、`?:name` 或 `*name`，适合导出给前端路由使用的 URL 模板。
+  /// 带正则约束的参数保留为不含约束的 `:name`。
+  ///
+  /// # 参数
+  ///
+  /// * `params` - 路径参数映射，可以只包含部分参数
+  ///
+  /// # 返回值
+  ///
+  /// 替换了已提供参数的路径字符串
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::formatter::PathFormatter;
+  /// use std::collections::HashMap;
+  ///
+  /// let formatter = PathFormatter::new("/users/:id/posts/:post_id").unwrap();
+  /// let mut params = HashMap::new();
+  /// params.insert("id".to_string(), "123".to_string());
+  ///
+  /// assert_eq!(formatter.format_partial(&params), "/users/123/posts/:post_id");
+  /// ```
+  pub fn format_partial(&self, params: &HashMap<String, String>) -> String {
+    let segments: Vec<String> = self
+      .parser
+      .segments()
+      .iter()
+      .map(|segment| match segment {
+        PathSegment::Literal(literal) => literal.clone(),
+        PathSegment::Parameter(name) | PathSegment::ConstrainedParameter { name, .. } => match params.get(name) {
+          Some(value) => crate::utils::url_encode(value),
+          None => format!(":{name}"),
+        },
+        PathSegment::OptionalParameter(name) => match params.get(name) {
+          Some(value) => crate::utils::url_encode(value),
+          None => format!("?:{name}"),
+        },
+        PathSegment::Wildcard(name) => match params.get(name) {
+          Some(value) => crate::utils::url_encode_with(value, &crate::utils::EncodeSet::PATH),
+          None => format!("*{name}"),
+        },
+      })
+      .collect();
+
+    format!("/{}", segments.join("/"))
+  }
+
   /// 格式化路径（使用类型安全的参数）
   ///
   /// # 参数
@@ -67,6 +118,16 @@ impl PathFormatter {
   }
 }
 
+/// 合并查询参数时对已存在的键的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeMode {
+  /// 用新的值替换已有的所有值
+  #[default]
+  Replace,
+  /// 把新的值追加到已有的值之后
+  Append,
+}
+
 /// 查询格式化器
 ///
 /// 用于将查询结构体格式化为查询字符串
@@ -138,6 +199,40 @@ impl QueryFormatter {
     self
   }
 
+  /// 合并另一个查询解析器中的参数
+  ///
+  /// # 参数
+  ///
+  /// * `other` - 要合并进来的参数
+  /// * `mode` - 两边都存在的键是替换还是追加
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::formatter::{MergeMode, QueryFormatter};
+  /// use ruled_router::parser::QueryParser;
+  ///
+  /// let mut formatter = QueryFormatter::new();
+  /// formatter.set("page", 3).add("tags", "rust");
+  ///
+  /// let overrides = QueryParser::new("page=1&tags=web").unwrap();
+  /// formatter.merge(&overrides, MergeMode::Append);
+  /// assert_eq!(formatter.format_sorted(), "page=3&page=1&tags=rust&tags=web");
+  /// ```
+  pub fn merge(&mut self, other: &QueryParser, mode: MergeMode) -> &mut Self {
+    for (key, values) in other.params() {
+      match mode {
+        MergeMode::Replace => {
+          self.params.insert(key.clone(), values.clone());
+        }
+        MergeMode::Append => {
+          self.params.entry(key.clone()).or_default().extend(values.iter().cloned());
+        }
+      }
+    }
+    self
+  }
+
   /// 格式化为查询字符串
   ///
   /// # 返回值
@@ -162,6 +257,24 @@ impl QueryFormatter {
     format_query_string(&self.params)
   }
 
+  /// 按键排序格式化为查询字符串
+  ///
+  /// 输出顺序确定，同一个键的多个值保持添加顺序
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::formatter::QueryFormatter;
+  ///
+  /// let mut formatter = QueryFormatter::new();
+  /// formatter.set("size", 20).add("tags", "rust").add("tags", "web").set("page", 1);
+  ///
+  /// assert_eq!(formatter.format_sorted(), "page=1&size=20&tags=rust&tags=web");
+  /// ```
+  pub fn format_sorted(&self) -> String {
+    format_query_string_sorted(&self.params)
+  }
+
   /// 格式化为完整的查询字符串（包含 '?' 前缀）
   ///
   /// # 返回值
@@ -265,6 +378,36 @@ impl UrlFormatter {
     Ok(format!("{}{}", normalize_path(&path), query))
   }
 
+  /// 格式化为绝对 URL
+  ///
+  /// # 参数
+  ///
+  /// * `base` - 基础 URL，可以带路径前缀和已有的查询参数
+  /// * `path_params` - 路径参数
+  ///
+  /// # 返回值
+  ///
+  /// 拼接到基础 URL 上的完整 URL，拼接规则见 [`join_url`]
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::formatter::UrlFormatter;
+  /// use std::collections::HashMap;
+  ///
+  /// let mut formatter = UrlFormatter::new("/users/:id").unwrap();
+  /// formatter.query_formatter_mut().set("page", 2);
+  ///
+  /// let mut path_params = HashMap::new();
+  /// path_params.insert("id".to_string(), "123".to_string());
+  ///
+  /// let url = formatter.format_absolute("https://example.com", &path_params).unwrap();
+  /// assert_eq!(url, "https://example.com/users/123?page=2");
+  /// ```
+  pub fn format_absolute(&self, base: &str, path_params: &HashMap<String, String>) -> Result<String, ParseError> {
+    Ok(join_url(base, &self.format(path_params)?))
+  }
+
   /// 格式化完整的 URL（使用类型安全的路径参数）
   pub fn format_typed<T: ToParam>(&self, path_params: &HashMap<String, T>) -> Result<String, ParseError> {
     let path = self.path_formatter.format_typed(path_params)?;
@@ -293,6 +436,24 @@ mod tests {
     assert_eq!(path, "/users/123/posts/456");
   }
 
+  #[test]
+  fn test_path_formatter_partial() {
+    let formatter = PathFormatter::new("/users/:id/posts/:post_id").unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), "123".to_string());
+    assert_eq!(formatter.format_partial(&params), "/users/123/posts/:post_id");
+    assert!(formatter.format(&params).is_err());
+
+    params.insert("post_id".to_string(), "a b".to_string());
+    assert_eq!(formatter.format_partial(&params), formatter.format(&params).unwrap());
+
+    // 可选参数和通配符按模式语法保留
+    let formatter = PathFormatter::new("/docs/?:page/*rest").unwrap();
+    assert_eq!(formatter.format_partial(&HashMap::new()), "/docs/?:page/*rest");
+    assert_eq!(PathFormatter::new("/").unwrap().format_partial(&HashMap::new()), "/");
+  }
+
   #[test]
   fn test_path_formatter_typed() {
     let formatter = PathFormatter::new("/users/:id").unwrap();
@@ -319,6 +480,21 @@ mod tests {
     assert!(query.contains("tags=web"));
   }
 
+  #[test]
+  fn test_query_formatter_borrowed_values() {
+    let q = "rust web".to_string();
+    let role = "admin".to_string();
+
+    let mut by_ref = QueryFormatter::new();
+    by_ref.set("q", &q).set("role", &role).add("tag", std::borrow::Cow::Borrowed("a"));
+    let mut by_value = QueryFormatter::new();
+    by_value.set("q", q.clone()).set("role", role.clone()).add("tag", "a");
+
+    assert_eq!(by_ref.format_sorted(), by_value.format_sorted());
+    // 借用后原值仍然可用
+    assert_eq!((q.as_str(), role.as_str()), ("rust web", "admin"));
+  }
+
   #[test]
   fn test_query_formatter_with_prefix() {
     let mut formatter = QueryFormatter::new();
@@ -345,6 +521,15 @@ mod tests {
     assert!(query.contains("colors=blue"));
   }
 
+  #[test]
+  fn test_query_formatter_format_sorted() {
+    let mut formatter = QueryFormatter::new();
+    formatter.set("size", 20).add("b", "2").add("b", "1").set("a", "x");
+
+    assert_eq!(formatter.format_sorted(), "a=x&b=2&b=1&size=20");
+    assert_eq!(formatter.format_sorted(), formatter.clone().format_sorted());
+  }
+
   #[test]
   fn test_url_formatter() {
     let mut formatter = UrlFormatter::new("/users/:id").unwrap();
@@ -361,6 +546,61 @@ mod tests {
     assert!(url.contains("size=20"));
   }
 
+  #[test]
+  fn test_url_formatter_format_absolute() {
+    let mut formatter = UrlFormatter::new("/users/:id").unwrap();
+    formatter.query_formatter_mut().set("page", 1);
+
+    let mut path_params = HashMap::new();
+    path_params.insert("id".to_string(), "123".to_string());
+
+    assert_eq!(
+      formatter.format_absolute("https://example.com", &path_params).unwrap(),
+      "https://example.com/users/123?page=1"
+    );
+    assert_eq!(
+      formatter.format_absolute("https://example.com/app/", &path_params).unwrap(),
+      "https://example.com/app/users/123?page=1"
+    );
+    assert_eq!(
+      formatter.format_absolute("https://example.com/app?lang=en", &path_params).unwrap(),
+      "https://example.com/app/users/123?lang=en&page=1"
+    );
+
+    // 缺少路径参数时返回错误
+    assert!(formatter.format_absolute("https://example.com", &HashMap::new()).is_err());
+  }
+
+  #[test]
+  fn test_query_formatter_merge_replace() {
+    let incoming = QueryParser::new("status=open&status=closed&page=3").unwrap();
+    let mut formatter = QueryFormatter::from_parser(&incoming);
+
+    // 保留原有的过滤条件，但强制回到第一页
+    let overrides = QueryParser::new("page=1&sort=name").unwrap();
+    formatter.merge(&overrides, MergeMode::Replace);
+    assert_eq!(formatter.format_sorted(), "page=1&sort=name&status=open&status=closed");
+
+    // 替换多值键时，所有旧值都被移除
+    let overrides = QueryParser::new("status=pending").unwrap();
+    formatter.merge(&overrides, MergeMode::Replace);
+    assert_eq!(formatter.params().get("status"), Some(&vec!["pending".to_string()]));
+  }
+
+  #[test]
+  fn test_query_formatter_merge_append() {
+    let mut formatter = QueryFormatter::new();
+    formatter.add("tags", "rust").set("page", 1);
+
+    let other = QueryParser::new("tags=web&tags=wasm&q=router").unwrap();
+    formatter.merge(&other, MergeMode::Append);
+    assert_eq!(formatter.format_sorted(), "page=1&q=router&tags=rust&tags=web&tags=wasm");
+
+    // 合并空参数不改变结果
+    formatter.merge(&QueryParser::new("").unwrap(), MergeMode::Append);
+    assert_eq!(formatter.len(), 3);
+  }
+
   #[test]
   fn test_query_formatter_operations() {
     let mut formatter = QueryFormatter::new();
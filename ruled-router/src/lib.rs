@@ -4,6 +4,9 @@
 //! 通过派生宏自动生成解析器和格式化器。
 
 //! 模块定义
+pub mod analysis;
+pub mod codegen;
+pub mod dispatch;
 pub mod error;
 pub mod formatter;
 pub mod parser;
@@ -11,16 +14,76 @@ pub mod prelude;
 pub mod traits;
 pub mod utils;
 
+// serde 查询参数模块（只有在启用 serde-query feature 时才编译）
+#[cfg(feature = "serde-query")]
+pub mod serde_query;
+
+// axum 集成模块（只有在启用 axum feature 时才编译）
+#[cfg(feature = "axum")]
+pub mod axum;
+
 // DOM 模块（只有在启用 dom feature 时才编译）
 #[cfg(feature = "dom")]
 pub mod dom;
 
 // 重新导出核心类型
-pub use error::{ParseError, ParseResult};
-pub use formatter::{PathFormatter, QueryFormatter, UrlFormatter};
-pub use parser::{PathParser, QueryParser};
-pub use traits::{FromParam, NoSubRouter, Query, RouteMatcher, RouterData, ToParam};
+pub use error::{ParseError, ParseErrorKind, ParseResult, QueryWarning};
+pub use formatter::{MergeMode, PathFormatter, QueryFormatter, UrlFormatter};
+pub use parser::{PathParser, PathRouter, QueryParser};
+pub use traits::{FromParam, NoSubRouter, Paginated, Query, RouteMatcher, RouterData, ToParam, TryFromParam};
+pub use utils::{build_url, parse_url};
+
+/// `RouterData` 的别名
+///
+/// 规范名称是 `RouterData`，`Router` 保留给沿用旧命名的代码
+pub use traits::RouterData as Router;
 
 // 重新导出派生宏（当启用 derive 特性时）
 #[cfg(feature = "derive")]
-pub use ruled_router_derive::{QueryDerive, QueryString, RouterData, RouterMatch};
+pub use ruled_router_derive::{routes, Param, QueryDerive, QueryString, RouterData, RouterMatch};
+
+// serde 查询参数派生宏（需要同时启用 derive 和 serde-query 特性）
+#[cfg(all(feature = "derive", feature = "serde-query"))]
+pub use ruled_router_derive::QuerySerde;
+
+/// 派生宏生成的代码使用的内部项，不属于公开 API
+#[doc(hidden)]
+pub mod __private {
+  use std::borrow::Cow;
+
+  use crate::error::ParseResult;
+  use crate::parser::PathParser;
+  use crate::traits::RouterData;
+
+  /// 带正则约束的路由模式引用此类型，未启用 `regex` feature 时在编译期报错
+  #[cfg(feature = "regex")]
+  pub struct RequireRegexFeature;
+
+  /// 取得路由主模式的解析器，有缓存时借用缓存
+  pub fn route_parser<R: RouterData>() -> ParseResult<Cow<'static, PathParser>> {
+    match R::path_parser_caches().first() {
+      Some(cache) => cache.get().map(Cow::Borrowed),
+      None => PathParser::new(R::pattern()).map(Cow::Owned),
+    }
+  }
+
+  /// 依次取得路由主模式和别名的解析器
+  ///
+  /// 优先借用 [`RouterData::path_parser_caches`] 中的缓存，没有缓存的路由每次编译模式
+  pub fn route_parsers<R: RouterData>() -> impl Iterator<Item = ParseResult<Cow<'static, PathParser>>> {
+    let caches = R::path_parser_caches();
+    let compiled = caches.is_empty().then(|| {
+      std::iter::once(R::pattern())
+        .chain(R::aliases().iter().copied())
+        .map(|pattern| PathParser::new(pattern).map(Cow::Owned))
+    });
+    caches
+      .iter()
+      .map(|cache| cache.get().map(Cow::Borrowed))
+      .chain(compiled.into_iter().flatten())
+  }
+}
+
+/// `#[derive(RouterData)]` 的别名，配合 `#[route(...)]` 或 `#[router(...)]` 使用
+#[cfg(feature = "derive")]
+pub use ruled_router_derive::RouterData as Router;
@@ -4,9 +4,11 @@
 
 pub mod path;
 pub mod query;
+pub mod router;
 pub mod types;
 
 // 重新导出主要类型
-pub use path::PathParser;
+pub use path::{ParamInfo, ParamKind, PathMatchFailure, PathParser, PathParserCache, PathParserOptions, PathSegment, TrailingSlash};
 pub use query::QueryParser;
+pub use router::PathRouter;
 pub use types::*;
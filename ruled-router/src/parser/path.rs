@@ -3,8 +3,11 @@
 //! 提供路径模式匹配和参数提取功能
 
 use crate::error::{ParseError, ParseResult};
-use crate::utils::{split_path_segments, url_decode};
+use crate::utils::{path_from_bytes, split_path_query, split_path_segments, split_path_segments_raw, url_decode_path};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
 
 /// 路径解析器
 ///
@@ -15,6 +18,42 @@ pub struct PathParser {
   pattern: String,
   /// 解析后的模式段
   pattern_segments: Vec<PathSegment>,
+  /// 匹配选项
+  options: PathParserOptions,
+  /// 编译后的参数约束，按参数名索引
+  #[cfg(feature = "regex")]
+  constraints: HashMap<String, regex::Regex>,
+}
+
+/// 路径解析器的匹配选项
+///
+/// 通过 [`PathParser::with_options`] 应用到解析器上，只影响匹配行为，
+/// 不影响 [`PathParser::format_path`] 的输出
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathParserOptions {
+  /// 字面量段是否忽略大小写（参数值始终保持原样）
+  pub case_insensitive_literals: bool,
+  /// 路径末尾斜杠的处理方式
+  pub trailing_slash: TrailingSlash,
+  /// 是否拒绝路径中间的空段（例如 `/a//b`），默认把重复的斜杠视为一个
+  ///
+  /// 末尾斜杠仍由 `trailing_slash` 决定
+  pub strict_slashes: bool,
+}
+
+/// 路径末尾斜杠的处理方式
+///
+/// 根路径 "/" 本身不视为带有末尾斜杠
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+  /// 拒绝带末尾斜杠的路径
+  Strict,
+  /// 接受并忽略末尾斜杠（默认）
+  #[default]
+  Ignore,
+  /// 去掉末尾斜杠后能够匹配时，返回 [`ParseError::TrailingSlashRedirect`]，
+  /// 由上层（例如 Web 框架）重定向到规范路径
+  Redirect,
 }
 
 /// 路径段类型
@@ -24,12 +63,70 @@ pub enum PathSegment {
   Literal(String),
   /// 参数段，例如 ":id"
   Parameter(String),
+  /// 带正则约束的参数段，例如 `:id(\d+)` 或 `{slug:[a-z0-9-]+}`
+  ///
+  /// 需要启用 `regex` feature 才能使用
+  ConstrainedParameter { name: String, regex: String },
   /// 可选参数段，例如 "?:optional"
   OptionalParameter(String),
   /// 通配符段，例如 "*path"
+  ///
+  /// 位于模式末尾时匹配所有剩余段；后面还有其他模式段时（如 "/files/*path/download"），
+  /// 只匹配让剩余模式成功所需的最少段数
   Wildcard(String),
 }
 
+/// 路径参数的种类，供代码生成和文档工具判断参数是否可以省略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+  /// 必选参数，包括带正则约束的参数
+  Required,
+  /// 可选参数，例如 "?:format"
+  Optional,
+  /// 通配符参数，匹配一个或多个段，例如 "*path"
+  Wildcard,
+}
+
+/// 路径参数的名称和种类
+///
+/// 由 [`PathParser::parameter_info`] 返回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamInfo {
+  /// 参数名
+  pub name: String,
+  /// 参数种类
+  pub kind: ParamKind,
+}
+
+/// 路径匹配失败的详细信息
+///
+/// 由 [`PathParser::match_path_detailed`] 返回，描述路径在哪个段匹配失败
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMatchFailure<'a> {
+  /// 失败位置在实际路径中的段索引（从 0 开始）
+  pub index: usize,
+  /// 匹配失败的模式段；如果路径比模式多出未匹配的段则为 None
+  pub segment: Option<&'a PathSegment>,
+  /// 实际遇到的路径段；如果路径段已经用完则为 None
+  pub actual: Option<String>,
+  /// 对应的解析错误
+  pub error: ParseError,
+}
+
+impl fmt::Display for PathMatchFailure<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} (at path segment {})", self.error, self.index)
+  }
+}
+
+impl std::error::Error for PathMatchFailure<'_> {}
+
+impl From<PathMatchFailure<'_>> for ParseError {
+  fn from(failure: PathMatchFailure<'_>) -> Self {
+    failure.error
+  }
+}
+
 impl PathParser {
   /// 创建新的路径解析器
   ///
@@ -52,16 +149,176 @@ impl PathParser {
     let pattern_segments = Self::parse_pattern(pattern)?;
     Ok(Self {
       pattern: pattern.to_string(),
+      options: PathParserOptions::default(),
+      #[cfg(feature = "regex")]
+      constraints: Self::compile_constraints(&pattern_segments)?,
       pattern_segments,
     })
   }
 
+  /// 设置匹配选项
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::{PathParser, PathParserOptions};
+  ///
+  /// let parser = PathParser::new("/users/:id").unwrap().with_options(PathParserOptions {
+  ///   case_insensitive_literals: true,
+  ///   ..Default::default()
+  /// });
+  /// assert!(parser.match_path("/Users/123").is_ok());
+  /// ```
+  pub fn with_options(mut self, options: PathParserOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  /// 设置字面量段是否忽略大小写
+  ///
+  /// 只影响字面量段的比较，参数值的大小写保持不变
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/users/:id").unwrap().with_case_insensitive_literals(true);
+  /// let params = parser.match_path("/USERS/Alice").unwrap();
+  /// assert_eq!(params.get("id"), Some(&"Alice".to_string()));
+  /// ```
+  pub fn with_case_insensitive_literals(mut self, enabled: bool) -> Self {
+    self.options.case_insensitive_literals = enabled;
+    self
+  }
+
+  /// 设置末尾斜杠的处理方式
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::error::ParseError;
+  /// use ruled_router::parser::{PathParser, TrailingSlash};
+  ///
+  /// let parser = PathParser::new("/users/:id").unwrap().with_trailing_slash(TrailingSlash::Redirect);
+  /// assert_eq!(
+  ///   parser.match_path("/users/123/"),
+  ///   Err(ParseError::trailing_slash_redirect("/users/123"))
+  /// );
+  /// ```
+  pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+    self.options.trailing_slash = trailing_slash;
+    self
+  }
+
+  /// 设置是否拒绝路径中间的空段
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/a/b").unwrap().with_strict_slashes(true);
+  /// assert!(parser.match_path("/a/b").is_ok());
+  /// assert!(parser.match_path("/a//b").is_err());
+  /// ```
+  pub fn with_strict_slashes(mut self, enabled: bool) -> Self {
+    self.options.strict_slashes = enabled;
+    self
+  }
+
+  /// 获取匹配选项
+  pub fn options(&self) -> &PathParserOptions {
+    &self.options
+  }
+
+  /// 启用 `strict_slashes` 时，返回路径中第一个空的中间段的索引
+  ///
+  /// 最后一个空段来自末尾斜杠，交给 `trailing_slash` 处理
+  fn empty_interior_segment(&self, path: &str) -> Option<usize> {
+    if !self.options.strict_slashes {
+      return None;
+    }
+    let segments = split_path_segments_raw(path);
+    segments[..segments.len().saturating_sub(1)]
+      .iter()
+      .position(|segment| segment.is_empty())
+  }
+
+  /// 比较字面量段，按选项决定是否忽略大小写
+  fn literal_matches(&self, expected: &str, actual: &str) -> bool {
+    actual == expected || (self.options.case_insensitive_literals && actual.to_lowercase() == expected.to_lowercase())
+  }
+
+  /// 编译模式中所有参数的正则约束
+  #[cfg(feature = "regex")]
+  fn compile_constraints(segments: &[PathSegment]) -> ParseResult<HashMap<String, regex::Regex>> {
+    let mut constraints = HashMap::new();
+    for segment in segments {
+      if let PathSegment::ConstrainedParameter { name, regex } = segment {
+        // 约束需要匹配整个段，而不是段中的一部分
+        let compiled = regex::Regex::new(&format!("^(?:{regex})$"))
+          .map_err(|e| ParseError::invalid_path(format!("Invalid constraint for parameter '{name}': {e}")))?;
+        constraints.insert(name.clone(), compiled);
+      }
+    }
+    Ok(constraints)
+  }
+
+  /// 解析带约束的参数段，支持 ":name(regex)" 和 "{name:regex}" 两种写法
+  fn parse_constrained_segment(segment: &str) -> ParseResult<Option<PathSegment>> {
+    let (name, regex) = if let Some(rest) = segment.strip_prefix(':') {
+      match (rest.find('('), rest.strip_suffix(')')) {
+        (Some(open), Some(inner)) => (&rest[..open], &inner[open + 1..]),
+        _ => return Ok(None),
+      }
+    } else if segment.starts_with('{') && segment.ends_with('}') {
+      match segment[1..segment.len() - 1].split_once(':') {
+        Some((name, regex)) => (name, regex),
+        None => return Ok(None),
+      }
+    } else {
+      return Ok(None);
+    };
+
+    if name.is_empty() {
+      return Err(ParseError::invalid_path("Parameter must have a name"));
+    }
+    if regex.is_empty() {
+      return Err(ParseError::invalid_path(format!(
+        "Constraint for parameter '{name}' must not be empty"
+      )));
+    }
+    if cfg!(not(feature = "regex")) {
+      return Err(ParseError::invalid_path(format!(
+        "Constraint on parameter '{name}' requires the `regex` feature"
+      )));
+    }
+
+    Ok(Some(PathSegment::ConstrainedParameter {
+      name: name.to_string(),
+      regex: regex.to_string(),
+    }))
+  }
+
   /// 解析路径模式
-  fn parse_pattern(pattern: &str) -> ParseResult<Vec<PathSegment>> {
+  pub(crate) fn parse_pattern(pattern: &str) -> ParseResult<Vec<PathSegment>> {
     let segments = split_path_segments(pattern);
     let mut parsed_segments = Vec::new();
 
     for segment in segments {
+      // 带约束的参数段需要先处理，因为正则中可能出现 "?:" 这样的片段
+      if let Some(constrained) = Self::parse_constrained_segment(segment)? {
+        parsed_segments.push(constrained);
+        continue;
+      }
+
+      // 其余含反斜杠转义的段都是字面量（正则约束中的反斜杠已在上面处理），例如 `\:foo` 表示字面量 `:foo`
+      if segment.contains('\\') {
+        parsed_segments.push(PathSegment::Literal(unescape_literal(segment)?));
+        continue;
+      }
+
       // 处理复合段，如 ":id?:format"
       if segment.contains("?:") && segment.starts_with(':') {
         // 分割复合段
@@ -140,63 +397,409 @@ impl PathParser {
   /// assert_eq!(params.get("id"), Some(&"123".to_string()));
   /// ```
   pub fn match_path(&self, path: &str) -> ParseResult<HashMap<String, String>> {
+    self.match_path_detailed(path).map_err(ParseError::from)
+  }
+
+  /// 匹配字节形式的路径并提取参数
+  ///
+  /// 先用 [`path_from_bytes`] 校验 UTF-8，再交给 [`match_path`](Self::match_path)
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/user/:id").unwrap();
+  /// let params = parser.match_path_bytes(b"/user/123").unwrap();
+  /// assert_eq!(params.get("id"), Some(&"123".to_string()));
+  /// ```
+  pub fn match_path_bytes(&self, path: &[u8]) -> ParseResult<HashMap<String, String>> {
+    self.match_path(path_from_bytes(path)?)
+  }
+
+  /// 匹配路径并以借用的方式提取参数
+  ///
+  /// 匹配规则和错误与 [`match_path`](Self::match_path) 完全一致。不含 '%' 的参数段直接借用 `path`，
+  /// 返回 `Cow::Borrowed`；只有需要解码的段（以及跨越重复斜杠的通配符值）才分配新的字符串。
+  /// 路径中的 '+' 不会被解码，因此不会触发分配。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要匹配的路径字符串
+  ///
+  /// # 返回值
+  ///
+  /// 以参数名为键的参数映射，如果匹配失败则返回错误
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  /// use std::borrow::Cow;
+  ///
+  /// let parser = PathParser::new("/users/:name/:id").unwrap();
+  /// let params = parser.match_path_borrowed("/users/john%20doe/42").unwrap();
+  /// assert!(matches!(params["id"], Cow::Borrowed("42")));
+  /// assert!(matches!(&params["name"], Cow::Owned(name) if name == "john doe"));
+  /// ```
+  pub fn match_path_borrowed<'s, 'a>(&'s self, path: &'a str) -> ParseResult<HashMap<&'s str, Cow<'a, str>>> {
+    let spans = segment_spans(path);
+    let mut params = HashMap::new();
+    let rejects_trailing_slash = self.options.trailing_slash != TrailingSlash::Ignore && strip_trailing_slash(path).is_some();
+    let rejects_empty_segment = self.empty_interior_segment(path).is_some();
+
+    if !rejects_trailing_slash && !rejects_empty_segment && self.capture_from(path, &spans, 0, 0, &mut params) {
+      return Ok(params);
+    }
+
+    // 失败时交给 match_path 生成同样的错误，失败路径上的分配无关紧要
+    Err(match self.match_path(path) {
+      Err(error) => error,
+      Ok(_) => ParseError::invalid_path(format!("Path '{path}' does not match pattern '{}'", self.pattern)),
+    })
+  }
+
+  /// 从指定位置开始借用地捕获参数，规则与 `match_segments_from` 保持一致
+  fn capture_from<'s, 'a>(
+    &'s self,
+    path: &'a str,
+    spans: &[(usize, usize)],
+    start_pattern_index: usize,
+    start_path_index: usize,
+    params: &mut HashMap<&'s str, Cow<'a, str>>,
+  ) -> bool {
+    let mut path_index = start_path_index;
+    let segment = |index: usize| spans.get(index).map(|&(start, end)| &path[start..end]);
+
+    for (pattern_index, pattern_segment) in self.pattern_segments.iter().enumerate().skip(start_pattern_index) {
+      match pattern_segment {
+        PathSegment::Literal(expected) => match segment(path_index) {
+          Some(actual) if self.literal_matches(expected, actual) => path_index += 1,
+          _ => return false,
+        },
+        PathSegment::Parameter(name) => match segment(path_index).and_then(decode_borrowed) {
+          Some(value) => {
+            params.insert(name, value);
+            path_index += 1;
+          }
+          None => return false,
+        },
+        PathSegment::ConstrainedParameter { name, .. } => match segment(path_index).and_then(decode_borrowed) {
+          Some(value) if self.satisfies_constraint(name, &value) => {
+            params.insert(name, value);
+            path_index += 1;
+          }
+          _ => return false,
+        },
+        PathSegment::OptionalParameter(name) => {
+          if let Some(actual) = segment(path_index) {
+            let Some(value) = decode_borrowed(actual) else {
+              return false;
+            };
+            params.insert(name, value);
+            path_index += 1;
+          }
+        }
+        PathSegment::Wildcard(name) => {
+          let remaining = &spans[path_index..];
+          if !remaining.iter().all(|&(start, end)| decodes(&path[start..end])) {
+            return false;
+          }
+
+          // 通配符是最后一个模式段时消耗所有剩余段
+          if pattern_index + 1 == self.pattern_segments.len() {
+            params.insert(name, join_wildcard(path, remaining));
+            return true;
+          }
+
+          // 否则回溯：优先消耗最少的段，保留后续模式需要的部分
+          for taken in 0..=remaining.len() {
+            let mut attempt = params.clone();
+            attempt.insert(name, join_wildcard(path, &remaining[..taken]));
+            if self.capture_from(path, spans, pattern_index + 1, path_index + taken, &mut attempt) {
+              *params = attempt;
+              return true;
+            }
+          }
+          return false;
+        }
+      }
+    }
+
+    path_index == spans.len()
+  }
+
+  /// 检查路径是否匹配当前模式
+  ///
+  /// 匹配结果与 `match_path(path).is_ok()` 完全一致，但不会构建参数映射，
+  /// 并且只对包含 '%' 的参数段调用 `url_decode_path`，在遇到第一个不匹配的段时立即返回。
+  /// 适合在分发循环中对同一路径测试大量模式，只对最终命中的模式调用 `match_path` 提取参数。
+  /// 在 `tests/performance_tests.rs` 的分发基准中（release 构建）约比 `match_path(..).is_ok()` 快 3 倍。
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/user/:id/profile").unwrap();
+  /// assert!(parser.matches("/user/123/profile"));
+  /// assert!(!parser.matches("/user/123/settings"));
+  /// ```
+  pub fn matches(&self, path: &str) -> bool {
+    if self.options.trailing_slash != TrailingSlash::Ignore && strip_trailing_slash(path).is_some() {
+      return false;
+    }
+    if self.empty_interior_segment(path).is_some() {
+      return false;
+    }
+
+    let path_segments = split_path_segments(path);
+    self.matches_from(&path_segments, 0, 0)
+  }
+
+  /// 从指定位置开始检查是否匹配，规则与 `match_segments_from` 保持一致
+  fn matches_from(&self, path_segments: &[&str], start_pattern_index: usize, start_path_index: usize) -> bool {
+    let mut path_index = start_path_index;
+
+    for (pattern_index, pattern_segment) in self.pattern_segments.iter().enumerate().skip(start_pattern_index) {
+      match pattern_segment {
+        PathSegment::Literal(expected) => match path_segments.get(path_index) {
+          Some(actual) if self.literal_matches(expected, actual) => path_index += 1,
+          _ => return false,
+        },
+        PathSegment::Parameter(_) => match path_segments.get(path_index) {
+          Some(actual) if decodes(actual) => path_index += 1,
+          _ => return false,
+        },
+        PathSegment::ConstrainedParameter { name, .. } => {
+          let Some(actual) = path_segments.get(path_index) else {
+            return false;
+          };
+          let satisfied = if needs_decoding(actual) {
+            url_decode_path(actual).is_ok_and(|value| self.satisfies_constraint(name, &value))
+          } else {
+            self.satisfies_constraint(name, actual)
+          };
+          if !satisfied {
+            return false;
+          }
+          path_index += 1;
+        }
+        PathSegment::OptionalParameter(_) => {
+          if let Some(actual) = path_segments.get(path_index) {
+            if !decodes(actual) {
+              return false;
+            }
+            path_index += 1;
+          }
+        }
+        PathSegment::Wildcard(_) => {
+          let remaining = &path_segments[path_index..];
+          if !remaining.iter().all(|segment| decodes(segment)) {
+            return false;
+          }
+          if pattern_index + 1 == self.pattern_segments.len() {
+            return true;
+          }
+          return (0..=remaining.len()).any(|taken| self.matches_from(path_segments, pattern_index + 1, path_index + taken));
+        }
+      }
+    }
+
+    path_index == path_segments.len()
+  }
+
+  /// 匹配路径并提取参数，失败时返回具体的失败位置
+  ///
+  /// 与 [`match_path`](Self::match_path) 的匹配规则完全一致，
+  /// 但失败时会携带失败的段索引、对应的模式段以及实际遇到的路径段，
+  /// 便于上层框架生成更友好的 404 诊断信息。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要匹配的路径字符串
+  ///
+  /// # 返回值
+  ///
+  /// 提取的参数映射，如果匹配失败则返回 [`PathMatchFailure`]
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::{PathParser, PathSegment};
+  ///
+  /// let parser = PathParser::new("/user/:id/profile").unwrap();
+  /// let failure = parser.match_path_detailed("/user/123/settings").unwrap_err();
+  /// assert_eq!(failure.index, 2);
+  /// assert_eq!(failure.segment, Some(&PathSegment::Literal("profile".to_string())));
+  /// assert_eq!(failure.actual.as_deref(), Some("settings"));
+  /// ```
+  pub fn match_path_detailed(&self, path: &str) -> Result<HashMap<String, String>, PathMatchFailure<'_>> {
+    if let Some(index) = self.empty_interior_segment(path) {
+      return Err(PathMatchFailure {
+        index,
+        segment: None,
+        actual: Some(String::new()),
+        error: ParseError::invalid_path(format!("Empty path segment in path: {path}")),
+      });
+    }
+
+    if let Some(canonical) = strip_trailing_slash(path) {
+      let trailing_slash_failure = |error| PathMatchFailure {
+        index: split_path_segments(path).len(),
+        segment: None,
+        actual: None,
+        error,
+      };
+
+      match self.options.trailing_slash {
+        TrailingSlash::Ignore => {}
+        TrailingSlash::Strict => {
+          let error = ParseError::invalid_path(format!("Unexpected trailing slash in path: {path}"));
+          return Err(trailing_slash_failure(error));
+        }
+        TrailingSlash::Redirect => {
+          // 只有规范路径能够匹配时才需要重定向
+          self.match_path_detailed(canonical)?;
+          return Err(trailing_slash_failure(ParseError::trailing_slash_redirect(canonical)));
+        }
+      }
+    }
+
     let path_segments = split_path_segments(path);
     let mut params = HashMap::new();
-    let mut path_index = 0;
+    self.match_segments_from(&path_segments, 0, 0, &mut params)?;
+    Ok(params)
+  }
+
+  /// 从指定的模式段和路径段位置开始匹配
+  ///
+  /// 通配符后面还有模式段时，从消耗零个路径段开始逐步回溯，
+  /// 选择能让剩余模式匹配成功的最少段数
+  fn match_segments_from(
+    &self,
+    path_segments: &[&str],
+    start_pattern_index: usize,
+    start_path_index: usize,
+    params: &mut HashMap<String, String>,
+  ) -> Result<(), PathMatchFailure<'_>> {
+    let mut path_index = start_path_index;
+
+    let fail = |path_index: usize, segment, error| PathMatchFailure {
+      index: path_index,
+      segment: Some(segment),
+      actual: path_segments.get(path_index).map(|s| s.to_string()),
+      error,
+    };
 
-    for (pattern_index, pattern_segment) in self.pattern_segments.iter().enumerate() {
+    for (pattern_index, pattern_segment) in self.pattern_segments.iter().enumerate().skip(start_pattern_index) {
       match pattern_segment {
         PathSegment::Literal(expected) => {
           if path_index >= path_segments.len() {
-            return Err(ParseError::segment_count_mismatch(self.pattern_segments.len(), path_segments.len()));
+            let error = ParseError::segment_count_mismatch(self.pattern_segments.len(), path_segments.len());
+            return Err(fail(path_index, pattern_segment, error));
           }
 
           let actual = path_segments[path_index];
-          if actual != expected {
-            return Err(ParseError::segment_mismatch(expected.clone(), actual.to_string(), pattern_index));
+          if !self.literal_matches(expected, actual) {
+            let error = ParseError::segment_mismatch(expected.clone(), actual.to_string(), pattern_index);
+            return Err(fail(path_index, pattern_segment, error));
           }
           path_index += 1;
         }
         PathSegment::Parameter(name) => {
           if path_index >= path_segments.len() {
-            return Err(ParseError::missing_parameter(name.clone()));
+            return Err(fail(path_index, pattern_segment, ParseError::missing_parameter(name.clone())));
+          }
+
+          let value = url_decode_path(path_segments[path_index]).map_err(|e| fail(path_index, pattern_segment, e))?;
+          params.insert(name.clone(), value);
+          path_index += 1;
+        }
+        PathSegment::ConstrainedParameter { name, regex } => {
+          if path_index >= path_segments.len() {
+            return Err(fail(path_index, pattern_segment, ParseError::missing_parameter(name.clone())));
           }
 
-          let value = url_decode(path_segments[path_index])?;
+          let value = url_decode_path(path_segments[path_index]).map_err(|e| fail(path_index, pattern_segment, e))?;
+          if !self.satisfies_constraint(name, &value) {
+            let error = ParseError::constraint_violation(name.clone(), value, regex.clone());
+            return Err(fail(path_index, pattern_segment, error));
+          }
           params.insert(name.clone(), value);
           path_index += 1;
         }
         PathSegment::OptionalParameter(name) => {
           if path_index < path_segments.len() {
-            let value = url_decode(path_segments[path_index])?;
+            let value = url_decode_path(path_segments[path_index]).map_err(|e| fail(path_index, pattern_segment, e))?;
             params.insert(name.clone(), value);
             path_index += 1;
           }
           // 可选参数，如果没有对应的路径段也不报错
         }
         PathSegment::Wildcard(name) => {
-          // 通配符匹配剩余的所有段
-          let remaining_segments: Vec<String> = path_segments[path_index..]
-            .iter()
-            .map(|s| url_decode(s))
-            .collect::<ParseResult<Vec<_>>>()?;
+          let mut decoded_segments = Vec::new();
+          for (offset, segment) in path_segments[path_index..].iter().enumerate() {
+            let value = url_decode_path(segment).map_err(|e| fail(path_index + offset, pattern_segment, e))?;
+            decoded_segments.push(value);
+          }
 
-          let wildcard_path = remaining_segments.join("/");
-          params.insert(name.clone(), wildcard_path);
+          // 通配符是最后一个模式段时消耗所有剩余段
+          if pattern_index + 1 == self.pattern_segments.len() {
+            params.insert(name.clone(), decoded_segments.join("/"));
+            return Ok(());
+          }
 
-          // 通配符消耗所有剩余段
-          path_index = path_segments.len();
-          break;
+          // 否则回溯：优先消耗最少的段，保留后续模式需要的部分
+          let mut deepest_failure: Option<PathMatchFailure<'_>> = None;
+          for taken in 0..=decoded_segments.len() {
+            let mut attempt = params.clone();
+            attempt.insert(name.clone(), decoded_segments[..taken].join("/"));
+
+            match self.match_segments_from(path_segments, pattern_index + 1, path_index + taken, &mut attempt) {
+              Ok(()) => {
+                *params = attempt;
+                return Ok(());
+              }
+              Err(failure) => {
+                // 优先报告实际遇到不匹配段的失败，其次报告匹配得最远的失败
+                let rank = |f: &PathMatchFailure<'_>| (f.actual.is_some(), f.index);
+                if deepest_failure.as_ref().is_none_or(|deepest| rank(&failure) > rank(deepest)) {
+                  deepest_failure = Some(failure);
+                }
+              }
+            }
+          }
+
+          // 循环至少执行一次，必然记录了失败信息
+          return Err(deepest_failure.expect("wildcard backtracking attempts at least once"));
         }
       }
     }
 
     // 检查是否还有未匹配的路径段
     if path_index < path_segments.len() {
-      return Err(ParseError::segment_count_mismatch(self.pattern_segments.len(), path_segments.len()));
+      return Err(PathMatchFailure {
+        index: path_index,
+        segment: None,
+        actual: Some(path_segments[path_index].to_string()),
+        error: ParseError::segment_count_mismatch(self.pattern_segments.len(), path_segments.len()),
+      });
     }
 
-    Ok(params)
+    Ok(())
+  }
+
+  /// 检查参数值是否满足约束
+  #[cfg(feature = "regex")]
+  fn satisfies_constraint(&self, name: &str, value: &str) -> bool {
+    self.constraints.get(name).is_none_or(|re| re.is_match(value))
+  }
+
+  /// 未启用 `regex` feature 时不可能存在约束段
+  #[cfg(not(feature = "regex"))]
+  fn satisfies_constraint(&self, _name: &str, _value: &str) -> bool {
+    true
   }
 
   /// 格式化路径
@@ -224,35 +827,57 @@ impl PathParser {
   /// assert_eq!(path, "/user/123/profile");
   /// ```
   pub fn format_path(&self, params: &HashMap<String, String>) -> ParseResult<String> {
-    let mut segments = Vec::new();
+    format_segments(&self.pattern_segments, params)
+  }
 
+  /// 检查参数映射是否满足模式，但不生成路径
+  ///
+  /// 每个必选参数（`:name`、带约束的参数和 `*name`）都必须有对应的键，可选参数可以缺失，
+  /// 不属于模式的键视为多余。只检查键名，不检查值是否满足约束。
+  ///
+  /// # 参数
+  ///
+  /// * `params` - 参数映射
+  ///
+  /// # 返回值
+  ///
+  /// 缺少参数时按模式顺序返回第一个缺失参数的 `MissingParameter` 错误；
+  /// 存在多余的键时返回列出所有多余键名（按字母排序）的 `InvalidPath` 错误
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  /// use std::collections::HashMap;
+  ///
+  /// let parser = PathParser::new("/user/:id/?:tab").unwrap();
+  /// let mut params = HashMap::new();
+  /// params.insert("id".to_string(), "123".to_string());
+  /// assert!(parser.validate_params(&params).is_ok());
+  ///
+  /// params.insert("extra".to_string(), "1".to_string());
+  /// assert!(parser.validate_params(&params).is_err());
+  /// ```
+  pub fn validate_params(&self, params: &HashMap<String, String>) -> ParseResult<()> {
     for segment in &self.pattern_segments {
-      match segment {
-        PathSegment::Literal(literal) => {
-          segments.push(literal.clone());
-        }
-        PathSegment::Parameter(name) => {
-          let value = params.get(name).ok_or_else(|| ParseError::missing_parameter(name.clone()))?;
-          segments.push(crate::utils::url_encode(value));
-        }
-        PathSegment::OptionalParameter(name) => {
-          if let Some(value) = params.get(name) {
-            segments.push(crate::utils::url_encode(value));
-          }
-        }
-        PathSegment::Wildcard(name) => {
-          let value = params.get(name).ok_or_else(|| ParseError::missing_parameter(name.clone()))?;
-          // 通配符值可能包含多个段，用 '/' 分隔
-          segments.push(crate::utils::url_encode(value));
+      if let PathSegment::Parameter(name) | PathSegment::ConstrainedParameter { name, .. } | PathSegment::Wildcard(name) = segment {
+        if !params.contains_key(name) {
+          return Err(ParseError::missing_parameter(name.clone()));
         }
       }
     }
 
-    if segments.is_empty() {
-      Ok("/".to_string())
-    } else {
-      Ok(format!("/{}", segments.join("/")))
+    let names = self.parameter_names();
+    let mut extra: Vec<&str> = params.keys().map(String::as_str).filter(|key| !names.contains(key)).collect();
+    if extra.is_empty() {
+      return Ok(());
     }
+    extra.sort_unstable();
+    let listed = extra.iter().map(|key| format!("'{key}'")).collect::<Vec<_>>().join(", ");
+    Err(ParseError::invalid_path(format!(
+      "Unexpected parameters {listed} for pattern '{}'",
+      self.pattern
+    )))
   }
 
   /// 获取路径模式
@@ -276,65 +901,395 @@ impl PathParser {
       .pattern_segments
       .iter()
       .filter_map(|s| match s {
-        PathSegment::Parameter(name) | PathSegment::OptionalParameter(name) | PathSegment::Wildcard(name) => Some(name.as_str()),
+        PathSegment::Parameter(name)
+        | PathSegment::ConstrainedParameter { name, .. }
+        | PathSegment::OptionalParameter(name)
+        | PathSegment::Wildcard(name) => Some(name.as_str()),
         PathSegment::Literal(_) => None,
       })
       .collect()
   }
 
+  /// 获取所有参数的名称和种类，按模式中出现的顺序排列
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::{ParamKind, PathParser};
+  ///
+  /// let parser = PathParser::new("/docs/:section/?:page").unwrap();
+  /// let kinds: Vec<ParamKind> = parser.parameter_info().into_iter().map(|info| info.kind).collect();
+  /// assert_eq!(kinds, vec![ParamKind::Required, ParamKind::Optional]);
+  /// ```
+  pub fn parameter_info(&self) -> Vec<ParamInfo> {
+    self
+      .pattern_segments
+      .iter()
+      .filter_map(|s| {
+        let (name, kind) = match s {
+          PathSegment::Parameter(name) | PathSegment::ConstrainedParameter { name, .. } => (name, ParamKind::Required),
+          PathSegment::OptionalParameter(name) => (name, ParamKind::Optional),
+          PathSegment::Wildcard(name) => (name, ParamKind::Wildcard),
+          PathSegment::Literal(_) => return None,
+        };
+        Some(ParamInfo { name: name.clone(), kind })
+      })
+      .collect()
+  }
+
+  /// 获取模式开头连续字面量段的长度（包括每段前的 '/'）
+  ///
+  /// 用于在多个模式都能匹配时衡量哪个更具体，例如 `/users/new` 为 10，`/users/:id` 为 6
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// assert_eq!(PathParser::new("/users/:id").unwrap().literal_prefix_len(), 6);
+  /// assert_eq!(PathParser::new("/:lang/docs").unwrap().literal_prefix_len(), 0);
+  /// ```
+  pub fn literal_prefix_len(&self) -> usize {
+    self
+      .pattern_segments
+      .iter()
+      .map_while(|s| match s {
+        PathSegment::Literal(literal) => Some(literal.len() + 1),
+        _ => None,
+      })
+      .sum()
+  }
+
+  /// 路径开头能被模式匹配时返回 [`PathParser::literal_prefix_len`]，否则返回 None
+  ///
+  /// 用于在多个模式之间选择最具体的一个，只检查模式覆盖的开头部分
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/users/:id").unwrap();
+  /// assert_eq!(parser.prefix_match_score("/users/42/posts"), Some(6));
+  /// assert_eq!(parser.prefix_match_score("/posts/42"), None);
+  /// ```
+  pub fn prefix_match_score(&self, path: &str) -> Option<usize> {
+    let consumed = self.consumed_length(path).ok()?;
+    self.match_path(&path[..consumed]).ok()?;
+    Some(self.literal_prefix_len())
+  }
+
   /// 计算路径消费的长度
   ///
+  /// 按模式段逐个对应路径段，返回原始字符串中最后一个被消费的段之后的字节偏移量。
+  /// 偏移量包含段前的 '/'（包括重复的斜杠），不包含查询字符串，
+  /// 因此 `&path[consumed..]` 就是交给子路由的剩余部分。
+  ///
   /// # 参数
   ///
   /// * `path` - 要匹配的路径字符串
   ///
   /// # 返回值
   ///
-  /// 当前模式消费的路径长度
+  /// 当前模式消费的路径长度（字节数）
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/users/:id").unwrap();
+  /// let path = "/users/123/profile?tab=basic";
+  /// let consumed = parser.consumed_length(path).unwrap();
+  /// assert_eq!(&path[..consumed], "/users/123");
+  /// assert_eq!(&path[consumed..], "/profile?tab=basic");
+  /// ```
   pub fn consumed_length(&self, path: &str) -> ParseResult<usize> {
-    let path_segments = split_path_segments(path);
+    let (path_part, _) = split_path_query(path);
+    let spans = segment_spans(path_part);
     let mut consumed_segments = 0;
 
     for pattern_segment in &self.pattern_segments {
       match pattern_segment {
-        PathSegment::Literal(_) | PathSegment::Parameter(_) => {
-          if consumed_segments >= path_segments.len() {
+        PathSegment::Literal(_) | PathSegment::Parameter(_) | PathSegment::ConstrainedParameter { .. } => {
+          if consumed_segments >= spans.len() {
             break;
           }
           consumed_segments += 1;
         }
         PathSegment::OptionalParameter(_) => {
-          if consumed_segments < path_segments.len() {
+          if consumed_segments < spans.len() {
             consumed_segments += 1;
           }
         }
         PathSegment::Wildcard(_) => {
           // 通配符消耗所有剩余段
-          consumed_segments = path_segments.len();
+          consumed_segments = spans.len();
           break;
         }
       }
     }
 
-    // 计算实际消费的字符长度
-    if consumed_segments == 0 {
-      return Ok(0);
-    }
+    // 最后一个被消费段的结束位置即为消费的长度
+    Ok(match consumed_segments {
+      0 => 0,
+      n => spans[n - 1].1,
+    })
+  }
+
+  /// 只匹配路径开头被模式覆盖的段
+  ///
+  /// 用于把子应用挂载在 `/admin` 这类前缀下：开头的段必须完整匹配模式（规则与
+  /// [`match_path`](Self::match_path) 相同），之后的部分原样返回，包含开头的 '/' 和查询字符串。
+  /// 可选参数和通配符尽可能多地消费路径段。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要匹配的路径字符串
+  ///
+  /// # 返回值
+  ///
+  /// 提取的参数和未消费的剩余路径，开头的段不匹配时返回错误
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParser;
+  ///
+  /// let parser = PathParser::new("/admin/:tenant").unwrap();
+  /// let (params, rest) = parser.match_prefix("/admin/acme/users/1?tab=roles").unwrap();
+  /// assert_eq!(params.get("tenant"), Some(&"acme".to_string()));
+  /// assert_eq!(rest, "/users/1?tab=roles");
+  ///
+  /// assert!(parser.match_prefix("/shop/acme").is_err());
+  /// ```
+  pub fn match_prefix<'a>(&self, path: &'a str) -> ParseResult<(HashMap<String, String>, &'a str)> {
+    let (path_part, _) = split_path_query(path);
+    let spans = segment_spans(path_part);
 
-    let consumed_path_segments = &path_segments[..consumed_segments];
-    let consumed_length = if consumed_path_segments.is_empty() {
-      0
+    // 模式最少和最多能消费的段数
+    let required = self
+      .pattern_segments
+      .iter()
+      .filter(|segment| !matches!(segment, PathSegment::OptionalParameter(_) | PathSegment::Wildcard(_)))
+      .count();
+    let most = if self.has_wildcard() {
+      spans.len()
     } else {
-      // 计算消费的字符数：段长度 + 分隔符
-      let segments_length: usize = consumed_path_segments.iter().map(|s| s.len()).sum();
-      let separators_length = consumed_segments; // 每个段前面有一个 '/'
-      segments_length + separators_length
+      self.pattern_segments.len().min(spans.len())
     };
 
-    Ok(consumed_length)
+    // 从最长的前缀开始尝试，第一次尝试的错误最能说明问题
+    let mut first_error = None;
+    for taken in (required.min(most)..=most).rev() {
+      let end = match taken {
+        0 => 0,
+        n => spans[n - 1].1,
+      };
+      match self.match_path(&path_part[..end]) {
+        Ok(params) => return Ok((params, &path[end..])),
+        Err(error) => {
+          first_error.get_or_insert(error);
+        }
+      }
+    }
+
+    Err(
+      first_error.unwrap_or_else(|| ParseError::invalid_path(format!("Path '{path}' does not start with pattern '{}'", self.pattern))),
+    )
   }
 }
 
+/// 固定模式的 [`PathParser`] 缓存
+///
+/// 放在 `static` 中使用，第一次调用 [`get`](Self::get) 时编译模式，之后复用同一个解析器。
+/// `#[derive(RouterData)]` 为每个路由类型生成一个这样的 static，避免每次解析都重新编译模式。
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::parser::PathParserCache;
+///
+/// static USER_PARSER: PathParserCache = PathParserCache::new("/users/:id");
+///
+/// let params = USER_PARSER.get().unwrap().match_path("/users/42").unwrap();
+/// assert_eq!(params.get("id"), Some(&"42".to_string()));
+/// ```
+#[derive(Debug)]
+pub struct PathParserCache {
+  pattern: &'static str,
+  parser: OnceLock<ParseResult<PathParser>>,
+}
+
+impl PathParserCache {
+  /// 创建缓存，此时还不会编译模式
+  pub const fn new(pattern: &'static str) -> Self {
+    Self {
+      pattern,
+      parser: OnceLock::new(),
+    }
+  }
+
+  /// 获取缓存的路径模式
+  pub fn pattern(&self) -> &'static str {
+    self.pattern
+  }
+
+  /// 获取编译后的解析器，模式无效时每次都返回同样的错误
+  pub fn get(&self) -> ParseResult<&PathParser> {
+    self
+      .parser
+      .get_or_init(|| PathParser::new(self.pattern))
+      .as_ref()
+      .map_err(Clone::clone)
+  }
+
+  /// 使用缓存的解析器格式化路径，结果与 [`PathParser::format_path`] 相同
+  ///
+  /// 格式化只需要模式中的段，因此约束的正则无法编译时仍然能够生成路径
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathParserCache;
+  /// use std::collections::HashMap;
+  ///
+  /// static USER_PARSER: PathParserCache = PathParserCache::new("/users/:id");
+  ///
+  /// let params = HashMap::from([("id".to_string(), "42".to_string())]);
+  /// assert_eq!(USER_PARSER.format_path(&params).unwrap(), "/users/42");
+  /// ```
+  pub fn format_path(&self, params: &HashMap<String, String>) -> ParseResult<String> {
+    match self.get() {
+      Ok(parser) => parser.format_path(params),
+      Err(_) => format_segments(&PathParser::parse_pattern(self.pattern)?, params),
+    }
+  }
+}
+
+/// 按模式段格式化路径，参数值按段的种类编码
+fn format_segments(pattern_segments: &[PathSegment], params: &HashMap<String, String>) -> ParseResult<String> {
+  let mut segments = Vec::new();
+
+  for segment in pattern_segments {
+    match segment {
+      PathSegment::Literal(literal) => {
+        segments.push(literal.clone());
+      }
+      PathSegment::Parameter(name) | PathSegment::ConstrainedParameter { name, .. } => {
+        let value = params.get(name).ok_or_else(|| ParseError::missing_parameter(name.clone()))?;
+        segments.push(crate::utils::url_encode(value));
+      }
+      PathSegment::OptionalParameter(name) => {
+        if let Some(value) = params.get(name) {
+          segments.push(crate::utils::url_encode(value));
+        }
+      }
+      PathSegment::Wildcard(name) => {
+        let value = params.get(name).ok_or_else(|| ParseError::missing_parameter(name.clone()))?;
+        // 通配符值可能包含多个段，编码时保留分隔的 '/'
+        segments.push(crate::utils::url_encode_with(value, &crate::utils::EncodeSet::PATH));
+      }
+    }
+  }
+
+  if segments.is_empty() {
+    Ok("/".to_string())
+  } else {
+    Ok(format!("/{}", segments.join("/")))
+  }
+}
+
+/// 去掉字面量段中的反斜杠转义，`\\x` 变为 `x`
+fn unescape_literal(segment: &str) -> ParseResult<String> {
+  let mut literal = String::with_capacity(segment.len());
+  let mut chars = segment.chars();
+  while let Some(c) = chars.next() {
+    if c == '\\' {
+      match chars.next() {
+        Some(escaped) => literal.push(escaped),
+        None => {
+          return Err(ParseError::invalid_path(format!(
+            "Pattern segment '{segment}' ends with a dangling escape"
+          )))
+        }
+      }
+    } else {
+      literal.push(c);
+    }
+  }
+  Ok(literal)
+}
+
+/// 去掉路径末尾的斜杠，路径没有末尾斜杠（或是根路径）时返回 None
+fn strip_trailing_slash(path: &str) -> Option<&str> {
+  if path.len() > 1 && path.ends_with('/') {
+    let canonical = path.trim_end_matches('/');
+    Some(if canonical.is_empty() { "/" } else { canonical })
+  } else {
+    None
+  }
+}
+
+/// 路径段是否包含需要解码的字符
+///
+/// 路径中的 '+' 按字面保留，只有百分号编码需要解码
+fn needs_decoding(segment: &str) -> bool {
+  segment.contains('%')
+}
+
+/// 解码路径段，不需要解码时直接借用原字符串，解码失败时返回 None
+fn decode_borrowed(segment: &str) -> Option<Cow<'_, str>> {
+  if needs_decoding(segment) {
+    url_decode_path(segment).ok().map(Cow::Owned)
+  } else {
+    Some(Cow::Borrowed(segment))
+  }
+}
+
+/// 把通配符消耗的段拼接为参数值
+///
+/// 段之间恰好以单个 '/' 相连且都不需要解码时直接借用原字符串
+fn join_wildcard<'a>(path: &'a str, spans: &[(usize, usize)]) -> Cow<'a, str> {
+  let (Some(&(start, _)), Some(&(_, end))) = (spans.first(), spans.last()) else {
+    return Cow::Borrowed("");
+  };
+  let raw = &path[start..end];
+  if !needs_decoding(raw) && !raw.contains("//") {
+    return Cow::Borrowed(raw);
+  }
+
+  let decoded = spans
+    .iter()
+    .map(|&(start, end)| url_decode_path(&path[start..end]).unwrap_or_else(|_| path[start..end].to_string()))
+    .collect::<Vec<_>>();
+  Cow::Owned(decoded.join("/"))
+}
+
+/// 路径段能否被成功解码，只有包含 '%' 时才可能失败
+fn decodes(segment: &str) -> bool {
+  !segment.contains('%') || url_decode_path(segment).is_ok()
+}
+
+/// 计算路径中每个非空段在原始字符串中的字节范围
+///
+/// 与 [`split_path_segments`] 的分段规则保持一致
+fn segment_spans(path: &str) -> Vec<(usize, usize)> {
+  let mut spans = Vec::new();
+  let mut start = 0;
+
+  for (index, c) in path.char_indices().chain(std::iter::once((path.len(), '/'))) {
+    if c == '/' {
+      if index > start {
+        spans.push((start, index));
+      }
+      start = index + 1;
+    }
+  }
+
+  spans
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -381,6 +1336,56 @@ mod tests {
     assert_eq!(params.get("id"), Some(&"456".to_string()));
   }
 
+  #[test]
+  fn test_validate_params() {
+    let parser = PathParser::new("/users/:user_id/files/*path/?:format").unwrap();
+    let mut params = HashMap::new();
+    params.insert("user_id".to_string(), "1".to_string());
+    params.insert("path".to_string(), "a/b".to_string());
+    assert_eq!(parser.validate_params(&params), Ok(()));
+
+    // 可选参数可以出现
+    params.insert("format".to_string(), "json".to_string());
+    assert_eq!(parser.validate_params(&params), Ok(()));
+
+    // 按模式顺序报告第一个缺失的必选参数
+    let mut missing = HashMap::new();
+    missing.insert("format".to_string(), "json".to_string());
+    assert_eq!(parser.validate_params(&missing), Err(ParseError::missing_parameter("user_id")));
+    missing.insert("user_id".to_string(), "1".to_string());
+    assert_eq!(parser.validate_params(&missing), Err(ParseError::missing_parameter("path")));
+
+    // 多余的键全部列出
+    params.insert("zzz".to_string(), "1".to_string());
+    params.insert("extra".to_string(), "1".to_string());
+    assert_eq!(
+      parser.validate_params(&params),
+      Err(ParseError::invalid_path(
+        "Unexpected parameters 'extra', 'zzz' for pattern '/users/:user_id/files/*path/?:format'"
+      ))
+    );
+
+    let root = PathParser::new("/").unwrap();
+    assert_eq!(root.validate_params(&HashMap::new()), Ok(()));
+  }
+
+  #[test]
+  fn test_match_path_bytes() {
+    let parser = PathParser::new("/user/:id").unwrap();
+    let params = parser.match_path_bytes(b"/user/123").unwrap();
+    assert_eq!(params.get("id"), Some(&"123".to_string()));
+
+    assert!(matches!(
+      parser.match_path_bytes(b"/user/\xC3\x28"),
+      Err(ParseError::UrlEncoding(_))
+    ));
+    // 合法 UTF-8 但结构不匹配时返回原本的匹配错误
+    assert!(matches!(
+      parser.match_path_bytes(b"/post/123"),
+      Err(ParseError::SegmentMismatch { .. })
+    ));
+  }
+
   #[test]
   fn test_match_with_optional() {
     let parser = PathParser::new("/user/:id?:format").unwrap();
@@ -404,6 +1409,90 @@ mod tests {
     assert_eq!(params.get("path"), Some(&"docs/readme.txt".to_string()));
   }
 
+  #[test]
+  fn test_match_wildcard_with_trailing_literal() {
+    let parser = PathParser::new("/files/*path/download").unwrap();
+
+    let params = parser.match_path("/files/a/b/download").unwrap();
+    assert_eq!(params.get("path"), Some(&"a/b".to_string()));
+
+    // 通配符可以不消耗任何段
+    let params = parser.match_path("/files/download").unwrap();
+    assert_eq!(params.get("path"), Some(&"".to_string()));
+
+    // 选择最少的段数，后面出现的同名字面量归入通配符之后的匹配
+    let params = parser.match_path("/files/download/x/download").unwrap();
+    assert_eq!(params.get("path"), Some(&"download/x".to_string()));
+
+    let failure = parser.match_path_detailed("/files/a/b/view").unwrap_err();
+    assert_eq!(failure.index, 3);
+    assert_eq!(failure.actual.as_deref(), Some("view"));
+    assert!(parser.match_path("/files/a/b").is_err());
+  }
+
+  #[test]
+  fn test_match_wildcard_with_trailing_parameter() {
+    let parser = PathParser::new("/repo/*path/blob/:rev").unwrap();
+
+    let params = parser.match_path("/repo/src/lib/blob/main").unwrap();
+    assert_eq!(params.get("path"), Some(&"src/lib".to_string()));
+    assert_eq!(params.get("rev"), Some(&"main".to_string()));
+
+    // 后面只有参数时，通配符留出参数需要的最后一段
+    let parser = PathParser::new("/files/*path/:name").unwrap();
+    let params = parser.match_path("/files/a/b/c.txt").unwrap();
+    assert_eq!(params.get("path"), Some(&"a/b".to_string()));
+    assert_eq!(params.get("name"), Some(&"c.txt".to_string()));
+    assert!(parser.match_path("/files").is_err());
+  }
+
+  #[test]
+  fn test_matches_agrees_with_match_path() {
+    let patterns = [
+      "/",
+      "/user/:id",
+      "/user/:id/profile",
+      "/blog/:year/:month?",
+      "/files/*path",
+      "/files/*path/download",
+      "/repo/*path/blob/:rev",
+    ];
+    let paths = [
+      "/",
+      "",
+      "/user",
+      "/user/123",
+      "/user/123/profile",
+      "/user/123/settings",
+      "/user/%E4%B8%AD/profile",
+      "/user/%ZZ",
+      "/user/a+b",
+      "/blog/2024",
+      "/blog/2024/05",
+      "/blog/2024/05/extra",
+      "/files",
+      "/files/a/b/download",
+      "/files/a/%ZZ/download",
+      "/files/download",
+      "/repo/src/blob/main",
+      "/repo/blob",
+    ];
+
+    for pattern in patterns {
+      let parser = PathParser::new(pattern).unwrap();
+      for path in paths {
+        assert_eq!(
+          parser.matches(path),
+          parser.match_path(path).is_ok(),
+          "pattern '{pattern}' and path '{path}' disagree"
+        );
+      }
+    }
+
+    let parser = PathParser::new("/User/:id").unwrap().with_case_insensitive_literals(true);
+    assert!(parser.matches("/user/1"));
+  }
+
   #[test]
   fn test_format_path() {
     let parser = PathParser::new("/user/:id/profile").unwrap();
@@ -414,6 +1503,51 @@ mod tests {
     assert_eq!(path, "/user/123/profile");
   }
 
+  #[test]
+  fn test_plus_in_path_is_literal() {
+    let parser = PathParser::new("/langs/:name").unwrap();
+
+    // 路径中的 '+' 不是空格
+    let params = parser.match_path("/langs/c++").unwrap();
+    assert_eq!(params.get("name"), Some(&"c++".to_string()));
+
+    // 格式化后再解析得到同一个值
+    let path = parser.format_path(&params).unwrap();
+    assert_eq!(path, "/langs/c%2B%2B");
+    assert_eq!(parser.match_path(&path).unwrap(), params);
+
+    let params = parser.match_path("/langs/a%20b").unwrap();
+    assert_eq!(params.get("name"), Some(&"a b".to_string()));
+  }
+
+  #[test]
+  fn test_escaped_literal_segments() {
+    let parser = PathParser::new(r"/legacy/\:export/:id").unwrap();
+    assert_eq!(
+      parser.segments(),
+      &[
+        PathSegment::Literal("legacy".to_string()),
+        PathSegment::Literal(":export".to_string()),
+        PathSegment::Parameter("id".to_string()),
+      ]
+    );
+    assert_eq!(parser.parameter_names(), vec!["id"]);
+
+    let params = parser.match_path("/legacy/:export/42").unwrap();
+    assert_eq!(params.get("id"), Some(&"42".to_string()));
+    assert!(parser.match_path("/legacy/export/42").is_err());
+    assert_eq!(parser.format_path(&params).unwrap(), "/legacy/:export/42");
+
+    // 大括号和反斜杠本身同样可以转义
+    let braces = PathParser::new(r"/files/\{x\}/a\\b").unwrap();
+    assert_eq!(braces.segments()[1], PathSegment::Literal("{x}".to_string()));
+    assert_eq!(braces.segments()[2], PathSegment::Literal(r"a\b".to_string()));
+    assert!(braces.match_path(r"/files/{x}/a\b").is_ok());
+    assert_eq!(braces.format_path(&HashMap::new()).unwrap(), r"/files/{x}/a\b");
+
+    assert!(PathParser::new(r"/files/x\").is_err());
+  }
+
   #[test]
   fn test_match_errors() {
     let parser = PathParser::new("/user/:id").unwrap();
@@ -434,6 +1568,158 @@ mod tests {
     assert_eq!(names, vec!["version", "id", "format", "path"]);
   }
 
+  #[test]
+  fn test_format_path_slashes_by_segment_kind() {
+    let mut params = HashMap::new();
+    params.insert("name".to_string(), "a/b".to_string());
+    params.insert("path".to_string(), "docs/guide v2/index.md".to_string());
+
+    // 普通段中的 '/' 被编码，通配符值中的 '/' 保留为段分隔符
+    let parser = PathParser::new("/repos/:name/blob/*path").unwrap();
+    let path = parser.format_path(&params).unwrap();
+    assert_eq!(path, "/repos/a%2Fb/blob/docs/guide%20v2/index.md");
+
+    let matched = parser.match_path(&path).unwrap();
+    assert_eq!(matched.get("name").map(String::as_str), Some("a/b"));
+    assert_eq!(matched.get("path").map(String::as_str), Some("docs/guide v2/index.md"));
+  }
+
+  #[test]
+  fn test_parameter_info() {
+    let parser = PathParser::new("/api/:version/files/*path/?:format").unwrap();
+    let info = |name: &str, kind| ParamInfo {
+      name: name.to_string(),
+      kind,
+    };
+    assert_eq!(
+      parser.parameter_info(),
+      vec![
+        info("version", ParamKind::Required),
+        info("path", ParamKind::Wildcard),
+        info("format", ParamKind::Optional),
+      ]
+    );
+    assert!(PathParser::new("/about").unwrap().parameter_info().is_empty());
+  }
+
+  #[test]
+  fn test_literal_prefix_len() {
+    assert_eq!(PathParser::new("/").unwrap().literal_prefix_len(), 0);
+    assert_eq!(PathParser::new("/users").unwrap().literal_prefix_len(), 6);
+    assert_eq!(PathParser::new("/users/new").unwrap().literal_prefix_len(), 10);
+    assert_eq!(PathParser::new("/users/:id/posts").unwrap().literal_prefix_len(), 6);
+    assert_eq!(PathParser::new("/files/*path").unwrap().literal_prefix_len(), 6);
+  }
+
+  #[test]
+  fn test_path_parser_cache_reuses_parser() {
+    static CACHE: PathParserCache = PathParserCache::new("/users/:id");
+    let first = CACHE.get().unwrap();
+    let second = CACHE.get().unwrap();
+    assert!(std::ptr::eq(first, second));
+    assert_eq!(CACHE.pattern(), "/users/:id");
+    assert_eq!(first.match_path("/users/7").unwrap().get("id"), Some(&"7".to_string()));
+
+    static INVALID: PathParserCache = PathParserCache::new("/users/:");
+    assert!(INVALID.get().is_err());
+    assert_eq!(INVALID.get().unwrap_err(), PathParser::new("/users/:").unwrap_err());
+  }
+
+  #[test]
+  fn test_parser_cache_format_path() {
+    static CACHE: PathParserCache = PathParserCache::new("/files/*path");
+    let params = HashMap::from([("path".to_string(), "docs/a b.txt".to_string())]);
+    assert_eq!(
+      CACHE.format_path(&params).unwrap(),
+      CACHE.get().unwrap().format_path(&params).unwrap()
+    );
+    assert_eq!(
+      CACHE.format_path(&HashMap::new()).unwrap_err(),
+      ParseError::missing_parameter("path")
+    );
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn test_parser_cache_format_path_with_invalid_constraint() {
+    // 约束的正则无法编译时解析失败，但格式化只需要模式中的段
+    static CACHE: PathParserCache = PathParserCache::new("/posts/:id([0-9)");
+    assert!(CACHE.get().is_err());
+    let params = HashMap::from([("id".to_string(), "42".to_string())]);
+    assert_eq!(CACHE.format_path(&params).unwrap(), "/posts/42");
+  }
+
+  #[test]
+  fn test_match_path_borrowed_borrows_plain_segments() {
+    let parser = PathParser::new("/users/:id/files/*path").unwrap();
+    let params = parser.match_path_borrowed("/users/42/files/docs/a+b.txt").unwrap();
+    assert!(matches!(params["id"], Cow::Borrowed("42")));
+    assert!(matches!(params["path"], Cow::Borrowed("docs/a+b.txt")));
+
+    let parser = PathParser::new("/users/?:tab").unwrap();
+    assert!(parser.match_path_borrowed("/users").unwrap().is_empty());
+    assert!(matches!(
+      parser.match_path_borrowed("/users/posts").unwrap()["tab"],
+      Cow::Borrowed("posts")
+    ));
+  }
+
+  #[test]
+  fn test_match_path_borrowed_owns_decoded_segments() {
+    let parser = PathParser::new("/users/:name/files/*path").unwrap();
+    let params = parser.match_path_borrowed("/users/john%20doe/files/a%2Fb//c").unwrap();
+    assert!(matches!(&params["name"], Cow::Owned(name) if name == "john doe"));
+    assert!(matches!(&params["path"], Cow::Owned(path) if path == "a/b/c"));
+
+    // 重复斜杠需要规范化，不能直接借用
+    let parser = PathParser::new("/files/*path").unwrap();
+    assert!(matches!(&parser.match_path_borrowed("/files/a//b").unwrap()["path"], Cow::Owned(path) if path == "a/b"));
+  }
+
+  #[test]
+  fn test_match_path_borrowed_agrees_with_match_path() {
+    let cases = [
+      ("/users/:id", "/users/42"),
+      ("/users/:id", "/users/42/"),
+      ("/users/:id", "/users"),
+      ("/users/:id", "/posts/42"),
+      ("/users/:id", "/users/%zz"),
+      ("/files/*path/download", "/files/a/b/download"),
+      ("/files/*path/download", "/files/download"),
+      ("/files/*path/download", "/files/a/b"),
+      ("/api/:version/users/:id?:format", "/api/v1/users/7/json"),
+      ("/", "/"),
+    ];
+
+    for (pattern, path) in cases {
+      let parser = PathParser::new(pattern).unwrap();
+      let owned = parser.match_path(path);
+      let borrowed = parser.match_path_borrowed(path).map(|params| {
+        params
+          .into_iter()
+          .map(|(name, value)| (name.to_string(), value.into_owned()))
+          .collect::<HashMap<_, _>>()
+      });
+      assert_eq!(borrowed, owned, "pattern {pattern}, path {path}");
+    }
+
+    let strict = PathParser::new("/users/:id").unwrap().with_trailing_slash(TrailingSlash::Strict);
+    assert_eq!(
+      strict.match_path_borrowed("/users/42/").unwrap_err(),
+      strict.match_path("/users/42/").unwrap_err()
+    );
+  }
+
+  #[test]
+  fn test_format_wildcard_keeps_separators() {
+    let parser = PathParser::new("/assets/*rest").unwrap();
+    let mut params = HashMap::new();
+    params.insert("rest".to_string(), "css/site v2.css".to_string());
+    let path = parser.format_path(&params).unwrap();
+    assert_eq!(path, "/assets/css/site%20v2.css");
+    assert_eq!(parser.match_path(&path).unwrap(), params);
+  }
+
   #[test]
   fn test_has_wildcard() {
     let parser1 = PathParser::new("/user/:id").unwrap();
@@ -442,4 +1728,295 @@ mod tests {
     let parser2 = PathParser::new("/files/*path").unwrap();
     assert!(parser2.has_wildcard());
   }
+
+  #[test]
+  fn test_match_path_detailed() {
+    let parser = PathParser::new("/api/:version/users/:id").unwrap();
+
+    // 字面量不匹配
+    let failure = parser.match_path_detailed("/api/v1/posts/1").unwrap_err();
+    assert_eq!(failure.index, 2);
+    assert_eq!(failure.segment, Some(&PathSegment::Literal("users".to_string())));
+    assert_eq!(failure.actual, Some("posts".to_string()));
+    assert_eq!(failure.error, ParseError::segment_mismatch("users", "posts", 2));
+
+    // 路径段不足
+    let failure = parser.match_path_detailed("/api/v1/users").unwrap_err();
+    assert_eq!(failure.index, 3);
+    assert_eq!(failure.segment, Some(&PathSegment::Parameter("id".to_string())));
+    assert_eq!(failure.actual, None);
+    assert_eq!(failure.error, ParseError::missing_parameter("id"));
+
+    // 多余的路径段
+    let failure = parser.match_path_detailed("/api/v1/users/1/extra").unwrap_err();
+    assert_eq!(failure.index, 4);
+    assert_eq!(failure.segment, None);
+    assert_eq!(failure.actual, Some("extra".to_string()));
+
+    // match_path 返回相同的错误
+    assert_eq!(
+      parser.match_path("/api/v1/posts/1").unwrap_err(),
+      ParseError::segment_mismatch("users", "posts", 2)
+    );
+  }
+
+  #[test]
+  fn test_consumed_length() {
+    let parser = PathParser::new("/users/:id").unwrap();
+
+    // 路径比模式长
+    let path = "/users/123/profile/basic";
+    let consumed = parser.consumed_length(path).unwrap();
+    assert_eq!(&path[..consumed], "/users/123");
+    assert_eq!(&path[consumed..], "/profile/basic");
+
+    // 完全匹配
+    assert_eq!(parser.consumed_length("/users/123").unwrap(), "/users/123".len());
+
+    // 查询字符串不计入消费长度
+    assert_eq!(parser.consumed_length("/users/123?tab=1").unwrap(), "/users/123".len());
+    let path = "/users/123/profile?tab=1";
+    assert_eq!(&path[parser.consumed_length(path).unwrap()..], "/profile?tab=1");
+
+    // 重复斜杠和缺少前导斜杠都按原始字符串计算偏移
+    let path = "//users//123/profile";
+    assert_eq!(&path[..parser.consumed_length(path).unwrap()], "//users//123");
+    assert_eq!(parser.consumed_length("users/123/profile").unwrap(), "users/123".len());
+
+    // 路径比模式短
+    assert_eq!(parser.consumed_length("/users").unwrap(), "/users".len());
+    assert_eq!(parser.consumed_length("/").unwrap(), 0);
+    assert_eq!(parser.consumed_length("").unwrap(), 0);
+  }
+
+  #[test]
+  fn test_match_prefix_with_trailing_parameter() {
+    let parser = PathParser::new("/admin/:tenant").unwrap();
+
+    // 模式正好覆盖整个路径
+    let (params, rest) = parser.match_prefix("/admin/acme").unwrap();
+    assert_eq!(params.get("tenant"), Some(&"acme".to_string()));
+    assert_eq!(rest, "");
+
+    // 只有查询字符串剩余
+    let (_, rest) = parser.match_prefix("/admin/acme?tab=1").unwrap();
+    assert_eq!(rest, "?tab=1");
+
+    // 参数同样会被解码，缺少参数或字面量不匹配时报错
+    let (params, _) = parser.match_prefix("/admin/big%20co/users").unwrap();
+    assert_eq!(params.get("tenant"), Some(&"big co".to_string()));
+    assert_eq!(parser.match_prefix("/admin").unwrap_err(), ParseError::missing_parameter("tenant"));
+    assert!(parser.match_prefix("/users/acme/admin").is_err());
+  }
+
+  #[test]
+  fn test_match_prefix_with_suffix_segments() {
+    let parser = PathParser::new("/admin").unwrap();
+    let (params, rest) = parser.match_prefix("/admin/users/1?page=2").unwrap();
+    assert!(params.is_empty());
+    assert_eq!(rest, "/users/1?page=2");
+
+    // 重复斜杠保留在剩余路径中，与 consumed_length 一致
+    let path = "//admin//users";
+    assert_eq!(parser.match_prefix(path).unwrap().1, "//users");
+    assert_eq!(parser.match_prefix(path).unwrap().1, &path[parser.consumed_length(path).unwrap()..]);
+
+    // 段边界：/administrator 不以 /admin 开头
+    assert!(parser.match_prefix("/administrator").is_err());
+
+    // 可选参数尽量消费，通配符消费所有段
+    let optional = PathParser::new("/docs/?:lang").unwrap();
+    assert_eq!(optional.match_prefix("/docs").unwrap().1, "");
+    let (params, rest) = optional.match_prefix("/docs/en/intro").unwrap();
+    assert_eq!(params.get("lang"), Some(&"en".to_string()));
+    assert_eq!(rest, "/intro");
+    let wildcard = PathParser::new("/files/*path/download").unwrap();
+    let (params, rest) = wildcard.match_prefix("/files/a/b/download/zip").unwrap();
+    assert_eq!(params.get("path"), Some(&"a/b".to_string()));
+    assert_eq!(rest, "/zip");
+
+    // 根模式不消费任何段
+    let root = PathParser::new("/").unwrap();
+    assert_eq!(root.match_prefix("/users/1").unwrap().1, "/users/1");
+  }
+
+  #[test]
+  fn test_consumed_length_wildcard() {
+    let parser = PathParser::new("/files/*path").unwrap();
+    let path = "/files/docs/readme.txt?download=1";
+    assert_eq!(&path[..parser.consumed_length(path).unwrap()], "/files/docs/readme.txt");
+
+    let root = PathParser::new("/").unwrap();
+    assert_eq!(root.consumed_length("/users/123").unwrap(), 0);
+  }
+
+  #[test]
+  fn test_case_insensitive_literals() {
+    let parser = PathParser::new("/users/:id/Profile").unwrap();
+    assert!(parser.match_path("/Users/123/profile").is_err());
+
+    let parser = parser.with_case_insensitive_literals(true);
+    for path in ["/users/123/Profile", "/Users/123/profile", "/USERS/123/PROFILE"] {
+      let params = parser.match_path(path).unwrap();
+      assert_eq!(params.get("id"), Some(&"123".to_string()));
+    }
+    assert!(parser.match_path("/members/123/profile").is_err());
+
+    // 参数值保持原样
+    let params = parser.match_path("/USERS/AbC/profile").unwrap();
+    assert_eq!(params.get("id"), Some(&"AbC".to_string()));
+
+    // 格式化使用模式中原有的大小写
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), "AbC".to_string());
+    assert_eq!(parser.format_path(&params).unwrap(), "/users/AbC/Profile");
+  }
+
+  #[test]
+  fn test_with_options() {
+    let options = PathParserOptions {
+      case_insensitive_literals: true,
+      trailing_slash: TrailingSlash::Strict,
+      strict_slashes: false,
+    };
+    let parser = PathParser::new("/api/:version").unwrap().with_options(options.clone());
+    assert_eq!(parser.options(), &options);
+    assert!(parser.match_path("/API/v1").is_ok());
+    assert!(parser.match_path("/API/v1/").is_err());
+  }
+
+  #[test]
+  fn test_trailing_slash_ignore() {
+    let parser = PathParser::new("/users/:id").unwrap();
+    assert_eq!(parser.options().trailing_slash, TrailingSlash::Ignore);
+
+    let params = parser.match_path("/users/123/").unwrap();
+    assert_eq!(params.get("id"), Some(&"123".to_string()));
+    assert!(parser.matches("/users/123/"));
+  }
+
+  #[test]
+  fn test_trailing_slash_strict() {
+    let parser = PathParser::new("/users/:id").unwrap().with_trailing_slash(TrailingSlash::Strict);
+    assert!(parser.match_path("/users/123").is_ok());
+    assert!(matches!(parser.match_path("/users/123/"), Err(ParseError::InvalidPath(_))));
+    assert!(!parser.matches("/users/123/"));
+
+    // 根路径不受影响
+    let root = PathParser::new("/").unwrap().with_trailing_slash(TrailingSlash::Strict);
+    assert!(root.match_path("/").is_ok());
+  }
+
+  #[test]
+  fn test_strict_slashes() {
+    let lenient = PathParser::new("/a/b").unwrap();
+    assert!(!lenient.options().strict_slashes);
+    assert!(lenient.match_path("/a//b").is_ok());
+    assert!(lenient.matches("/a//b"));
+
+    let strict = PathParser::new("/a/b").unwrap().with_strict_slashes(true);
+    assert!(strict.match_path("/a/b").is_ok());
+    assert!(matches!(strict.match_path("/a//b"), Err(ParseError::InvalidPath(_))));
+    assert!(strict.match_path("//a/b").is_err());
+    assert!(!strict.matches("/a//b"));
+    assert!(strict.match_path_borrowed("/a//b").is_err());
+
+    let failure = strict.match_path_detailed("/a//b").unwrap_err();
+    assert_eq!(failure.index, 1);
+    assert_eq!(failure.actual.as_deref(), Some(""));
+
+    // 末尾斜杠仍由 trailing_slash 决定
+    assert!(strict.match_path("/a/b/").is_ok());
+    assert!(strict.match_path("/").is_err());
+    let root = PathParser::new("/").unwrap().with_strict_slashes(true);
+    assert!(root.match_path("/").is_ok());
+  }
+
+  #[test]
+  fn test_trailing_slash_redirect() {
+    let parser = PathParser::new("/users/:id").unwrap().with_trailing_slash(TrailingSlash::Redirect);
+    assert!(parser.match_path("/users/123").is_ok());
+    assert_eq!(
+      parser.match_path("/users/123//"),
+      Err(ParseError::trailing_slash_redirect("/users/123"))
+    );
+    assert!(!parser.matches("/users/123/"));
+
+    // 规范路径本身无法匹配时返回普通错误
+    let failure = parser.match_path_detailed("/posts/123/").unwrap_err();
+    assert_eq!(failure.error, ParseError::segment_mismatch("users", "posts", 0));
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn test_constrained_parameter() {
+    let parser = PathParser::new(r"/user/:id(\d+)").unwrap();
+    assert_eq!(
+      parser.segments()[1],
+      PathSegment::ConstrainedParameter {
+        name: "id".to_string(),
+        regex: r"\d+".to_string(),
+      }
+    );
+
+    let params = parser.match_path("/user/123").unwrap();
+    assert_eq!(params.get("id"), Some(&"123".to_string()));
+
+    // 约束需要匹配整个段
+    assert_eq!(
+      parser.match_path("/user/abc"),
+      Err(ParseError::constraint_violation("id", "abc", r"\d+"))
+    );
+    assert!(parser.match_path("/user/12a").is_err());
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn test_constrained_brace_parameter() {
+    let parser = PathParser::new("/posts/{slug:[a-z0-9-]+}").unwrap();
+    assert_eq!(parser.parameter_names(), vec!["slug"]);
+
+    let params = parser.match_path("/posts/hello-world-2").unwrap();
+    assert_eq!(params.get("slug"), Some(&"hello-world-2".to_string()));
+    assert!(matches!(
+      parser.match_path("/posts/Hello_World"),
+      Err(ParseError::ConstraintViolation { .. })
+    ));
+
+    let mut params = HashMap::new();
+    params.insert("slug".to_string(), "rust-tips".to_string());
+    assert_eq!(parser.format_path(&params).unwrap(), "/posts/rust-tips");
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn test_constraints_disambiguate_routes() {
+    let by_id = PathParser::new(r"/posts/:id(\d+)").unwrap();
+    let by_slug = PathParser::new("/posts/:slug").unwrap();
+
+    assert!(by_id.match_path("/posts/42").is_ok());
+    assert!(by_id.match_path("/posts/rust-tips").is_err());
+    assert!(by_slug.match_path("/posts/rust-tips").is_ok());
+
+    // matches 对约束段的判断与 match_path 一致，包括需要解码的值
+    assert!(by_id.matches("/posts/42"));
+    assert!(!by_id.matches("/posts/rust-tips"));
+    assert!(by_id.matches("/posts/%34%32"));
+    assert!(!by_id.matches("/posts/4+2"));
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn test_invalid_constraint() {
+    assert!(PathParser::new("/user/:id([)").is_err());
+    assert!(PathParser::new("/user/:id()").is_err());
+    assert!(PathParser::new("/user/{:\\d+}").is_err());
+  }
+
+  #[cfg(not(feature = "regex"))]
+  #[test]
+  fn test_constraint_requires_feature() {
+    assert!(PathParser::new(r"/user/:id(\d+)").is_err());
+    assert!(PathParser::new("/posts/{slug:[a-z]+}").is_err());
+  }
 }
@@ -3,7 +3,7 @@
 //! 提供查询字符串的解析和格式化功能
 
 use crate::error::{ParseError, ParseResult};
-use crate::utils::{format_query_string, parse_query_string};
+use crate::utils::{format_query_pairs, format_query_string, parse_query_pairs_with_sep, parse_query_string};
 use std::collections::HashMap;
 
 /// 查询参数解析器
@@ -43,6 +43,30 @@ impl QueryParser {
     })
   }
 
+  /// 使用自定义分隔符创建查询参数解析器
+  ///
+  /// 用于对接以 `;` 分隔参数对的旧接口，例如传入 `&['&', ';']` 同时接受两种分隔符。
+  /// 保存的查询字符串按原顺序改用 `&` 连接，因此 `format` 的输出仍然是 `&` 分隔的
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::QueryParser;
+  ///
+  /// let parser = QueryParser::new_with_separators("q=rust;page=2", &['&', ';']).unwrap();
+  /// assert_eq!(parser.get("page"), Some("2"));
+  /// assert_eq!(parser.format(), "q=rust&page=2");
+  /// ```
+  pub fn new_with_separators(query: &str, separators: &[char]) -> ParseResult<Self> {
+    let pairs = parse_query_pairs_with_sep(query, separators)?;
+    let raw_query = format_query_pairs(&pairs);
+    let mut params = HashMap::new();
+    for (key, value) in pairs {
+      params.entry(key).or_insert_with(Vec::new).push(value);
+    }
+    Ok(Self { raw_query, params })
+  }
+
   /// 从参数映射创建查询解析器
   ///
   /// # 参数
@@ -125,7 +149,7 @@ impl QueryParser {
   ///
   /// # 返回值
   ///
-  /// 解析后的值，如果参数不存在或解析失败则返回错误
+  /// 解析后的值，如果参数不存在或解析失败则返回错误，解析失败的错误信息包含参数名
   ///
   /// # 示例
   ///
@@ -143,7 +167,7 @@ impl QueryParser {
     T: crate::traits::FromParam,
   {
     let value = self.get(key).ok_or_else(|| ParseError::missing_parameter(key.to_string()))?;
-    T::from_param(value)
+    T::from_param(value).map_err(|err| value_error(err, format!("failed to parse value of '{key}'")))
   }
 
   /// 获取可选的类型安全值
@@ -154,7 +178,7 @@ impl QueryParser {
   ///
   /// # 返回值
   ///
-  /// 解析后的可选值，如果参数不存在则返回 None，如果解析失败则返回错误
+  /// 解析后的可选值，如果参数不存在则返回 None，如果解析失败则返回包含参数名的错误
   ///
   /// # 示例
   ///
@@ -172,7 +196,9 @@ impl QueryParser {
     T: crate::traits::FromParam,
   {
     match self.get(key) {
-      Some(value) => T::from_param(value).map(Some),
+      Some(value) => T::from_param(value)
+        .map(Some)
+        .map_err(|err| value_error(err, format!("failed to parse value of '{key}'"))),
       None => Ok(None),
     }
   }
@@ -204,7 +230,7 @@ impl QueryParser {
     T: crate::traits::FromParam,
   {
     match self.get(key) {
-      Some(value) => T::from_param(value),
+      Some(value) => T::from_param(value).map_err(|err| value_error(err, format!("failed to parse value of '{key}'"))),
       None => Ok(default),
     }
   }
@@ -217,7 +243,7 @@ impl QueryParser {
   ///
   /// # 返回值
   ///
-  /// 解析后的值向量，如果任何值解析失败则返回错误
+  /// 解析后的值向量，如果任何值解析失败则返回错误，错误信息包含参数名和值的索引（从 0 开始）
   ///
   /// # 示例
   ///
@@ -227,13 +253,21 @@ impl QueryParser {
   /// let parser = QueryParser::new("ids=1&ids=2&ids=3").unwrap();
   /// let ids: Vec<u32> = parser.get_all_parsed("ids").unwrap();
   /// assert_eq!(ids, vec![1, 2, 3]);
+  ///
+  /// let parser = QueryParser::new("ids=1&ids=x").unwrap();
+  /// let err = parser.get_all_parsed::<u32>("ids").unwrap_err();
+  /// assert!(err.to_string().contains("failed to parse value #1 of 'ids'"));
   /// ```
   pub fn get_all_parsed<T>(&self, key: &str) -> ParseResult<Vec<T>>
   where
     T: crate::traits::FromParam,
   {
     let values = self.get_all(key);
-    values.iter().map(|s| T::from_param(s)).collect::<ParseResult<Vec<_>>>()
+    values
+      .iter()
+      .enumerate()
+      .map(|(index, s)| T::from_param(s).map_err(|err| value_error(err, format!("failed to parse value #{index} of '{key}'"))))
+      .collect::<ParseResult<Vec<_>>>()
   }
 
   /// 设置参数值
@@ -281,6 +315,76 @@ impl QueryParser {
     result
   }
 
+  /// 返回覆盖了部分参数的新解析器
+  ///
+  /// 覆盖列表中出现的键会替换原有的所有值，同一个键在列表中出现多次时保留全部值；
+  /// 其余参数保持不变。常用于生成分页链接，例如在当前查询上把 `page` 加一。
+  ///
+  /// # 参数
+  ///
+  /// * `overrides` - 要覆盖的键值对
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::QueryParser;
+  ///
+  /// let current = QueryParser::new("q=rust&page=2").unwrap();
+  /// let next = current.with_overrides(&[("page", "3")]);
+  /// assert_eq!(next.get("page"), Some("3"));
+  /// assert_eq!(next.get("q"), Some("rust"));
+  /// assert_eq!(current.get("page"), Some("2"));
+  /// ```
+  pub fn with_overrides(&self, overrides: &[(&str, &str)]) -> QueryParser {
+    let mut params = self.params.clone();
+    let mut overridden: Vec<&str> = Vec::new();
+
+    for (key, value) in overrides {
+      if overridden.contains(key) {
+        params.entry(key.to_string()).or_default().push(value.to_string());
+      } else {
+        params.insert(key.to_string(), vec![value.to_string()]);
+        overridden.push(key);
+      }
+    }
+
+    QueryParser::from_params(params)
+  }
+
+  /// 返回只包含某个命名空间参数的新解析器
+  ///
+  /// 只保留以 `prefix` 开头的键，并去掉前缀；去掉前缀后为空的键被忽略。
+  /// 适合一个 URL 同时携带多个组件的参数（如 `user.page=1&post.page=2`），
+  /// 每个组件只解析属于自己的部分
+  ///
+  /// # 参数
+  ///
+  /// * `prefix` - 键名前缀，例如 `"user."`
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::QueryParser;
+  ///
+  /// let parser = QueryParser::new("user.page=1&user.sort=name&post.page=2").unwrap();
+  /// let user = parser.with_prefix("user.");
+  /// assert_eq!(user.get("page"), Some("1"));
+  /// assert_eq!(user.get("sort"), Some("name"));
+  /// assert!(!user.contains("post.page"));
+  /// ```
+  pub fn with_prefix(&self, prefix: &str) -> QueryParser {
+    let params = self
+      .params
+      .iter()
+      .filter_map(|(key, values)| {
+        let stripped = key.strip_prefix(prefix)?;
+        (!stripped.is_empty()).then(|| (stripped.to_string(), values.clone()))
+      })
+      .collect();
+
+    QueryParser::from_params(params)
+  }
+
   /// 清空所有参数
   pub fn clear(&mut self) {
     self.params.clear();
@@ -296,6 +400,34 @@ impl QueryParser {
     self.params.keys().map(|s| s.as_str()).collect()
   }
 
+  /// 遍历所有键值对
+  ///
+  /// 多值参数的每个值各产生一项，同一个键的值保持在查询字符串中的顺序；
+  /// 不同键之间的顺序与 HashMap 一致，是不确定的
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::QueryParser;
+  ///
+  /// let parser = QueryParser::new("tags=web&tags=rust").unwrap();
+  /// let pairs: Vec<(&str, &str)> = parser.iter().collect();
+  /// assert_eq!(pairs, vec![("tags", "web"), ("tags", "rust")]);
+  /// ```
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+    self
+      .params
+      .iter()
+      .flat_map(|(key, values)| values.iter().map(move |value| (key.as_str(), value.as_str())))
+  }
+
+  /// 按键遍历参数，每个键只产生一项，包含它的所有值
+  ///
+  /// 不同键之间的顺序是不确定的
+  pub fn iter_grouped(&self) -> impl Iterator<Item = (&str, &[String])> {
+    self.params.iter().map(|(key, values)| (key.as_str(), values.as_slice()))
+  }
+
   /// 检查是否为空
   ///
   /// # 返回值
@@ -385,6 +517,37 @@ impl QueryBuilder {
     self
   }
 
+  /// 值为 `Some` 时设置参数值，为 `None` 时不做任何改动
+  pub fn set_opt<T>(self, key: &str, value: Option<T>) -> Self
+  where
+    T: crate::traits::ToParam,
+  {
+    match value {
+      Some(value) => self.set(key, value),
+      None => self,
+    }
+  }
+
+  /// 依次添加多个参数值，追加在已有的值之后
+  pub fn add_all<T>(mut self, key: &str, values: impl IntoIterator<Item = T>) -> Self
+  where
+    T: crate::traits::ToParam,
+  {
+    let entry = self.params.entry(key.to_string()).or_default();
+    entry.extend(values.into_iter().map(|value| value.to_param()));
+    // 没有任何值时不留下空的参数
+    if entry.is_empty() {
+      self.params.remove(key);
+    }
+    self
+  }
+
+  /// 移除参数的所有值
+  pub fn remove(mut self, key: &str) -> Self {
+    self.params.remove(key);
+    self
+  }
+
   /// 构建查询解析器
   pub fn build(self) -> QueryParser {
     QueryParser::from_params(self.params)
@@ -396,6 +559,15 @@ impl QueryBuilder {
   }
 }
 
+/// 为参数值的解析错误补上参数名等上下文，原始错误作为错误源保留
+fn value_error(err: ParseError, context: String) -> ParseError {
+  let detail = match err.type_conversion_message() {
+    Some(message) => message.to_string(),
+    None => err.to_string(),
+  };
+  ParseError::type_conversion_with_source(format!("{context}: {detail}"), err)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -409,6 +581,19 @@ mod tests {
     assert_eq!(parser.get("missing"), None);
   }
 
+  #[test]
+  fn test_new_with_separators() {
+    let parser = QueryParser::new_with_separators("q=rust;tags=web&tags=api", &['&', ';']).unwrap();
+    assert_eq!(parser.get("q"), Some("rust"));
+    assert_eq!(parser.get_all("tags"), &["web", "api"]);
+    assert_eq!(parser.format(), "q=rust&tags=web&tags=api");
+
+    // 默认解析器只认 '&'
+    let parser = QueryParser::new("q=rust;page=2").unwrap();
+    assert_eq!(parser.get("q"), Some("rust;page=2"));
+    assert_eq!(parser.get("page"), None);
+  }
+
   #[test]
   fn test_parse_multi_value_query() {
     let parser = QueryParser::new("tags=web&tags=backend&tags=rust").unwrap();
@@ -419,6 +604,25 @@ mod tests {
     assert_eq!(parser.get("tags"), Some("web")); // 第一个值
   }
 
+  #[test]
+  fn test_iter_pairs() {
+    let parser = QueryParser::new("tags=web&q=rust&tags=backend&tags=api").unwrap();
+
+    let mut pairs: Vec<(&str, &str)> = parser.iter().collect();
+    assert_eq!(pairs.len(), 4);
+    // 同一个键的值保持原始顺序，排序只影响不同的键
+    pairs.sort_by_key(|(key, _)| *key);
+    assert_eq!(pairs, vec![("q", "rust"), ("tags", "web"), ("tags", "backend"), ("tags", "api")]);
+
+    let mut grouped: Vec<(&str, &[String])> = parser.iter_grouped().collect();
+    grouped.sort_by_key(|(key, _)| *key);
+    assert_eq!(grouped.len(), 2);
+    assert_eq!(grouped[0], ("q", &["rust".to_string()][..]));
+    assert_eq!(grouped[1].1, &["web", "backend", "api"]);
+
+    assert_eq!(QueryParser::new("").unwrap().iter().count(), 0);
+  }
+
   #[test]
   fn test_empty_query() {
     let parser = QueryParser::new("").unwrap();
@@ -445,6 +649,47 @@ mod tests {
     assert_eq!(parser.get("test"), Some("hello world")); // + 解码为空格
   }
 
+  #[test]
+  fn test_plus_in_query_is_space() {
+    let parser = QueryParser::new("q=a+b").unwrap();
+    assert_eq!(parser.get("q"), Some("a b"));
+  }
+
+  #[test]
+  fn test_with_overrides() {
+    let current = QueryParser::new("q=rust&page=2&tags=web&tags=wasm").unwrap();
+
+    // 覆盖单值键，不影响原解析器
+    let next = current.with_overrides(&[("page", "3")]);
+    assert_eq!(next.get("page"), Some("3"));
+    assert_eq!(next.get_all("tags"), &["web", "wasm"]);
+    assert_eq!(current.get("page"), Some("2"));
+
+    // 覆盖多值键会移除全部旧值，重复的覆盖键保留所有新值
+    let retagged = current.with_overrides(&[("tags", "cli"), ("tags", "tui"), ("sort", "new")]);
+    assert_eq!(retagged.get_all("tags"), &["cli", "tui"]);
+    assert_eq!(retagged.get("sort"), Some("new"));
+    assert_eq!(retagged.get("q"), Some("rust"));
+
+    // raw 查询字符串同步更新
+    assert_eq!(QueryParser::new(retagged.raw()).unwrap().get_all("tags"), &["cli", "tui"]);
+  }
+
+  #[test]
+  fn test_with_prefix() {
+    let parser = QueryParser::new("user.page=1&user.sort=name&user.tags=a&user.tags=b&post.page=2&user.=x&page=9").unwrap();
+
+    let user = parser.with_prefix("user.");
+    let mut keys = user.keys();
+    keys.sort_unstable();
+    assert_eq!(keys, vec!["page", "sort", "tags"]);
+    assert_eq!(user.get("page"), Some("1"));
+    assert_eq!(user.get_all("tags"), &["a", "b"]);
+
+    assert_eq!(parser.with_prefix("post.").format(), "page=2");
+    assert!(parser.with_prefix("comment.").is_empty());
+  }
+
   #[test]
   fn test_query_modification() {
     let mut parser = QueryParser::new("q=rust").unwrap();
@@ -460,6 +705,35 @@ mod tests {
     assert_eq!(parser.get("q"), None);
   }
 
+  #[test]
+  fn test_parse_errors_mention_key_and_index() {
+    use std::error::Error;
+
+    let parser = QueryParser::new("ids=1&ids=2&ids=3&ids=x&page=abc").unwrap();
+
+    let err = parser.get_all_parsed::<u32>("ids").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Type conversion error: failed to parse value #3 of 'ids': Cannot convert 'x' to u32"
+    );
+    // 原始错误作为错误源保留
+    let source = err.source().unwrap().downcast_ref::<ParseError>().unwrap();
+    assert_eq!(source.to_string(), "Type conversion error: Cannot convert 'x' to u32");
+
+    let err = parser.get_parsed::<u32>("page").unwrap_err();
+    assert!(err.to_string().contains("failed to parse value of 'page'"));
+    let err = parser.get_optional::<u32>("page").unwrap_err();
+    assert!(err.to_string().contains("failed to parse value of 'page'"));
+    let err = parser.get_with_default::<u32>("page", 1).unwrap_err();
+    assert!(err.to_string().contains("'abc'"));
+
+    // 缺少参数仍然是 MissingParameter
+    assert_eq!(
+      parser.get_parsed::<u32>("limit").unwrap_err(),
+      ParseError::missing_parameter("limit")
+    );
+  }
+
   #[test]
   fn test_query_builder() {
     let query = QueryBuilder::new()
@@ -476,6 +750,31 @@ mod tests {
     assert!(query.contains("tags=backend"));
   }
 
+  #[test]
+  fn test_query_builder_conditional_setters() {
+    let page: Option<u32> = None;
+    let parser = QueryBuilder::new()
+      .set("page", 1u32)
+      .set_opt("page", page)
+      .set_opt("q", Some("rust"))
+      .add("tags", "web")
+      .add_all("tags", ["backend", "api"])
+      .add_all("ids", Vec::<u32>::new())
+      .set("debug", true)
+      .remove("debug")
+      .build();
+
+    // None 不覆盖已有的值
+    assert_eq!(parser.get("page"), Some("1"));
+    assert_eq!(parser.get("q"), Some("rust"));
+    assert_eq!(parser.get_all("tags"), &["web", "backend", "api"]);
+    assert!(!parser.contains("ids"));
+    assert!(!parser.contains("debug"));
+
+    let query = QueryBuilder::new().add_all("ids", [1u32, 2, 3]).build_string();
+    assert_eq!(query, "ids=1&ids=2&ids=3");
+  }
+
   #[test]
   fn test_contains_and_keys() {
     let parser = QueryParser::new("q=rust&page=2&tags=web").unwrap();
@@ -0,0 +1,126 @@
+//! 有序路径路由
+//!
+//! 按顺序对一组路径模式进行首个匹配分发
+
+use std::collections::HashMap;
+
+use super::path::PathParser;
+use crate::error::ParseResult;
+
+/// 有序路径路由器
+///
+/// 按添加顺序保存一组 [`PathParser`]，匹配时返回第一个命中的模式，
+/// 因此更具体的模式应该放在更通用的模式之前。
+#[derive(Debug, Clone)]
+pub struct PathRouter {
+  parsers: Vec<PathParser>,
+}
+
+impl PathRouter {
+  /// 从模式列表创建路由器
+  ///
+  /// # 参数
+  ///
+  /// * `patterns` - 按优先级排列的路径模式
+  ///
+  /// # 返回值
+  ///
+  /// 创建的路由器，任一模式无效时返回错误
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::parser::PathRouter;
+  ///
+  /// let router = PathRouter::from_patterns(&["/users/new", "/users/:id"]).unwrap();
+  /// let (index, params) = router.match_first("/users/42").unwrap();
+  /// assert_eq!(index, 1);
+  /// assert_eq!(params.get("id"), Some(&"42".to_string()));
+  /// ```
+  pub fn from_patterns(patterns: &[&str]) -> ParseResult<Self> {
+    let parsers = patterns
+      .iter()
+      .map(|pattern| PathParser::new(pattern))
+      .collect::<ParseResult<Vec<_>>>()?;
+    Ok(Self { parsers })
+  }
+
+  /// 查找第一个匹配的模式
+  ///
+  /// 先用 [`PathParser::matches`] 快速筛选，只对命中的模式提取参数
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要匹配的路径
+  ///
+  /// # 返回值
+  ///
+  /// 命中模式的索引及其提取的参数，没有模式匹配时返回 None
+  pub fn match_first(&self, path: &str) -> Option<(usize, HashMap<String, String>)> {
+    self.parsers.iter().enumerate().find_map(|(index, parser)| {
+      if parser.matches(path) {
+        parser.match_path(path).ok().map(|params| (index, params))
+      } else {
+        None
+      }
+    })
+  }
+
+  /// 获取所有解析器
+  pub fn parsers(&self) -> &[PathParser] {
+    &self.parsers
+  }
+
+  /// 获取模式数量
+  pub fn len(&self) -> usize {
+    self.parsers.len()
+  }
+
+  /// 检查是否没有任何模式
+  pub fn is_empty(&self) -> bool {
+    self.parsers.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_match_first_returns_index_and_params() {
+    let router = PathRouter::from_patterns(&["/", "/users/:id", "/posts/:year/:slug"]).unwrap();
+    assert_eq!(router.len(), 3);
+
+    assert_eq!(router.match_first("/"), Some((0, HashMap::new())));
+
+    let (index, params) = router.match_first("/posts/2024/hello").unwrap();
+    assert_eq!(index, 2);
+    assert_eq!(params.get("year"), Some(&"2024".to_string()));
+    assert_eq!(params.get("slug"), Some(&"hello".to_string()));
+
+    assert_eq!(router.match_first("/unknown/path"), None);
+  }
+
+  #[test]
+  fn test_match_first_ordering_precedence() {
+    // 重叠的模式按声明顺序决定优先级
+    let specific_first = PathRouter::from_patterns(&["/users/new", "/users/:id", "/users/*rest"]).unwrap();
+    assert_eq!(specific_first.match_first("/users/new").map(|(index, _)| index), Some(0));
+    assert_eq!(specific_first.match_first("/users/7").map(|(index, _)| index), Some(1));
+    assert_eq!(specific_first.match_first("/users/7/posts").map(|(index, _)| index), Some(2));
+
+    let generic_first = PathRouter::from_patterns(&["/users/:id", "/users/new"]).unwrap();
+    let (index, params) = generic_first.match_first("/users/new").unwrap();
+    assert_eq!(index, 0);
+    assert_eq!(params.get("id"), Some(&"new".to_string()));
+  }
+
+  #[test]
+  fn test_from_patterns_errors() {
+    assert!(PathRouter::from_patterns(&["/users/:id", "/files/*"]).is_err());
+
+    let empty = PathRouter::from_patterns(&[]).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.match_first("/"), None);
+  }
+}
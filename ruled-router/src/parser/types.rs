@@ -4,6 +4,11 @@
 
 use crate::error::ParseError;
 use crate::traits::{FromParam, ToParam};
+use std::borrow::Cow;
+use std::num::{
+  NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8,
+  NonZeroUsize,
+};
 
 /// 为基本数字类型实现 FromParam 和 ToParam
 macro_rules! impl_from_to_param_for_numbers {
@@ -11,10 +16,11 @@ macro_rules! impl_from_to_param_for_numbers {
         $(
             impl FromParam for $t {
                 fn from_param(param: &str) -> Result<Self, ParseError> {
-                    param.parse().map_err(|_| {
-                        ParseError::type_conversion(format!(
-                            "Cannot convert '{}' to {}", param, stringify!($t)
-                        ))
+                    param.parse().map_err(|e| {
+                        ParseError::type_conversion_with_source(
+                            format!("Cannot convert '{}' to {}", param, stringify!($t)),
+                            e,
+                        )
                     })
                 }
             }
@@ -31,6 +37,22 @@ macro_rules! impl_from_to_param_for_numbers {
 // 实现所有基本数字类型
 impl_from_to_param_for_numbers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
 
+// 非零整数通过 FromStr 解析，"0" 会被拒绝（ParseIntError 的 kind 为 Zero）
+impl_from_to_param_for_numbers!(
+  NonZeroU8,
+  NonZeroU16,
+  NonZeroU32,
+  NonZeroU64,
+  NonZeroU128,
+  NonZeroUsize,
+  NonZeroI8,
+  NonZeroI16,
+  NonZeroI32,
+  NonZeroI64,
+  NonZeroI128,
+  NonZeroIsize
+);
+
 /// String 的实现
 impl FromParam for String {
   fn from_param(param: &str) -> Result<Self, ParseError> {
@@ -44,8 +66,29 @@ impl ToParam for String {
   }
 }
 
-/// &str 的实现
-impl ToParam for &str {
+/// str 的实现，`&str` 通过引用的实现获得 ToParam
+impl ToParam for str {
+  fn to_param(&self) -> String {
+    self.to_string()
+  }
+}
+
+/// 引用的实现，格式化时可以借用值而不必先克隆
+impl<T: ToParam + ?Sized> ToParam for &T {
+  fn to_param(&self) -> String {
+    (**self).to_param()
+  }
+}
+
+/// Cow<str> 的实现
+impl ToParam for Cow<'_, str> {
+  fn to_param(&self) -> String {
+    self.to_string()
+  }
+}
+
+/// Box<str> 的实现
+impl ToParam for Box<str> {
   fn to_param(&self) -> String {
     self.to_string()
   }
@@ -90,24 +133,141 @@ impl<T: ToParam> ToParam for Option<T> {
   }
 }
 
-/// Vec<T> 的实现（用于多值参数）
-impl<T: FromParam> FromParam for Vec<T> {
+/// 逗号分隔的参数值
+///
+/// 显式地按逗号拆分和连接，适用于 `/tags/:tags` 捕获 "rust,web" 这类确实表示列表的路径参数。
+/// 解析时去掉每个元素两端的空白，并丢弃空元素。
+///
+/// # 迁移说明
+///
+/// 旧版本为 `Vec<T>` 实现了按逗号拆分的 `FromParam` 和按逗号连接的 `ToParam`，
+/// 会把 `Vec<String>` 中的合法值 "a,b" 拆成两个元素。`Vec<T>` 现在不再实现这两个 trait：
+/// 表示列表的单个参数请改用 `CommaSeparated<T>`；多值查询参数由 `QueryParser::get_all_parsed`
+/// 或 Query 派生宏按重复键处理，需要单个键的分隔值时使用 `#[query(delimiter = ",")]`。
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::parser::CommaSeparated;
+/// use ruled_router::traits::{FromParam, ToParam};
+///
+/// let tags = CommaSeparated::<String>::from_param("rust, web,,wasm").unwrap();
+/// assert_eq!(tags.0, vec!["rust", "web", "wasm"]);
+/// assert_eq!(tags.to_param(), "rust,web,wasm");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommaSeparated<T>(pub Vec<T>);
+
+impl<T: FromParam> FromParam for CommaSeparated<T> {
   fn from_param(param: &str) -> Result<Self, ParseError> {
-    if param.is_empty() {
-      Ok(Vec::new())
-    } else {
-      // 假设多个值用逗号分隔
-      param.split(',').map(|s| T::from_param(s.trim())).collect()
-    }
+    param
+      .split(',')
+      .map(str::trim)
+      .filter(|item| !item.is_empty())
+      .map(T::from_param)
+      .collect::<Result<Vec<_>, _>>()
+      .map(CommaSeparated)
   }
 }
 
-impl<T: ToParam> ToParam for Vec<T> {
+impl<T: ToParam> ToParam for CommaSeparated<T> {
   fn to_param(&self) -> String {
-    self.iter().map(|item| item.to_param()).collect::<Vec<_>>().join(",")
+    self.0.iter().map(|item| item.to_param()).collect::<Vec<_>>().join(",")
   }
 }
 
+/// 十六进制整数参数
+///
+/// 适用于 `/tx/:hash` 这类用十六进制表示数字的路径参数。解析时不区分大小写，
+/// 不接受 `0x` 前缀和符号；格式化时输出不带前导零的小写形式
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::parser::Hex;
+/// use ruled_router::traits::{FromParam, ToParam};
+///
+/// let hash = Hex::<u32>::from_param("FF").unwrap();
+/// assert_eq!(hash.0, 255);
+/// assert_eq!(hash.to_param(), "ff");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Hex<T>(pub T);
+
+/// 三十六进制（`0-9a-z`）整数参数
+///
+/// 常用于缩短 URL 中的数字 ID。解析规则与 [`Hex`] 相同，格式化时输出小写形式
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::parser::Base36;
+/// use ruled_router::traits::{FromParam, ToParam};
+///
+/// let id = Base36::<u64>::from_param("zz").unwrap();
+/// assert_eq!(id.0, 1295);
+/// assert_eq!(id.to_param(), "zz");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Base36<T>(pub T);
+
+/// 按给定进制解析无符号整数，拒绝 `from_str_radix` 接受的前导 '+'
+macro_rules! parse_radix {
+  ($param:expr, $t:ty, $radix:expr, $radix_name:expr) => {{
+    let param: &str = $param;
+    let message = || format!("Cannot convert '{}' to {} {}", param, $radix_name, stringify!($t));
+    if param.starts_with('+') {
+      Err(ParseError::type_conversion(message()))
+    } else {
+      <$t>::from_str_radix(param, $radix).map_err(|e| ParseError::type_conversion_with_source(message(), e))
+    }
+  }};
+}
+
+/// 为无符号整数实现 Hex<T> 和 Base36<T> 的 FromParam 和 ToParam
+macro_rules! impl_radix_params {
+    ($($t:ty),*) => {
+        $(
+            impl FromParam for Hex<$t> {
+                fn from_param(param: &str) -> Result<Self, ParseError> {
+                    parse_radix!(param, $t, 16, "hexadecimal").map(Hex)
+                }
+            }
+
+            impl ToParam for Hex<$t> {
+                fn to_param(&self) -> String {
+                    format!("{:x}", self.0)
+                }
+            }
+
+            impl FromParam for Base36<$t> {
+                fn from_param(param: &str) -> Result<Self, ParseError> {
+                    parse_radix!(param, $t, 36, "base36").map(Base36)
+                }
+            }
+
+            impl ToParam for Base36<$t> {
+                fn to_param(&self) -> String {
+                    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+                    let mut value = self.0;
+                    let mut digits = Vec::new();
+                    loop {
+                        digits.push(DIGITS[(value % 36) as usize]);
+                        value /= 36;
+                        if value == 0 {
+                            break;
+                        }
+                    }
+                    digits.reverse();
+                    String::from_utf8(digits).expect("base36 digits are ASCII")
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_params!(u8, u16, u32, u64, u128, usize);
+
 /// char 的实现
 impl FromParam for char {
   fn from_param(param: &str) -> Result<Self, ParseError> {
@@ -127,6 +287,119 @@ impl ToParam for char {
   }
 }
 
+/// 为网络地址类型实现 FromParam 和 ToParam
+///
+/// 通过 `FromStr` 解析，通过 `Display` 格式化。
+/// IPv6 地址在路径段中使用原始的冒号形式（如 `/nodes/2001:db8::1/status`），
+/// 不加方括号：路径段内没有 URL 主机部分那样的端口歧义。
+/// `PathParser::format_path` 会把冒号编码为 `%3A`，匹配时再解码回原始形式。
+/// `SocketAddr` 遵循标准库格式，IPv6 套接字地址为 `[::1]:8080`。
+macro_rules! impl_from_to_param_for_net {
+  ($($t:ty),*) => {
+    $(
+      impl FromParam for $t {
+        fn from_param(param: &str) -> Result<Self, ParseError> {
+          param
+            .parse()
+            .map_err(|e| ParseError::type_conversion_with_source(format!("Cannot convert '{}' to {}: {}", param, stringify!($t), e), e))
+        }
+      }
+
+      impl ToParam for $t {
+        fn to_param(&self) -> String {
+          self.to_string()
+        }
+      }
+    )*
+  };
+}
+
+impl_from_to_param_for_net!(std::net::IpAddr, std::net::Ipv4Addr, std::net::Ipv6Addr, std::net::SocketAddr);
+
+/// uuid::Uuid 的实现（需要启用 `uuid` feature）
+///
+/// 使用 `Uuid::parse_str` 解析，格式化为小写的连字符形式
+#[cfg(feature = "uuid")]
+impl FromParam for uuid::Uuid {
+  fn from_param(param: &str) -> Result<Self, ParseError> {
+    uuid::Uuid::parse_str(param)
+      .map_err(|e| ParseError::type_conversion_with_source(format!("Cannot convert '{param}' to Uuid: {e}"), e))
+  }
+}
+
+#[cfg(feature = "uuid")]
+impl ToParam for uuid::Uuid {
+  fn to_param(&self) -> String {
+    self.hyphenated().to_string()
+  }
+}
+
+/// chrono::NaiveDate 的实现（需要启用 `chrono` feature）
+///
+/// 只接受 ISO-8601 日期 `YYYY-MM-DD`，例如 `2024-03-15`
+#[cfg(feature = "chrono")]
+impl FromParam for chrono::NaiveDate {
+  fn from_param(param: &str) -> Result<Self, ParseError> {
+    chrono::NaiveDate::parse_from_str(param, "%Y-%m-%d").map_err(|e| {
+      ParseError::type_conversion_with_source(format!("Cannot convert '{param}' to NaiveDate (expected YYYY-MM-DD): {e}"), e)
+    })
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl ToParam for chrono::NaiveDate {
+  fn to_param(&self) -> String {
+    self.format("%Y-%m-%d").to_string()
+  }
+}
+
+/// chrono::NaiveDateTime 的实现（需要启用 `chrono` feature）
+///
+/// 接受不带时区的 ISO-8601 日期时间 `YYYY-MM-DDTHH:MM:SS`，秒后可带小数部分，
+/// 例如 `2024-03-15T08:30:00` 或 `2024-03-15T08:30:00.250`。
+/// 带有时区偏移或 `Z` 的字符串会被拒绝，这类值请使用 `DateTime<Utc>`。
+#[cfg(feature = "chrono")]
+impl FromParam for chrono::NaiveDateTime {
+  fn from_param(param: &str) -> Result<Self, ParseError> {
+    chrono::NaiveDateTime::parse_from_str(param, "%Y-%m-%dT%H:%M:%S%.f").map_err(|e| {
+      ParseError::type_conversion_with_source(
+        format!("Cannot convert '{param}' to NaiveDateTime (expected YYYY-MM-DDTHH:MM:SS[.fff]): {e}"),
+        e,
+      )
+    })
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl ToParam for chrono::NaiveDateTime {
+  fn to_param(&self) -> String {
+    self.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+  }
+}
+
+/// chrono::DateTime<Utc> 的实现（需要启用 `chrono` feature）
+///
+/// 接受 RFC-3339 时间戳，必须带时区，例如 `2024-03-15T08:30:00Z` 或 `2024-03-15T16:30:00+08:00`。
+/// 带偏移的值会换算为 UTC，格式化时总是输出以 `Z` 结尾的 UTC 时间，
+/// 因此 `+08:00` 的输入不会原样往返，但表示的是同一时刻。
+#[cfg(feature = "chrono")]
+impl FromParam for chrono::DateTime<chrono::Utc> {
+  fn from_param(param: &str) -> Result<Self, ParseError> {
+    chrono::DateTime::parse_from_rfc3339(param)
+      .map(|datetime| datetime.with_timezone(&chrono::Utc))
+      .map_err(|e| {
+        ParseError::type_conversion_with_source(format!("Cannot convert '{param}' to DateTime<Utc> (expected RFC 3339): {e}"), e)
+      })
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl ToParam for chrono::DateTime<chrono::Utc> {
+  fn to_param(&self) -> String {
+    self.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+  }
+}
+
 /// 自定义类型示例：UserId
 ///
 /// 展示如何为自定义类型实现 FromParam 和 ToParam
@@ -254,6 +527,46 @@ mod tests {
     assert_eq!(std::f64::consts::PI.to_param(), "3.141592653589793");
   }
 
+  #[test]
+  fn test_nonzero_conversions() {
+    assert_eq!(NonZeroU32::from_param("42").unwrap(), NonZeroU32::new(42).unwrap());
+    assert_eq!(NonZeroI64::from_param("-7").unwrap(), NonZeroI64::new(-7).unwrap());
+
+    // 零和非数字都是类型转换错误
+    let err = NonZeroU32::from_param("0").unwrap_err();
+    assert_eq!(
+      err,
+      ParseError::type_conversion_with_source("Cannot convert '0' to NonZeroU32", "0".parse::<NonZeroU32>().unwrap_err())
+    );
+    assert!(matches!(
+      NonZeroU32::from_param("abc"),
+      Err(ParseError::TypeConversionWithSource { .. })
+    ));
+    assert!(matches!(
+      NonZeroU8::from_param("256"),
+      Err(ParseError::TypeConversionWithSource { .. })
+    ));
+
+    assert_eq!(NonZeroU32::new(42).unwrap().to_param(), "42");
+    assert_eq!(NonZeroIsize::new(-3).unwrap().to_param(), "-3");
+  }
+
+  #[test]
+  fn test_number_conversion_error_source() {
+    use std::error::Error;
+
+    let err = u32::from_param("abc").unwrap_err();
+    assert_eq!(err.to_string(), "Type conversion error: Cannot convert 'abc' to u32");
+    let source = err.source().expect("numeric conversion should keep the ParseIntError");
+    assert!(source.downcast_ref::<std::num::ParseIntError>().is_some());
+
+    let err = f64::from_param("1.2.3").unwrap_err();
+    assert!(err.source().unwrap().downcast_ref::<std::num::ParseFloatError>().is_some());
+
+    // 只有消息的转换错误没有错误源
+    assert!(ParseError::type_conversion("custom").source().is_none());
+  }
+
   #[test]
   fn test_string_conversions() {
     assert_eq!(String::from_param("hello").unwrap(), "hello");
@@ -261,6 +574,16 @@ mod tests {
     assert_eq!("test".to_string().to_param(), "test");
   }
 
+  #[test]
+  fn test_borrowed_to_param() {
+    let owned = "hello world".to_string();
+    assert_eq!(<&String as ToParam>::to_param(&&owned), owned.to_param());
+    assert_eq!(<&&u32 as ToParam>::to_param(&&&42), "42");
+    assert_eq!(Cow::Borrowed("borrowed").to_param(), "borrowed");
+    assert_eq!(Cow::<str>::Owned("owned".to_string()).to_param(), "owned");
+    assert_eq!(Box::<str>::from("boxed").to_param(), "boxed");
+  }
+
   #[test]
   fn test_bool_conversions() {
     assert!(bool::from_param("true").unwrap());
@@ -288,13 +611,44 @@ mod tests {
   }
 
   #[test]
-  fn test_vec_conversions() {
-    assert_eq!(Vec::<u32>::from_param("").unwrap(), Vec::<u32>::new());
-    assert_eq!(Vec::<u32>::from_param("1,2,3").unwrap(), vec![1, 2, 3]);
-    assert_eq!(Vec::<String>::from_param("a,b,c").unwrap(), vec!["a", "b", "c"]);
+  fn test_comma_separated_conversions() {
+    assert_eq!(CommaSeparated::<u32>::from_param("1,2,3").unwrap(), CommaSeparated(vec![1, 2, 3]));
+    assert_eq!(CommaSeparated::<u32>::from_param("").unwrap(), CommaSeparated(vec![]));
+    assert_eq!(
+      CommaSeparated::<Slug>::from_param("rust, web").unwrap(),
+      CommaSeparated(vec![Slug("rust".to_string()), Slug("web".to_string())])
+    );
+    assert!(CommaSeparated::<u32>::from_param("1,x").is_err());
+
+    assert_eq!(CommaSeparated(vec![1u32, 2, 3]).to_param(), "1,2,3");
+    assert_eq!(CommaSeparated::<u32>(vec![]).to_param(), "");
+  }
+
+  #[test]
+  fn test_hex_conversions() {
+    assert_eq!(Hex::<u32>::from_param("ff").unwrap(), Hex(255));
+    assert_eq!(Hex::<u64>::from_param("DeadBeef").unwrap(), Hex(0xdead_beef));
+    assert_eq!(Hex(255u32).to_param(), "ff");
+    assert_eq!(Hex(0u8).to_param(), "0");
+
+    // 非十六进制字符、前缀、符号和溢出都是类型转换错误
+    let err = Hex::<u32>::from_param("fg").unwrap_err();
+    assert_eq!(err.kind(), crate::error::ParseErrorKind::TypeConversion);
+    assert_eq!(err.to_string(), "Type conversion error: Cannot convert 'fg' to hexadecimal u32");
+    assert!(Hex::<u32>::from_param("0xff").is_err());
+    assert!(Hex::<u32>::from_param("+ff").is_err());
+    assert!(Hex::<u32>::from_param("").is_err());
+    assert!(Hex::<u8>::from_param("100").is_err());
+  }
 
-    assert_eq!(vec![1u32, 2, 3].to_param(), "1,2,3");
-    assert_eq!(vec!["a".to_string(), "b".to_string()].to_param(), "a,b");
+  #[test]
+  fn test_base36_conversions() {
+    assert_eq!(Base36::<u64>::from_param("zz").unwrap(), Base36(1295));
+    assert_eq!(Base36::<u64>::from_param("ZZ").unwrap(), Base36(1295));
+    assert_eq!(Base36(0u64).to_param(), "0");
+    assert_eq!(Base36(u64::MAX).to_param(), "3w5e11264sgsf");
+    assert_eq!(Base36::<u64>::from_param(&Base36(u64::MAX).to_param()).unwrap(), Base36(u64::MAX));
+    assert!(Base36::<u64>::from_param("a-b").is_err());
   }
 
   #[test]
@@ -308,6 +662,142 @@ mod tests {
     assert_eq!('x'.to_param(), "x");
   }
 
+  #[test]
+  fn test_net_conversions() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    assert_eq!(Ipv4Addr::from_param("192.168.1.10").unwrap(), Ipv4Addr::new(192, 168, 1, 10));
+    assert_eq!(
+      Ipv6Addr::from_param("2001:db8::1").unwrap(),
+      Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)
+    );
+    assert_eq!(IpAddr::from_param("::1").unwrap(), IpAddr::V6(Ipv6Addr::LOCALHOST));
+    assert_eq!(IpAddr::from_param("127.0.0.1").unwrap(), IpAddr::V4(Ipv4Addr::LOCALHOST));
+    assert_eq!(
+      SocketAddr::from_param("[::1]:8080").unwrap(),
+      SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8080)
+    );
+
+    // 格式化使用原始冒号形式，不加方括号
+    assert_eq!(IpAddr::from_param("2001:db8::1").unwrap().to_param(), "2001:db8::1");
+    assert_eq!(SocketAddr::from_param("10.0.0.1:443").unwrap().to_param(), "10.0.0.1:443");
+
+    assert!(IpAddr::from_param("999.1.1.1").is_err());
+    assert!(Ipv4Addr::from_param("::1").is_err());
+    assert!(SocketAddr::from_param("10.0.0.1").is_err());
+    assert!(matches!(
+      Ipv6Addr::from_param("not-an-ip"),
+      Err(ParseError::TypeConversionWithSource { .. })
+    ));
+  }
+
+  #[test]
+  fn test_ipv6_path_param_roundtrip() {
+    use crate::parser::PathParser;
+    use std::net::IpAddr;
+
+    let parser = PathParser::new("/nodes/:addr/status").unwrap();
+    let params = parser.match_path("/nodes/2001:db8::1/status").unwrap();
+    let addr = IpAddr::from_param(&params["addr"]).unwrap();
+    assert_eq!(addr.to_param(), "2001:db8::1");
+
+    let mut format_params = std::collections::HashMap::new();
+    format_params.insert("addr".to_string(), addr.to_param());
+    // format_path 会对冒号做百分号编码，解析时解码回原始形式
+    let formatted = parser.format_path(&format_params).unwrap();
+    assert_eq!(formatted, "/nodes/2001%3Adb8%3A%3A1/status");
+    let reparsed = parser.match_path(&formatted).unwrap();
+    assert_eq!(IpAddr::from_param(&reparsed["addr"]).unwrap(), addr);
+  }
+
+  #[cfg(feature = "uuid")]
+  #[test]
+  fn test_uuid_conversions() {
+    let id = uuid::Uuid::from_param("67E55044-10B1-426F-9247-BB680E5FE0C8").unwrap();
+    assert_eq!(id.to_param(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(uuid::Uuid::from_param(&id.to_param()).unwrap(), id);
+
+    // 也接受不带连字符的形式
+    assert_eq!(uuid::Uuid::from_param("67e5504410b1426f9247bb680e5fe0c8").unwrap(), id);
+
+    assert!(matches!(
+      uuid::Uuid::from_param("not-a-uuid"),
+      Err(ParseError::TypeConversionWithSource { .. })
+    ));
+    assert!(matches!(
+      uuid::Uuid::from_param("67e55044-10b1-426f-9247-bb680e5fe0c"),
+      Err(ParseError::TypeConversionWithSource { .. })
+    ));
+
+    // 与数字、网络地址类型一致，保留底层的 uuid::Error 作为错误源
+    let err = uuid::Uuid::from_param("not-a-uuid").unwrap_err();
+    assert!(std::error::Error::source(&err).unwrap().downcast_ref::<uuid::Error>().is_some());
+  }
+
+  #[cfg(feature = "chrono")]
+  #[test]
+  fn test_chrono_naive_date_conversions() {
+    use chrono::NaiveDate;
+
+    let date = NaiveDate::from_param("2024-03-15").unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+    assert_eq!(date.to_param(), "2024-03-15");
+    assert_eq!(NaiveDate::from_param(&date.to_param()).unwrap(), date);
+
+    assert!(NaiveDate::from_param("2024-02-30").is_err());
+    assert!(NaiveDate::from_param("15/03/2024").is_err());
+    match NaiveDate::from_param("2024-13-01") {
+      Err(ParseError::TypeConversionWithSource { message, source }) => {
+        assert!(message.contains("2024-13-01"));
+        assert!(source.downcast_ref::<chrono::ParseError>().is_some());
+      }
+      other => panic!("Expected TypeConversion error, got {other:?}"),
+    }
+  }
+
+  #[cfg(feature = "chrono")]
+  #[test]
+  fn test_chrono_naive_datetime_conversions() {
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    let datetime = NaiveDateTime::from_param("2024-03-15T08:30:00").unwrap();
+    assert_eq!(
+      datetime,
+      NaiveDate::from_ymd_opt(2024, 3, 15).unwrap().and_hms_opt(8, 30, 0).unwrap()
+    );
+    assert_eq!(datetime.to_param(), "2024-03-15T08:30:00");
+    assert_eq!(NaiveDateTime::from_param(&datetime.to_param()).unwrap(), datetime);
+
+    let fractional = NaiveDateTime::from_param("2024-03-15T08:30:00.250").unwrap();
+    assert_eq!(fractional.to_param(), "2024-03-15T08:30:00.250");
+    assert_eq!(NaiveDateTime::from_param(&fractional.to_param()).unwrap(), fractional);
+
+    // 不接受时区
+    assert!(NaiveDateTime::from_param("2024-03-15T08:30:00Z").is_err());
+    assert!(NaiveDateTime::from_param("2024-03-15").is_err());
+  }
+
+  #[cfg(feature = "chrono")]
+  #[test]
+  fn test_chrono_utc_datetime_conversions() {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    let datetime = DateTime::<Utc>::from_param("2024-03-15T08:30:00Z").unwrap();
+    assert_eq!(datetime, Utc.with_ymd_and_hms(2024, 3, 15, 8, 30, 0).unwrap());
+    assert_eq!(datetime.to_param(), "2024-03-15T08:30:00Z");
+    assert_eq!(DateTime::<Utc>::from_param(&datetime.to_param()).unwrap(), datetime);
+
+    // 偏移量换算为 UTC
+    let offset = DateTime::<Utc>::from_param("2024-03-15T16:30:00+08:00").unwrap();
+    assert_eq!(offset, datetime);
+    assert_eq!(offset.to_param(), "2024-03-15T08:30:00Z");
+
+    match DateTime::<Utc>::from_param("2024-03-15T08:30:00") {
+      Err(ParseError::TypeConversionWithSource { message, .. }) => assert!(message.contains("2024-03-15T08:30:00")),
+      other => panic!("Expected TypeConversion error, got {other:?}"),
+    }
+  }
+
   #[test]
   fn test_user_id() {
     assert_eq!(UserId::from_param("123").unwrap(), UserId(123));
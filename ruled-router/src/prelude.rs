@@ -2,15 +2,15 @@
 //!
 //! This module re-exports the most commonly used items from the crate.
 
-pub use crate::error::ParseError;
+pub use crate::error::{ParseError, ParseErrorKind, QueryWarning};
 pub use crate::formatter::{PathFormatter, QueryFormatter};
 pub use crate::parser::{PathParser, QueryParser};
-pub use crate::traits::{FromParam, Query, RouteMatcher, RouterData, ToParam};
+pub use crate::traits::{FromParam, Paginated, Query, RouteMatcher, RouterData, ToParam, TryFromParam};
 pub use crate::utils::*;
 
 #[cfg(feature = "derive")]
-pub use ruled_router_derive::{QueryDerive, RouterData};
+pub use ruled_router_derive::{Param, QueryDerive, RouterData};
 
 // DOM 功能导出（只有在启用 dom feature 时才导出）
 #[cfg(feature = "dom")]
-pub use crate::dom::{helpers, DomRouter};
+pub use crate::dom::{helpers, BrowserRouter, DomRouter};
@@ -0,0 +1,174 @@
+//! 基于 serde 的查询参数解析
+//!
+//! 通过 `serde_urlencoded` 解析和格式化查询字符串，供 `#[derive(QuerySerde)]` 生成的代码调用。
+//! 需要启用 `serde-query` feature。
+//!
+//! `serde_urlencoded` 只支持扁平的键值对，重复的键不会被合并为数组，因此 `QuerySerde`
+//! 不支持 `Vec<T>` 等序列字段，派生时直接报错。需要重复键语义时请使用 `QueryDerive`。
+
+use std::collections::HashMap;
+
+use serde::de::value::{BorrowedStrDeserializer, Error as ValueError, MapDeserializer};
+use serde::de::{DeserializeOwned, Error as _, IntoDeserializer, Visitor};
+use serde::{forward_to_deserialize_any, Deserializer, Serialize};
+
+use crate::error::ParseError;
+
+/// 使用 serde 从查询字符串解析
+///
+/// # 参数
+///
+/// * `query` - 查询字符串，不包含前导的 '?'
+///
+/// # 返回值
+///
+/// 解析后的值，`serde_urlencoded` 报错时返回 `ParseError::InvalidQuery`
+pub fn parse<T: DeserializeOwned>(query: &str) -> Result<T, ParseError> {
+  serde_urlencoded::from_str(query).map_err(|e| ParseError::invalid_query(e.to_string()))
+}
+
+/// 使用 serde 从已解析的查询参数映射构造
+///
+/// 直接反序列化映射中的键值对，不再重新拼接为查询字符串。值的解析规则与 `serde_urlencoded` 一致：
+/// 数字和布尔值按 `FromStr` 解析，`Option<T>` 字段出现即为 `Some`，单元枚举按变体名匹配。
+/// 同一个键的多个值会作为重复字段报错
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::serde_query::from_query_map;
+/// use ruled_router::utils::parse_query_string;
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct Search {
+///   q: String,
+///   page: Option<u32>,
+/// }
+///
+/// let map = parse_query_string("q=rust&page=2").unwrap();
+/// let search: Search = from_query_map(&map).unwrap();
+/// assert_eq!(search, Search { q: "rust".to_string(), page: Some(2) });
+/// ```
+pub fn from_query_map<T: DeserializeOwned>(query_map: &HashMap<String, Vec<String>>) -> Result<T, ParseError> {
+  let pairs = query_map
+    .iter()
+    .flat_map(|(key, values)| values.iter().map(move |value| (key.as_str(), ValueDeserializer(value))));
+  T::deserialize(MapDeserializer::<_, ValueError>::new(pairs)).map_err(|e| ParseError::invalid_query(e.to_string()))
+}
+
+/// 单个查询参数值的反序列化器，行为与 `serde_urlencoded` 对值的处理一致
+struct ValueDeserializer<'de>(&'de str);
+
+impl<'de> IntoDeserializer<'de, ValueError> for ValueDeserializer<'de> {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+/// 按 `FromStr` 解析数字和布尔值
+macro_rules! deserialize_parsed {
+  ($($method:ident => $visit:ident: $t:ty,)*) => {
+    $(
+      fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        let value: $t = self.0.parse().map_err(ValueError::custom)?;
+        visitor.$visit(value)
+      }
+    )*
+  };
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+  type Error = ValueError;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+    visitor.visit_borrowed_str(self.0)
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+    visitor.visit_some(self)
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, ValueError> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    _variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, ValueError> {
+    visitor.visit_enum(BorrowedStrDeserializer::new(self.0))
+  }
+
+  deserialize_parsed! {
+    deserialize_bool => visit_bool: bool,
+    deserialize_i8 => visit_i8: i8,
+    deserialize_i16 => visit_i16: i16,
+    deserialize_i32 => visit_i32: i32,
+    deserialize_i64 => visit_i64: i64,
+    deserialize_u8 => visit_u8: u8,
+    deserialize_u16 => visit_u16: u16,
+    deserialize_u32 => visit_u32: u32,
+    deserialize_u64 => visit_u64: u64,
+    deserialize_f32 => visit_f32: f32,
+    deserialize_f64 => visit_f64: f64,
+  }
+
+  forward_to_deserialize_any! {
+    i128 u128 char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+  }
+}
+
+/// 使用 serde 格式化为查询字符串
+///
+/// # 参数
+///
+/// * `value` - 要格式化的值
+///
+/// # 返回值
+///
+/// 格式化后的查询字符串，不包含前导的 '?'，`serde_urlencoded` 报错时返回 `ParseError::InvalidQuery`
+pub fn format<T: Serialize>(value: &T) -> Result<String, ParseError> {
+  serde_urlencoded::to_string(value).map_err(|e| ParseError::invalid_query(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+  struct Search {
+    q: String,
+    page: Option<u32>,
+  }
+
+  #[test]
+  fn test_parse_and_format() {
+    let search: Search = parse("q=hello%20world&page=2").unwrap();
+    assert_eq!(
+      search,
+      Search {
+        q: "hello world".to_string(),
+        page: Some(2),
+      }
+    );
+    assert_eq!(format(&search).unwrap(), "q=hello+world&page=2");
+  }
+
+  #[test]
+  fn test_errors_map_to_invalid_query() {
+    assert!(matches!(parse::<Search>("page=2"), Err(ParseError::InvalidQuery(_))));
+    assert!(matches!(parse::<Search>("q=a&page=x"), Err(ParseError::InvalidQuery(_))));
+  }
+
+  #[test]
+  fn test_from_query_map_matches_parse() {
+    for query in ["q=hello%20world&page=2", "q=rust", "q=", "page=2", "q=a&page=x", "q=a&q=b"] {
+      let map = crate::utils::parse_query_string(query).unwrap();
+      assert_eq!(from_query_map::<Search>(&map), parse::<Search>(query), "{query}");
+    }
+  }
+}
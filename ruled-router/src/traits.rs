@@ -2,7 +2,9 @@
 //!
 //! 定义了路由解析和格式化的核心接口
 
-use crate::error::{ParseError, RouteState};
+use crate::error::{ParseError, QueryWarning, RouteState};
+use crate::parser::{PathParser, PathParserCache, PathSegment};
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 /// 嵌套路由解析结果
@@ -29,6 +31,158 @@ pub struct RouteInfo {
   pub sub_route_info: Option<Box<RouteInfo>>,
 }
 
+impl RouteInfo {
+  /// 按深度优先顺序返回每一层路由信息
+  ///
+  /// 第一个元素是当前层级，随后依次是各级子路由
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::traits::RouteInfo;
+  ///
+  /// let info = RouteInfo {
+  ///   pattern: "/users",
+  ///   formatted: "/users/123".to_string(),
+  ///   sub_route_info: Some(Box::new(RouteInfo {
+  ///     pattern: "/:id",
+  ///     formatted: "/123".to_string(),
+  ///     sub_route_info: None,
+  ///   })),
+  /// };
+  ///
+  /// let levels = info.flatten();
+  /// assert_eq!(levels.len(), 2);
+  /// assert_eq!(levels[1].formatted, "/123");
+  /// assert_eq!(info.segments(), vec!["/users", "/:id"]);
+  /// ```
+  pub fn flatten(&self) -> Vec<&RouteInfo> {
+    let mut levels = vec![self];
+    let mut current = self;
+    while let Some(sub) = &current.sub_route_info {
+      levels.push(sub);
+      current = sub;
+    }
+    levels
+  }
+
+  /// 返回从当前层级到最深层的模式链
+  pub fn segments(&self) -> Vec<&'static str> {
+    self.flatten().into_iter().map(|info| info.pattern).collect()
+  }
+}
+
+/// 静态的路由树描述
+///
+/// 与 [`RouteInfo`] 不同，它不依赖路由实例，描述的是所有可能的路由结构，适合生成站点地图或路由文档。
+/// 由 [`RouteMatcher::describe`] 生成，根节点对应路由匹配器本身，`pattern` 为空字符串。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteTree {
+  /// 当前层级的模式字符串
+  pub pattern: &'static str,
+  /// 子路由，按变体声明顺序排列
+  pub children: Vec<RouteTree>,
+}
+
+impl RouteTree {
+  /// 列出所有叶子节点的完整模式
+  ///
+  /// 从根到叶子逐层拼接模式，例如 "/users"、"/profile"、"/basic/:id" 拼接为 "/users/profile/basic/:id"。
+  /// 子模式为 "/" 时不增加路径段；只有根节点时返回空列表。结果按深度优先、变体声明顺序排列。
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::traits::RouteTree;
+  ///
+  /// let tree = RouteTree {
+  ///   pattern: "",
+  ///   children: vec![
+  ///     RouteTree { pattern: "/", children: vec![] },
+  ///     RouteTree {
+  ///       pattern: "/users",
+  ///       children: vec![
+  ///         RouteTree { pattern: "/", children: vec![] },
+  ///         RouteTree { pattern: "/:id", children: vec![] },
+  ///       ],
+  ///     },
+  ///   ],
+  /// };
+  /// assert_eq!(tree.full_patterns(), vec!["/", "/users", "/users/:id"]);
+  /// ```
+  pub fn full_patterns(&self) -> Vec<String> {
+    let mut patterns = Vec::new();
+    for child in &self.children {
+      child.collect_full_patterns(self.pattern, &mut patterns);
+    }
+    patterns
+  }
+
+  /// 把当前节点拼接到 `prefix` 之后，递归收集叶子节点的完整模式
+  fn collect_full_patterns(&self, prefix: &str, patterns: &mut Vec<String>) {
+    let prefix = prefix.trim_end_matches('/');
+    let full = match self.pattern.trim_start_matches('/') {
+      "" if prefix.is_empty() => "/".to_string(),
+      "" => prefix.to_string(),
+      rest => format!("{prefix}/{rest}"),
+    };
+
+    if self.children.is_empty() {
+      patterns.push(full);
+    } else {
+      for child in &self.children {
+        child.collect_full_patterns(&full, patterns);
+      }
+    }
+  }
+}
+
+/// OpenAPI 风格的路径描述
+///
+/// 把 `/users/:id` 这类模式转换为 OpenAPI 使用的 `/users/{id}` 形式，并列出参数名。
+/// 可选参数和通配符同样使用 `{name}` 表示，OpenAPI 本身无法表达这两种语义。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenApiPath {
+  /// 使用 `{param}` 占位符的路径
+  pub path: String,
+  /// 按出现顺序排列的参数名
+  pub parameters: Vec<String>,
+}
+
+impl OpenApiPath {
+  /// 从路径模式创建 OpenAPI 路径
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::traits::OpenApiPath;
+  ///
+  /// let path = OpenApiPath::from_pattern("/files/:id/?:format/*path").unwrap();
+  /// assert_eq!(path.path, "/files/{id}/{format}/{path}");
+  /// assert_eq!(path.parameters, vec!["id", "format", "path"]);
+  /// ```
+  pub fn from_pattern(pattern: &str) -> Result<Self, ParseError> {
+    let parser = PathParser::new(pattern)?;
+
+    let segments: Vec<String> = parser
+      .segments()
+      .iter()
+      .map(|segment| match segment {
+        PathSegment::Literal(literal) => literal.clone(),
+        PathSegment::Parameter(name)
+        | PathSegment::ConstrainedParameter { name, .. }
+        | PathSegment::OptionalParameter(name)
+        | PathSegment::Wildcard(name) => format!("{{{name}}}"),
+      })
+      .collect();
+
+    Ok(OpenApiPath {
+      path: format!("/{}", segments.join("/")),
+      parameters: parser.parameter_names().into_iter().map(String::from).collect(),
+    })
+  }
+}
+
 /// 将路由匹配器转换为路由信息的 trait
 pub trait ToRouteInfo {
   /// 将当前路由匹配器转换为路由信息
@@ -64,6 +218,37 @@ pub trait RouteMatcher: Sized + ToRouteInfo {
   /// ```
   fn try_parse(path: &str) -> Result<Self, ParseError>;
 
+  /// 解析路径，同时返回胜出变体的模式捕获到的路径参数
+  ///
+  /// 参数表与对该变体的模式调用 [`PathParser::match_path`] 得到的结果一致，
+  /// 只包含当前层级的参数，不包含子路由的参数。
+  ///
+  /// `#[derive(RouterMatch)]` 生成的实现在匹配时直接保留参数；
+  /// 默认实现先调用 `try_parse`，再按 [`ToRouteInfo::to_route_info`] 给出的模式重新匹配一次。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要解析的路径字符串
+  ///
+  /// # 返回值
+  ///
+  /// 成功时返回匹配的路由枚举变体及其路径参数，失败时返回 ParseError
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let (route, params) = AppRouterMatch::match_with_params("/users/123")?;
+  /// assert_eq!(params.get("id"), Some(&"123".to_string()));
+  /// ```
+  fn match_with_params(path: &str) -> Result<(Self, HashMap<String, String>), ParseError> {
+    let route = Self::try_parse(path)?;
+    let parser = PathParser::new(route.to_route_info().pattern)?;
+    let (path_part, _) = crate::utils::split_path_query(path);
+    let consumed = parser.consumed_length(path_part)?;
+    let params = parser.match_path(&path_part[..consumed])?;
+    Ok((route, params))
+  }
+
   /// 将路由匹配格式化为路径字符串
   ///
   /// # 返回值
@@ -78,6 +263,25 @@ pub trait RouteMatcher: Sized + ToRouteInfo {
   /// ```
   fn format(&self) -> String;
 
+  /// 格式化路由并追加额外的查询参数
+  ///
+  /// 适合在链接上附加 `ref=email` 这类不属于路由定义的临时参数。
+  /// 路由自身已有查询参数时用 '&' 连接，否则用 '?'，键和值都会被 URL 编码。
+  ///
+  /// # 参数
+  ///
+  /// * `extra` - 要追加的键值对
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let url = route_match.format_with_query(&[("ref", "email")]);
+  /// assert_eq!(url, "/users/123?tab=profile&ref=email");
+  /// ```
+  fn format_with_query(&self, extra: &[(&str, &str)]) -> String {
+    crate::utils::append_query_pairs(&self.format(), extra)
+  }
+
   /// 获取所有可能的路由模式
   ///
   /// # 返回值
@@ -92,6 +296,144 @@ pub trait RouteMatcher: Sized + ToRouteInfo {
   /// ```
   fn patterns() -> Vec<&'static str>;
 
+  /// 计算路径开头能被哪个模式匹配，返回其中最长的字面量前缀长度
+  ///
+  /// 外层 `RouterMatch` 用它比较 `#[route(matcher)]` 包装的匹配器与其他变体的具体程度。
+  /// 默认实现每次编译 [`RouteMatcher::patterns`]，`#[derive(RouterMatch)]` 使用各路由缓存的解析器。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 不包含查询参数的路径
+  ///
+  /// # 返回值
+  ///
+  /// 所有模式都无法匹配路径开头时返回 None
+  fn match_score(path: &str) -> Option<usize> {
+    Self::patterns()
+      .into_iter()
+      .filter_map(|pattern| PathParser::new(pattern).ok()?.prefix_match_score(path))
+      .max()
+  }
+
+  /// 描述所有可能的嵌套路由结构
+  ///
+  /// 返回的根节点 `pattern` 为空，每个子节点对应一个变体的 [`RouterData::pattern`]，
+  /// 并递归包含其 `SubRouterMatch` 的子节点。
+  ///
+  /// `#[derive(RouterMatch)]` 生成的实现会递归展开子路由；
+  /// 默认实现基于 [`RouteMatcher::patterns`]，只有一层。
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let tree = AppRouterMatch::describe();
+  /// assert_eq!(tree.children[0].pattern, "/users");
+  /// assert_eq!(tree.children[0].children[0].pattern, "/profile");
+  /// ```
+  fn describe() -> RouteTree {
+    RouteTree {
+      pattern: "",
+      children: Self::patterns()
+        .into_iter()
+        .map(|pattern| RouteTree { pattern, children: vec![] })
+        .collect(),
+    }
+  }
+
+  /// 列出匹配器能够产生的所有完整叶子路径模式
+  ///
+  /// 基于 [`RouteMatcher::describe`]，逐层拼接每个变体的模式和其子路由的模式，
+  /// 适合生成站点地图、路由文档，或在测试中核对完整的路由表面。
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let patterns = AppRouterMatch::all_full_patterns();
+  /// assert!(patterns.contains(&"/users/profile/basic/:id".to_string()));
+  /// ```
+  fn all_full_patterns() -> Vec<String> {
+    Self::describe().full_patterns()
+  }
+
+  /// 在运行时决定的挂载前缀下解析路径
+  ///
+  /// 去掉 `path` 开头的 `prefix` 后对剩余部分调用 [`RouteMatcher::try_parse`]，
+  /// 同一组路由因此可以挂载在不同的位置。前缀必须在段边界结束（`/v1` 不匹配 `/v10/users`），
+  /// 末尾的 '/' 会被忽略；剩余部分为空或只有查询字符串时按根路径 "/" 解析。
+  ///
+  /// # 参数
+  ///
+  /// * `prefix` - 挂载前缀，例如 "/plugins/blog"
+  /// * `path` - 要解析的完整路径
+  ///
+  /// # 返回值
+  ///
+  /// 成功时返回匹配的路由，路径不以前缀开头或剩余部分无法解析时返回 ParseError
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let route = PluginRoutes::try_parse_under("/plugins/blog", "/plugins/blog/posts/1")?;
+  /// assert_eq!(route, PluginRoutes::try_parse("/posts/1")?);
+  /// ```
+  fn try_parse_under(prefix: &str, path: &str) -> Result<Self, ParseError> {
+    let prefix = prefix.trim_end_matches('/');
+    let rest = path
+      .strip_prefix(prefix)
+      .filter(|rest| rest.is_empty() || rest.starts_with(['/', '?', '#']))
+      .ok_or_else(|| ParseError::invalid_path(format!("Path '{path}' is not under prefix '{prefix}'")))?;
+
+    if rest.starts_with('/') {
+      Self::try_parse(rest)
+    } else {
+      Self::try_parse(&format!("/{rest}"))
+    }
+  }
+
+  /// 严格地解析完整路径，逐层解析所有嵌套路由
+  ///
+  /// 与 [`RouteMatcher::try_parse`] 不同，任意层级留下无法被子路由消费的剩余路径时都会返回错误，
+  /// 而不是把 `RouteState::ParseFailed` 记录在结果中。
+  ///
+  /// `#[derive(RouterMatch)]` 生成的实现会对匹配的变体调用 [`RouterData::parse_nested`]；
+  /// 默认实现等同于 `try_parse`。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要解析的完整路径
+  ///
+  /// # 返回值
+  ///
+  /// 所有层级都解析成功时返回完整的路由树，否则返回 ParseError
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let route = AppRouterMatch::try_parse_nested("/users/profile/basic/123")?;
+  /// assert!(AppRouterMatch::try_parse_nested("/users/profile/unknown").is_err());
+  /// ```
+  fn try_parse_nested(path: &str) -> Result<Self, ParseError> {
+    Self::try_parse(path)
+  }
+
+  /// 导出所有模式的 OpenAPI 路径描述
+  ///
+  /// 默认实现基于 [`RouteMatcher::patterns`]，跳过无法解析的模式
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// for item in AppRouterMatch::openapi_paths() {
+  ///   println!("{} {:?}", item.path, item.parameters);
+  /// }
+  /// ```
+  fn openapi_paths() -> Vec<OpenApiPath> {
+    Self::patterns()
+      .into_iter()
+      .filter_map(|pattern| OpenApiPath::from_pattern(pattern).ok())
+      .collect()
+  }
+
   /// 尝试解析路径的剩余部分（用于嵌套路由）
   ///
   /// # 参数
@@ -147,6 +489,24 @@ pub trait RouteMatcher: Sized + ToRouteInfo {
       indent_str, indent_str, route_info.pattern, indent_str, route_info.formatted
     )
   }
+
+  /// 格式化为适合单行日志的 URL 分解
+  ///
+  /// 输出形如 `path: /users/123 | query: page=1&limit=20 | sub: /profile/basic`：
+  /// `path` 是当前层级自身的路径，`query` 是最终 URL 的查询部分，`sub` 是子路由的路径。
+  /// 没有查询参数或子路由时省略对应部分
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let route_match = AppRouterMatch::try_parse("/users/123/profile/basic?page=1")?;
+  /// assert_eq!(route_match.format_pretty(), "path: /users/123 | query: page=1 | sub: /profile/basic");
+  /// ```
+  fn format_pretty(&self) -> String {
+    let route_info = self.to_route_info();
+    let sub_formatted = route_info.sub_route_info.map(|sub_info| sub_info.formatted);
+    crate::utils::format_pretty_url(&route_info.formatted, sub_formatted.as_deref())
+  }
 }
 
 /// 空的路由匹配类型，用于没有子路由的情况
@@ -219,6 +579,21 @@ pub trait RouterData: Sized {
   /// ```
   fn parse(path: &str) -> Result<Self, ParseError>;
 
+  /// 从字节形式的路径解析路由
+  ///
+  /// 用于底层 HTTP 服务器直接给出 `&[u8]` 请求目标的场景，避免在调用处使用有损的
+  /// `String::from_utf8_lossy`。默认实现校验 UTF-8 后交给 [`parse`](Self::parse)，
+  /// 非法 UTF-8 返回 `UrlEncoding` 错误。
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let route = MyRoute::parse_bytes(b"/user/123?tab=profile")?;
+  /// ```
+  fn parse_bytes(path: &[u8]) -> Result<Self, ParseError> {
+    Self::parse(crate::utils::path_from_bytes(path)?)
+  }
+
   /// 将路由格式化为路径字符串
   ///
   /// # 返回值
@@ -233,6 +608,24 @@ pub trait RouterData: Sized {
   /// ```
   fn format(&self) -> String;
 
+  /// 格式化路由并追加额外的查询参数
+  ///
+  /// 路由自身已有查询参数时用 '&' 连接，否则用 '?'，键和值都会被 URL 编码。
+  ///
+  /// # 参数
+  ///
+  /// * `extra` - 要追加的键值对
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let url = route.format_with_query(&[("ref", "email")]);
+  /// assert_eq!(url, "/user/123?tab=profile&ref=email");
+  /// ```
+  fn format_with_query(&self, extra: &[(&str, &str)]) -> String {
+    crate::utils::append_query_pairs(&self.format(), extra)
+  }
+
   /// 将路由和可能的子路由格式化为完整路径字符串
   ///
   /// 此方法专门用于处理包含子路由的 RouterData 结构。
@@ -253,6 +646,48 @@ pub trait RouterData: Sized {
     self.format()
   }
 
+  /// 把格式化后的路径和查询参数拼接到基础 URL 上
+  ///
+  /// 基础 URL 的路径作为前缀保留，已有的查询参数排在路由的查询参数之前，片段保持不变
+  ///
+  /// # 参数
+  ///
+  /// * `base` - 基础 URL，例如 `https://example.com/app/`
+  ///
+  /// # 返回值
+  ///
+  /// 拼接后的绝对 URL
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let base = url::Url::parse("https://example.com/app/?lang=en").unwrap();
+  /// let url = route.to_url(&base);
+  /// assert_eq!(url.as_str(), "https://example.com/app/user/123?lang=en&tab=profile");
+  /// ```
+  #[cfg(feature = "url")]
+  fn to_url(&self, base: &url::Url) -> url::Url {
+    let formatted = self.format();
+    let (route_path, route_query) = crate::utils::split_path_query(&formatted);
+
+    let mut url = base.clone();
+    let path = format!("{}/{}", base.path().trim_end_matches('/'), route_path.trim_start_matches('/'));
+    url.set_path(&path);
+
+    let queries: Vec<&str> = [base.query(), route_query]
+      .into_iter()
+      .flatten()
+      .filter(|query| !query.is_empty())
+      .collect();
+    if queries.is_empty() {
+      url.set_query(None);
+    } else {
+      url.set_query(Some(&queries.join("&")));
+    }
+
+    url
+  }
+
   /// 获取路由模式（用于调试和文档生成）
   ///
   /// # 返回值
@@ -260,6 +695,58 @@ pub trait RouterData: Sized {
   /// 路由的模式字符串，例如 "/user/:id"
   fn pattern() -> &'static str;
 
+  /// 获取别名模式
+  ///
+  /// 解析时在 [`RouterData::pattern`] 匹配失败后依次尝试这些模式，格式化总是使用主模式。
+  /// `#[router(alias = "...")]` 生成的实现返回声明顺序的别名，默认实现没有别名。
+  ///
+  /// # 返回值
+  ///
+  /// 别名模式列表，例如旧版链接使用的 "/u/:id"
+  fn aliases() -> &'static [&'static str] {
+    &[]
+  }
+
+  /// 获取主模式和别名编译后的解析器缓存，顺序与 [`RouterData::pattern`]、[`RouterData::aliases`] 一致
+  ///
+  /// `RouterMatch` 在每次匹配时从这里取得解析器，避免重复编译模式。
+  /// `#[derive(RouterData)]` 返回在所有调用之间共享的静态缓存；
+  /// 默认实现返回空切片，此时调用方每次都重新编译模式。
+  fn path_parser_caches() -> &'static [PathParserCache] {
+    &[]
+  }
+
+  /// 从路径参数映射构建路由，是 [`PathParser::match_path`] 的逆操作
+  ///
+  /// 适合在测试或生成链接时直接构造路由，而不必先拼接路径字符串。
+  /// 查询参数取空查询字符串解析得到的默认值，没有出现在模式中的键会被忽略；
+  /// 缺少模式参数、参数值无法转换或要求必须存在子路由时返回错误。
+  ///
+  /// # 参数
+  ///
+  /// * `params` - 以参数名为键的路径参数
+  ///
+  /// # 示例
+  ///
+  /// ```rust
+  /// use ruled_router::prelude::*;
+  /// use std::collections::HashMap;
+  ///
+  /// #[derive(Debug, PartialEq, RouterData)]
+  /// #[router(pattern = "/users/:id")]
+  /// struct UserRoute {
+  ///   id: u32,
+  /// }
+  ///
+  /// let params = HashMap::from([("id".to_string(), "123".to_string())]);
+  /// assert_eq!(UserRoute::from_params(params).unwrap(), UserRoute { id: 123 });
+  /// ```
+  fn from_params(params: HashMap<String, String>) -> Result<Self, ParseError> {
+    // 默认实现：按模式格式化路径后再解析，参数值的编码和类型转换与 parse 完全一致
+    let parser = PathParser::new(Self::pattern())?;
+    Self::parse(&parser.format_path(&params)?)
+  }
+
   /// 解析路径并返回路由和子路由状态
   ///
   /// # 参数
@@ -292,6 +779,22 @@ pub trait RouterData: Sized {
     Ok((route, RouteState::NoSubRoute))
   }
 
+  /// 严格地解析路由及其所有嵌套子路由
+  ///
+  /// 剩余路径交给子路由的 [`RouteMatcher::try_parse_nested`] 继续解析，
+  /// 任意层级无法消费剩余路径时返回错误。默认实现等同于 `parse`。
+  ///
+  /// # 参数
+  ///
+  /// * `path` - 要解析的路径字符串，可以包含查询参数
+  ///
+  /// # 返回值
+  ///
+  /// 所有层级都解析成功时返回路由实例，否则返回 ParseError
+  fn parse_nested(path: &str) -> Result<Self, ParseError> {
+    Self::parse(path)
+  }
+
   /// 格式化路由和子路由为完整路径
   ///
   /// # 参数
@@ -330,13 +833,22 @@ pub trait RouterData: Sized {
   ///
   /// # 返回值
   ///
-  /// 当前路由消费的路径长度
+  /// 当前路由消费的路径长度，不包含查询字符串和交给子路由的剩余部分
   fn consumed_length(path: &str) -> Result<usize, ParseError> {
-    // 默认实现：尝试解析并计算消费的长度
-    let _route = Self::parse(path)?;
-    // 这里需要具体的实现来计算实际消费的长度
-    // 暂时返回整个路径的长度
-    Ok(path.len())
+    // 默认实现：根据路由模式计算消费的长度，并确认消费的部分确实匹配模式；主模式不匹配时再尝试别名
+    let consumed_by = |pattern: &str| -> Result<usize, ParseError> {
+      let parser = crate::parser::PathParser::new(pattern)?;
+      let consumed = parser.consumed_length(path)?;
+      parser.match_path(&path[..consumed])?;
+      Ok(consumed)
+    };
+    let primary = consumed_by(Self::pattern());
+    if primary.is_err() {
+      if let Some(consumed) = Self::aliases().iter().find_map(|alias| consumed_by(alias).ok()) {
+        return Ok(consumed);
+      }
+    }
+    primary
   }
 
   /// 递归解析嵌套路由（自动化版本）
@@ -412,6 +924,58 @@ pub trait RouterData: Sized {
     Ok((result, remaining_path))
   }
 
+  /// 获取当前路由中子路由的路由信息
+  ///
+  /// `#[derive(RouterData)]` 生成的实现直接读取 `#[sub_router]` 字段；
+  /// 默认实现先格式化再通过 `parse_with_sub` 重新解析，格式化结果无法解析回来时返回 None
+  ///
+  /// # 返回值
+  ///
+  /// 存在子路由时返回其路由信息，否则返回 None
+  fn sub_route_info(&self) -> Option<Box<RouteInfo>> {
+    match Self::parse_with_sub(&self.format()) {
+      Ok((_, RouteState::SubRoute(sub))) => Some(Box::new(sub.to_route_info())),
+      _ => None,
+    }
+  }
+
+  /// 获取上一级页面的路径，用于面包屑和“返回上级”按钮
+  ///
+  /// 存在子路由时去掉最深一层子路由的路径；没有子路由时去掉当前路径的最后一段，
+  /// 只剩根路径时返回 None。结果不包含查询参数
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let route = UserModuleRoute::parse("/users/profile/basic/123")?;
+  /// assert_eq!(route.parent_path().as_deref(), Some("/users/profile"));
+  /// assert_eq!(UserRoute::parse("/users/123")?.parent_path().as_deref(), Some("/users"));
+  /// assert_eq!(AboutRoute::parse("/about")?.parent_path(), None);
+  /// ```
+  fn parent_path(&self) -> Option<String> {
+    let formatted = self.format();
+    let (path, _) = crate::utils::split_path_query(&formatted);
+
+    // 沿子路由信息找到最深的一层，它的格式化结果就是该层自身的路径
+    let mut deepest = self.sub_route_info();
+    while let Some(sub_info) = deepest.as_mut().and_then(|info| info.sub_route_info.take()) {
+      deepest = Some(sub_info);
+    }
+
+    if let Some(deepest) = deepest {
+      let (sub_path, _) = crate::utils::split_path_query(&deepest.formatted);
+      let parent = path.strip_suffix(sub_path).filter(|_| !sub_path.is_empty());
+      if let Some(parent) = parent.filter(|parent| !parent.is_empty()) {
+        return Some(parent.to_string());
+      }
+    }
+
+    match path.trim_end_matches('/').rsplit_once('/') {
+      Some((parent, _)) if !parent.is_empty() => Some(parent.to_string()),
+      _ => None,
+    }
+  }
+
   /// 获取查询参数的字段名称列表
   ///
   /// # 返回值
@@ -446,6 +1010,99 @@ pub trait Query: Sized {
   /// ```
   fn parse(query: &str) -> Result<Self, ParseError>;
 
+  /// 宽松地从查询字符串解析参数
+  ///
+  /// 与 [`Query::parse`] 不同，单个参数值无法解析时不会导致整体失败：
+  /// 有效的字段正常填充，无效或缺失的字段保留默认值，每个被忽略的值都会记录一条警告。
+  ///
+  /// 默认实现在严格解析失败时整体回退到 `Self::default()`，
+  /// 标注了 `#[query(lenient)]` 的 `QueryDerive` 实现会逐字段地处理。
+  ///
+  /// # 参数
+  ///
+  /// * `query` - 查询字符串，不包含前导的 '?'
+  ///
+  /// # 返回值
+  ///
+  /// 解析后的查询参数对象，以及被忽略参数的警告列表
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// let (params, warnings) = SearchParams::parse_lenient("q=rust&page=abc");
+  /// assert_eq!(params.q, Some("rust".to_string()));
+  /// assert_eq!(warnings[0].key, "page");
+  /// ```
+  fn parse_lenient(query: &str) -> (Self, Vec<QueryWarning>)
+  where
+    Self: Default,
+  {
+    match Self::parse(query) {
+      Ok(parsed) => (parsed, Vec::new()),
+      Err(err) => (Self::default(), vec![QueryWarning::new("", query, err.to_string())]),
+    }
+  }
+
+  /// 严格地从查询字符串解析参数，拒绝未知的参数名
+  ///
+  /// 先按 [`Query::parse`] 解析已知字段，再检查是否存在 [`Query::query_keys`] 之外的参数名，
+  /// 存在时返回列出这些参数名的 `ParseError::InvalidQuery`。例如 `?pag=2` 不会被当作缺少 `page`。
+  ///
+  /// 默认实现依赖 `query_keys` 返回完整的参数名，`QueryDerive` 生成的实现满足这一点。
+  ///
+  /// # 参数
+  ///
+  /// * `query` - 查询字符串，不包含前导的 '?'
+  ///
+  /// # 返回值
+  ///
+  /// 成功时返回解析后的查询参数对象，解析失败或存在未知参数时返回 ParseError
+  ///
+  /// # 示例
+  ///
+  /// ```rust,ignore
+  /// assert!(SearchParams::parse_strict("q=rust&page=2").is_ok());
+  /// assert!(SearchParams::parse_strict("q=rust&pag=2").is_err());
+  /// ```
+  fn parse_strict(query: &str) -> Result<Self, ParseError> {
+    let parsed = Self::parse(query)?;
+
+    let params = crate::utils::parse_query_string(query)?;
+    let mut unexpected = Self::unexpected_query_keys(&params);
+
+    if unexpected.is_empty() {
+      Ok(parsed)
+    } else {
+      unexpected.sort_unstable();
+      Err(ParseError::invalid_query(format!(
+        "Unexpected query parameters: {}",
+        unexpected.join(", ")
+      )))
+    }
+  }
+
+  /// 找出不属于当前类型的查询参数名（用于 [`Query::parse_strict`]）
+  ///
+  /// 默认实现返回不在 [`Query::query_keys`] 中的参数名；
+  /// 包含 `#[query(nested)]` 字段的 `QueryDerive` 实现会逐层检查分组内的参数名，
+  /// `#[query(flatten)]` 字段的参数名交给子类型检查。
+  ///
+  /// # 参数
+  ///
+  /// * `query_map` - 查询参数的键值对映射
+  ///
+  /// # 返回值
+  ///
+  /// 未知的参数名，顺序不固定
+  fn unexpected_query_keys(query_map: &std::collections::HashMap<String, Vec<String>>) -> Vec<String> {
+    let known_keys = Self::query_keys();
+    query_map
+      .keys()
+      .filter(|key| !known_keys.contains(&key.as_str()))
+      .cloned()
+      .collect()
+  }
+
   /// 将参数格式化为查询字符串
   ///
   /// # 返回值
@@ -486,9 +1143,50 @@ pub trait Query: Sized {
   fn query_keys() -> Vec<&'static str>;
 }
 
+/// 分页查询参数的 trait
+///
+/// 在 `QueryDerive` 结构体的 `Option<u32>` 字段上标注 `#[query(page)]` 即可自动实现，
+/// 缺少页码时视为第 1 页。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Clone, QueryDerive)]
+/// struct ListQuery {
+///   #[query(page)]
+///   page: Option<u32>,
+///   per_page: Option<u32>,
+/// }
+///
+/// let next_url = format!("/posts?{}", query.next_page().format());
+/// ```
+pub trait Paginated: Sized {
+  /// 当前页码，缺少页码时返回 None
+  fn page(&self) -> Option<u32>;
+
+  /// 返回页码设置为 `page` 的副本
+  fn with_page(&self, page: u32) -> Self;
+
+  /// 返回下一页，缺少页码时视为第 1 页
+  fn next_page(&self) -> Self {
+    self.with_page(self.page().unwrap_or(1).saturating_add(1))
+  }
+
+  /// 返回上一页，页码最小为 1
+  fn prev_page(&self) -> Self {
+    self.with_page(self.page().unwrap_or(1).saturating_sub(1).max(1))
+  }
+}
+
 /// 类型转换 trait，用于路径参数的类型转换
 ///
 /// 实现此 trait 的类型可以从字符串参数转换而来
+#[diagnostic::on_unimplemented(
+  message = "`{Self}` cannot be parsed from a route or query parameter",
+  label = "`{Self}` does not implement FromParam",
+  note = "query fields must be `Option<T>`, `Vec<T>`, or a type implementing FromParam; use #[query(with = \"...\")] for custom parsing",
+  note = "`Vec<T>` is not a parameter type; use `ruled_router::parser::CommaSeparated<T>` for a comma-separated list in a single parameter"
+)]
 pub trait FromParam: Sized {
   /// 从字符串参数转换为目标类型
   ///
@@ -511,6 +1209,55 @@ pub trait FromParam: Sized {
   fn from_param(param: &str) -> Result<Self, ParseError>;
 }
 
+/// 使用自定义错误类型的参数转换 trait
+///
+/// 稳定版 Rust 不支持关联类型默认值，因此 [`FromParam`] 保持返回 `ParseError`，
+/// 需要领域错误的类型改为实现此 trait：只要 `Error` 可以转换为 `ParseError`，
+/// 就会通过通用实现自动获得 `FromParam`，可以直接用于路由字段和查询参数。
+/// 同一个类型只能实现两者之一
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::error::ParseError;
+/// use ruled_router::traits::{FromParam, TryFromParam};
+///
+/// struct Even(u32);
+///
+/// struct OddNumber(u32);
+///
+/// impl From<OddNumber> for ParseError {
+///   fn from(err: OddNumber) -> Self {
+///     ParseError::type_conversion(format!("{} is odd", err.0))
+///   }
+/// }
+///
+/// impl TryFromParam for Even {
+///   type Error = OddNumber;
+///
+///   fn try_from_param(param: &str) -> Result<Self, OddNumber> {
+///     let value = param.parse().map_err(|_| OddNumber(1))?;
+///     if value % 2 == 0 { Ok(Even(value)) } else { Err(OddNumber(value)) }
+///   }
+/// }
+///
+/// assert_eq!(Even::from_param("4").unwrap().0, 4);
+/// assert_eq!(Even::from_param("3").err().unwrap().to_string(), "Type conversion error: 3 is odd");
+/// ```
+pub trait TryFromParam: Sized {
+  /// 转换失败时的错误类型
+  type Error: Into<ParseError>;
+
+  /// 从字符串参数转换为目标类型，失败时返回自定义错误
+  fn try_from_param(param: &str) -> Result<Self, Self::Error>;
+}
+
+impl<T: TryFromParam> FromParam for T {
+  fn from_param(param: &str) -> Result<Self, ParseError> {
+    T::try_from_param(param).map_err(Into::into)
+  }
+}
+
 /// 类型格式化 trait，用于将参数转换为字符串
 ///
 /// 实现此 trait 的类型可以转换为字符串用于 URL 路径
@@ -3,11 +3,14 @@
 //! 提供 URL 编码/解码、路径分离等基础功能
 
 use crate::error::{ParseError, ParseResult};
+use crate::traits::{Query, RouterData};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// URL 编码函数
 ///
-/// 将字符串进行 URL 编码，遵循 RFC 3986 标准
+/// 将字符串进行 URL 编码，遵循 RFC 3986 标准，只保留 unreserved 字符，
+/// 等同于 `url_encode_with(input, &EncodeSet::STRICT)`
 ///
 /// # 参数
 ///
@@ -26,26 +29,76 @@ use std::collections::HashMap;
 /// assert_eq!(encoded, "hello%20world");
 /// ```
 pub fn url_encode(input: &str) -> String {
-  input
-    .chars()
-    .map(|c| match c {
-      // 不需要编码的字符（RFC 3986 unreserved characters）
-      'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => c.to_string(),
-      // 空格编码为 %20（而不是 +，这是 application/x-www-form-urlencoded 的规则）
-      ' ' => "%20".to_string(),
-      // 其他字符进行百分号编码
-      _ => {
-        let mut buf = [0; 4];
-        let encoded = c.encode_utf8(&mut buf);
-        encoded.bytes().map(|b| format!("%{b:02X}")).collect::<String>()
+  url_encode_with(input, &EncodeSet::STRICT)
+}
+
+/// 百分号编码时保持原样的字符集合
+///
+/// RFC 3986 unreserved 字符（`A-Z a-z 0-9 - _ . ~`）总是保持原样，集合只描述额外允许的字符。
+/// 路径段和查询值的安全字符不同：例如通配符的值包含多个段，其中的 `/` 必须保留，
+/// 而普通路径段中的 `/` 必须编码，否则会改变段的划分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeSet<'a> {
+  /// 除 unreserved 字符外额外保持原样的字符
+  allowed: &'a str,
+}
+
+impl<'a> EncodeSet<'a> {
+  /// 只保留 unreserved 字符，适合单个路径段和查询参数的键值
+  pub const STRICT: EncodeSet<'static> = EncodeSet { allowed: "" };
+
+  /// 额外保留 `/`，适合包含多个段的路径值（例如通配符参数）
+  pub const PATH: EncodeSet<'static> = EncodeSet { allowed: "/" };
+
+  /// 额外保留查询部分中没有特殊含义的 `/`、`?`、`:`、`@`；`&`、`=`、`+`、`#` 仍然编码
+  pub const QUERY: EncodeSet<'static> = EncodeSet { allowed: "/?:@" };
+
+  /// 自定义额外保持原样的字符
+  ///
+  /// `%` 总是会被编码，否则编码结果无法正确解码
+  pub const fn custom(allowed: &'a str) -> Self {
+    EncodeSet { allowed }
+  }
+
+  /// 字符是否保持原样
+  pub fn allows(&self, c: char) -> bool {
+    matches!(c, 'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~') || (c != '%' && self.allowed.contains(c))
+  }
+}
+
+/// 使用指定字符集合的 URL 编码函数
+///
+/// 集合允许的字符保持原样，其余字符按 UTF-8 字节进行百分号编码，空格编码为 `%20`
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::{url_encode_with, EncodeSet};
+///
+/// assert_eq!(url_encode_with("docs/a b.md", &EncodeSet::PATH), "docs/a%20b.md");
+/// assert_eq!(url_encode_with("docs/a b.md", &EncodeSet::STRICT), "docs%2Fa%20b.md");
+/// assert_eq!(url_encode_with("a,b", &EncodeSet::custom(",")), "a,b");
+/// ```
+pub fn url_encode_with(input: &str, set: &EncodeSet) -> String {
+  let mut encoded = String::with_capacity(input.len());
+  for c in input.chars() {
+    if set.allows(c) {
+      encoded.push(c);
+    } else {
+      // 空格同样编码为 %20（而不是 +，这是 application/x-www-form-urlencoded 的规则）
+      let mut buf = [0; 4];
+      for b in c.encode_utf8(&mut buf).bytes() {
+        encoded.push_str(&format!("%{b:02X}"));
       }
-    })
-    .collect()
+    }
+  }
+  encoded
 }
 
 /// URL 解码函数
 ///
-/// 将 URL 编码的字符串解码为原始字符串
+/// 将 URL 编码的字符串解码为原始字符串，等同于 [`url_decode_query`]，
+/// 即把 `+` 视为空格。解码路径段时应使用 [`url_decode_path`]。
 ///
 /// # 参数
 ///
@@ -64,6 +117,60 @@ pub fn url_encode(input: &str) -> String {
 /// assert_eq!(decoded, "hello world");
 /// ```
 pub fn url_decode(input: &str) -> ParseResult<String> {
+  url_decode_query(input)
+}
+
+/// 解码路径段
+///
+/// 只处理百分号编码，`+` 按字面保留，例如 `c++` 解码后仍是 `c++`
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::url_decode_path;
+///
+/// assert_eq!(url_decode_path("c++").unwrap(), "c++");
+/// assert_eq!(url_decode_path("hello%20world").unwrap(), "hello world");
+/// ```
+pub fn url_decode_path(input: &str) -> ParseResult<String> {
+  percent_decode(input, false)
+}
+
+/// 解码查询参数的键或值
+///
+/// 遵循 application/x-www-form-urlencoded 的规则，`+` 和 `%20` 都解码为空格
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::url_decode_query;
+///
+/// assert_eq!(url_decode_query("a+b").unwrap(), "a b");
+/// assert_eq!(url_decode_query("c%2B%2B").unwrap(), "c++");
+/// ```
+pub fn url_decode_query(input: &str) -> ParseResult<String> {
+  percent_decode(input, true)
+}
+
+/// 把原始字节形式的请求目标转换为字符串
+///
+/// 底层 HTTP 服务器给出的请求目标是 `&[u8]`，百分号编码之前也可能不是合法的 UTF-8。
+/// 这里只做校验，不做解码，非法 UTF-8 返回 `UrlEncoding` 错误
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::path_from_bytes;
+///
+/// assert_eq!(path_from_bytes(b"/users/42").unwrap(), "/users/42");
+/// assert!(path_from_bytes(b"/users/\xFF").is_err());
+/// ```
+pub fn path_from_bytes(bytes: &[u8]) -> ParseResult<&str> {
+  std::str::from_utf8(bytes).map_err(|err| ParseError::url_encoding(format!("Invalid UTF-8 in path at byte {}", err.valid_up_to())))
+}
+
+/// 百分号解码的共同实现，`plus_as_space` 决定是否把 `+` 解码为空格
+fn percent_decode(input: &str, plus_as_space: bool) -> ParseResult<String> {
   let mut result = Vec::new();
   let mut chars = input.chars().peekable();
 
@@ -78,13 +185,17 @@ pub fn url_decode(input: &str) -> ParseResult<String> {
           .next()
           .ok_or_else(|| ParseError::url_encoding("Incomplete percent encoding"))?;
 
+        // from_str_radix 接受 "+1" 这样的符号前缀，因此先逐个检查十六进制字符
         let hex_str = format!("{hex1}{hex2}");
+        if !hex1.is_ascii_hexdigit() || !hex2.is_ascii_hexdigit() {
+          return Err(ParseError::url_encoding(format!("Invalid hex in percent encoding: {hex_str}")));
+        }
         let byte = u8::from_str_radix(&hex_str, 16)
           .map_err(|_| ParseError::url_encoding(format!("Invalid hex in percent encoding: {hex_str}")))?;
 
         result.push(byte);
       }
-      '+' => {
+      '+' if plus_as_space => {
         // 在查询参数中，+ 表示空格
         result.push(b' ');
       }
@@ -100,9 +211,42 @@ pub fn url_decode(input: &str) -> ParseResult<String> {
   String::from_utf8(result).map_err(|_| ParseError::url_encoding("Invalid UTF-8 sequence after URL decoding"))
 }
 
+/// 分离路径、查询参数和片段
+///
+/// 先去掉第一个 '#' 之后的片段，再按第一个 '?' 分离路径和查询参数。
+/// 已编码的 `%23` 不会被当作片段分隔符。
+///
+/// # 参数
+///
+/// * `url` - 完整的 URL 路径字符串
+///
+/// # 返回值
+///
+/// 返回元组 (路径部分, 查询参数部分, 片段部分)，不存在的部分为 None
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::split_url;
+///
+/// assert_eq!(split_url("/page?tab=1#section"), ("/page", Some("tab=1"), Some("section")));
+/// assert_eq!(split_url("/page#section"), ("/page", None, Some("section")));
+/// assert_eq!(split_url("/page?tab=1"), ("/page", Some("tab=1"), None));
+/// ```
+pub fn split_url(url: &str) -> (&str, Option<&str>, Option<&str>) {
+  let (rest, fragment) = match url.split_once('#') {
+    Some((rest, fragment)) => (rest, Some(fragment)),
+    None => (url, None),
+  };
+  match rest.split_once('?') {
+    Some((path, query)) => (path, Some(query), fragment),
+    None => (rest, None, fragment),
+  }
+}
+
 /// 分离路径和查询参数
 ///
-/// 将完整的 URL 路径分离为路径部分和查询参数部分
+/// 将完整的 URL 路径分离为路径部分和查询参数部分，片段（`#...`）会被丢弃，见 [`split_url`]
 ///
 /// # 参数
 ///
@@ -126,12 +270,31 @@ pub fn url_decode(input: &str) -> ParseResult<String> {
 /// assert_eq!(query, None);
 /// ```
 pub fn split_path_query(url: &str) -> (&str, Option<&str>) {
-  if let Some(question_pos) = url.find('?') {
-    let path = &url[..question_pos];
-    let query = &url[question_pos + 1..];
-    (path, Some(query))
+  let (path, query, _) = split_url(url);
+  (path, query)
+}
+
+/// 把空的路径部分规范化为根路径 "/"
+///
+/// 只有查询参数或片段的 URL（如 `"?page=1"`）以及空字符串都视为根路径，
+/// 因此会与 `"/"` 模式匹配；路径部分非空时原样返回，不分配新字符串。
+/// `#[derive(RouterData)]` 生成的解析方法在匹配前调用此函数
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::normalize_empty_path;
+///
+/// assert_eq!(normalize_empty_path(""), "/");
+/// assert_eq!(normalize_empty_path("?page=1"), "/?page=1");
+/// assert_eq!(normalize_empty_path("#top"), "/#top");
+/// assert_eq!(normalize_empty_path("/users?page=1"), "/users?page=1");
+/// ```
+pub fn normalize_empty_path(path: &str) -> Cow<'_, str> {
+  if split_url(path).0.is_empty() {
+    Cow::Owned(format!("/{path}"))
   } else {
-    (url, None)
+    Cow::Borrowed(path)
   }
 }
 
@@ -162,10 +325,40 @@ pub fn split_path_segments(path: &str) -> Vec<&str> {
   path.trim_start_matches('/').split('/').filter(|s| !s.is_empty()).collect()
 }
 
+/// 将路径分割为段，保留空段
+///
+/// 只去掉一个前导 '/'，重复的斜杠和末尾斜杠都会产生空段，
+/// 用于需要区分 `/a//b` 和 `/a/b` 的严格匹配。根路径和空字符串返回空向量
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::split_path_segments_raw;
+///
+/// assert_eq!(split_path_segments_raw("/a//b"), vec!["a", "", "b"]);
+/// assert_eq!(split_path_segments_raw("/a/b/"), vec!["a", "b", ""]);
+/// assert_eq!(split_path_segments_raw("/"), Vec::<&str>::new());
+/// ```
+pub fn split_path_segments_raw(path: &str) -> Vec<&str> {
+  let path = path.strip_prefix('/').unwrap_or(path);
+  if path.is_empty() {
+    Vec::new()
+  } else {
+    path.split('/').collect()
+  }
+}
+
 /// 解析查询字符串为参数映射
 ///
 /// 将查询字符串解析为键值对映射，支持多值参数
 ///
+/// 畸形输入按以下规则处理：
+/// - 只在第一个 `=` 处分割，`a==b` 得到键 `a`、值 `=b`
+/// - 空的参数对会被跳过，因此 `&&a=1&&` 只包含 `a`
+/// - 没有 `=` 的参数值为空字符串，`flag` 得到键 `flag`、值 `""`
+/// - 解码后键名为空的参数对（例如单独的 `=` 或 `=x`）会被跳过
+/// - 百分号编码不完整或解码结果不是合法 UTF-8 时返回 `UrlEncoding` 错误
+///
 /// # 参数
 ///
 /// * `query` - 查询字符串，不包含前导的 '?'
@@ -185,30 +378,137 @@ pub fn split_path_segments(path: &str) -> Vec<&str> {
 /// assert_eq!(params.get("tags"), Some(&vec!["web".to_string(), "backend".to_string()]));
 /// ```
 pub fn parse_query_string(query: &str) -> ParseResult<HashMap<String, Vec<String>>> {
+  parse_query_string_with_sep(query, &['&'])
+}
+
+/// 使用自定义分隔符解析查询字符串为参数映射
+///
+/// 一些旧系统按早期 W3C 建议用 `;` 代替 `&` 分隔参数对，传入 `&['&', ';']` 即可同时接受两者。
+/// 解码和畸形输入的处理规则与 [`parse_query_string`] 相同；格式化始终使用 `&`
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::parse_query_string_with_sep;
+///
+/// let params = parse_query_string_with_sep("q=rust;page=2&tags=web", &['&', ';']).unwrap();
+/// assert_eq!(params.get("page"), Some(&vec!["2".to_string()]));
+/// assert_eq!(params.get("tags"), Some(&vec!["web".to_string()]));
+/// ```
+pub fn parse_query_string_with_sep(query: &str, sep: &[char]) -> ParseResult<HashMap<String, Vec<String>>> {
   let mut params = HashMap::new();
 
-  if query.is_empty() {
-    return Ok(params);
+  for (key, value) in parse_query_pairs_with_sep(query, sep)? {
+    params.entry(key).or_insert_with(Vec::new).push(value);
   }
 
-  for pair in query.split('&') {
+  Ok(params)
+}
+
+/// 按出现顺序解析查询字符串为键值对列表
+///
+/// 与 [`parse_query_string`] 使用相同的解码和畸形输入处理规则，但保留参数的原始顺序
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::parse_query_pairs;
+///
+/// let pairs = parse_query_pairs("b=2&a=1&b=3").unwrap();
+/// assert_eq!(pairs[0], ("b".to_string(), "2".to_string()));
+/// assert_eq!(pairs.len(), 3);
+/// ```
+pub fn parse_query_pairs(query: &str) -> ParseResult<Vec<(String, String)>> {
+  parse_query_pairs_with_sep(query, &['&'])
+}
+
+/// 使用自定义分隔符按出现顺序解析查询字符串为键值对列表
+///
+/// 参见 [`parse_query_string_with_sep`]
+pub fn parse_query_pairs_with_sep(query: &str, sep: &[char]) -> ParseResult<Vec<(String, String)>> {
+  let mut pairs = Vec::new();
+
+  for pair in query.split(sep) {
     if pair.is_empty() {
       continue;
     }
 
-    let (key, value) = if let Some(eq_pos) = pair.find('=') {
-      let key = &pair[..eq_pos];
-      let value = &pair[eq_pos + 1..];
-      (url_decode(key)?, url_decode(value)?)
-    } else {
-      // 没有等号的参数，值为空字符串
-      (url_decode(pair)?, String::new())
+    // 只在第一个等号处分割，值中可以包含等号；没有等号的参数，值为空字符串
+    let (key, value) = match pair.split_once('=') {
+      Some((key, value)) => (url_decode_query(key)?, url_decode_query(value)?),
+      None => (url_decode_query(pair)?, String::new()),
     };
 
-    params.entry(key).or_insert_with(Vec::new).push(value);
+    // 键名为空的参数对无法对应任何字段，直接跳过
+    if key.is_empty() {
+      continue;
+    }
+
+    pairs.push((key, value));
   }
 
-  Ok(params)
+  Ok(pairs)
+}
+
+/// 把子参数名放入分组，生成 `group[key]` 形式的参数名
+///
+/// 已经带有方括号的子参数名只包裹第一段，因此可以逐层嵌套
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::nest_query_key;
+///
+/// assert_eq!(nest_query_key("page", "size"), "page[size]");
+/// assert_eq!(nest_query_key("filter", "price[min]"), "filter[price][min]");
+/// ```
+pub fn nest_query_key(group: &str, key: &str) -> String {
+  match key.find('[') {
+    Some(bracket_pos) => format!("{group}[{}]{}", &key[..bracket_pos], &key[bracket_pos..]),
+    None => format!("{group}[{key}]"),
+  }
+}
+
+/// 从 `group[key]` 形式的参数名中取出分组内的参数名
+///
+/// 是 [`nest_query_key`] 的逆操作，参数名不属于该分组或格式不正确时返回 None
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::nested_query_key;
+///
+/// assert_eq!(nested_query_key("page[size]", "page"), Some("size".to_string()));
+/// assert_eq!(nested_query_key("filter[price][min]", "filter"), Some("price[min]".to_string()));
+/// assert_eq!(nested_query_key("page", "page"), None);
+/// ```
+pub fn nested_query_key(key: &str, group: &str) -> Option<String> {
+  let inner = key.strip_prefix(group)?.strip_prefix('[')?;
+  let (head, rest) = inner.split_once(']')?;
+  if head.is_empty() {
+    None
+  } else {
+    Some(format!("{head}{rest}"))
+  }
+}
+
+/// 取出属于某个分组的所有参数，参数名去掉分组前缀
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::{nested_query_map, parse_query_string};
+///
+/// let params = parse_query_string("page[size]=20&page[number]=2&q=rust").unwrap();
+/// let page = nested_query_map(&params, "page");
+/// assert_eq!(page.get("size"), Some(&vec!["20".to_string()]));
+/// assert_eq!(page.len(), 2);
+/// ```
+pub fn nested_query_map(query_map: &HashMap<String, Vec<String>>, group: &str) -> HashMap<String, Vec<String>> {
+  query_map
+    .iter()
+    .filter_map(|(key, values)| nested_query_key(key, group).map(|inner| (inner, values.clone())))
+    .collect()
 }
 
 /// 将参数映射格式化为查询字符串
@@ -240,21 +540,81 @@ pub fn parse_query_string(query: &str) -> ParseResult<HashMap<String, Vec<String
 /// assert!(query.contains("tags=backend"));
 /// ```
 pub fn format_query_string(params: &HashMap<String, Vec<String>>) -> String {
+  format_query_entries(params.iter())
+}
+
+/// 按键排序格式化查询字符串
+///
+/// 与 [`format_query_string`] 相同，但按键名排序输出，同一个键的多个值保持原有顺序，
+/// 适用于缓存键、快照测试等需要确定性输出的场景
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::format_query_string_sorted;
+/// use std::collections::HashMap;
+///
+/// let mut params = HashMap::new();
+/// params.insert("q".to_string(), vec!["rust".to_string()]);
+/// params.insert("tags".to_string(), vec!["web".to_string(), "backend".to_string()]);
+/// params.insert("page".to_string(), vec!["2".to_string()]);
+///
+/// let query = format_query_string_sorted(&params);
+/// assert_eq!(query, "page=2&q=rust&tags=web&tags=backend");
+/// ```
+pub fn format_query_string_sorted(params: &HashMap<String, Vec<String>>) -> String {
+  let mut entries: Vec<_> = params.iter().collect();
+  entries.sort_by_key(|(key, _)| *key);
+  format_query_entries(entries.into_iter())
+}
+
+/// 按给定顺序格式化键值对列表
+///
+/// 输出顺序与输入顺序完全一致，同一个键可以出现多次
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::format_query_pairs;
+///
+/// let pairs = vec![
+///   ("q".to_string(), "rust".to_string()),
+///   ("page".to_string(), "1".to_string()),
+///   ("tags".to_string(), "web".to_string()),
+///   ("tags".to_string(), "api".to_string()),
+/// ];
+/// assert_eq!(format_query_pairs(&pairs), "q=rust&page=1&tags=web&tags=api");
+/// ```
+pub fn format_query_pairs(pairs: &[(String, String)]) -> String {
+  pairs
+    .iter()
+    .map(|(key, value)| encode_query_pair(key, value))
+    .collect::<Vec<_>>()
+    .join("&")
+}
+
+/// 将键值对依次编码并用 '&' 连接
+fn format_query_entries<'a>(entries: impl Iterator<Item = (&'a String, &'a Vec<String>)>) -> String {
   let mut parts = Vec::new();
 
-  for (key, values) in params {
+  for (key, values) in entries {
     for value in values {
-      if value.is_empty() {
-        parts.push(url_encode(key));
-      } else {
-        parts.push(format!("{}={}", url_encode(key), url_encode(value)));
-      }
+      parts.push(encode_query_pair(key, value));
     }
   }
 
   parts.join("&")
 }
 
+/// 编码单个键值对，空值只输出键名
+fn encode_query_pair(key: &str, value: &str) -> String {
+  if value.is_empty() {
+    url_encode(key)
+  } else {
+    format!("{}={}", url_encode(key), url_encode(value))
+  }
+}
+
 /// 规范化路径
 ///
 /// 移除路径中的多余斜杠，确保路径格式一致
@@ -289,10 +649,334 @@ pub fn normalize_path(path: &str) -> String {
   }
 }
 
+/// 检查路径是否已经是规范形式
+///
+/// 规范形式即 [`normalize_path`] 的输出：以 '/' 开头，没有重复斜杠，除根路径外没有末尾斜杠。
+/// 路径已是规范形式时借用返回原值，不分配内存；否则返回规范化后的路径，便于 web 层发起重定向。
+/// 参数只应包含路径部分，不含查询字符串。
+///
+/// # 参数
+///
+/// * `path` - 原始路径
+///
+/// # 返回值
+///
+/// 已规范时返回 `Ok(path)`，否则返回 `Err(规范路径)`
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::canonicalize_path;
+///
+/// assert_eq!(canonicalize_path("/users/1"), Ok("/users/1"));
+/// assert_eq!(canonicalize_path("//users//1"), Err("/users/1".to_string()));
+/// ```
+pub fn canonicalize_path(path: &str) -> Result<&str, String> {
+  let is_canonical = path == "/" || (path.starts_with('/') && !path.ends_with('/') && !path.contains("//"));
+  if is_canonical {
+    Ok(path)
+  } else {
+    Err(normalize_path(path))
+  }
+}
+
+/// 把路径（可带查询字符串）拼接到基础 URL 上
+///
+/// 基础 URL 的路径作为前缀保留（末尾的 '/' 会被合并），基础 URL 已有的查询参数排在前面，
+/// 片段（`#...`）保留在结果末尾。
+///
+/// # 参数
+///
+/// * `base` - 基础 URL，例如 `https://example.com/app/`
+/// * `path` - 以 '/' 开头的路径，可以包含查询字符串
+///
+/// # 返回值
+///
+/// 拼接后的绝对 URL
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::join_url;
+///
+/// assert_eq!(join_url("https://example.com", "/users/1?page=2"), "https://example.com/users/1?page=2");
+/// assert_eq!(join_url("https://example.com/app/", "/users/1"), "https://example.com/app/users/1");
+/// assert_eq!(join_url("https://example.com/?lang=en", "/users/1?page=2"), "https://example.com/users/1?lang=en&page=2");
+/// ```
+pub fn join_url(base: &str, path: &str) -> String {
+  let (base_path, base_query, fragment) = split_url(base);
+  let (route_path, route_query) = split_path_query(path);
+
+  let mut url = String::with_capacity(base.len() + path.len() + 1);
+  url.push_str(base_path.trim_end_matches('/'));
+  if !route_path.starts_with('/') {
+    url.push('/');
+  }
+  url.push_str(route_path);
+
+  let queries: Vec<&str> = [base_query, route_query]
+    .into_iter()
+    .flatten()
+    .filter(|query| !query.is_empty())
+    .collect();
+  if !queries.is_empty() {
+    url.push('?');
+    url.push_str(&queries.join("&"));
+  }
+
+  if let Some(fragment) = fragment {
+    url.push('#');
+    url.push_str(fragment);
+  }
+
+  url
+}
+
+/// 在 URL 上追加查询参数
+///
+/// 已有查询参数时用 '&' 连接，否则先加 '?'；键和值都经过 [`url_encode`] 编码，片段保持在最后
+///
+/// # 参数
+///
+/// * `url` - 原始 URL，可以带查询参数和片段
+/// * `extra` - 要追加的键值对
+///
+/// # 返回值
+///
+/// 追加参数后的 URL
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::append_query_pairs;
+///
+/// assert_eq!(append_query_pairs("/users/1", &[("ref", "email")]), "/users/1?ref=email");
+/// assert_eq!(append_query_pairs("/search?q=rust", &[("ref", "a b")]), "/search?q=rust&ref=a%20b");
+/// ```
+pub fn append_query_pairs(url: &str, extra: &[(&str, &str)]) -> String {
+  let extra_query = extra
+    .iter()
+    .map(|(key, value)| encode_query_pair(key, value))
+    .collect::<Vec<_>>()
+    .join("&");
+  append_raw_query(url, &extra_query)
+}
+
+/// 把某一层路由格式化后的 URL 分解为单行描述
+///
+/// `formatted` 是当前层级 `format()` 的结果（包含子路由路径和查询），`sub_formatted` 是子路由
+/// `format()` 的结果。子路由路径是 `formatted` 路径部分的后缀，去掉后得到当前层级自身的路径。
+/// 供 [`RouteMatcher::format_pretty`](crate::traits::RouteMatcher::format_pretty) 使用
+///
+/// # 示例
+///
+/// ```rust
+/// use ruled_router::utils::format_pretty_url;
+///
+/// assert_eq!(
+///   format_pretty_url("/users/123/profile/basic?page=1", Some("/profile/basic")),
+///   "path: /users/123 | query: page=1 | sub: /profile/basic"
+/// );
+/// assert_eq!(format_pretty_url("/about", None), "path: /about");
+/// ```
+pub fn format_pretty_url(formatted: &str, sub_formatted: Option<&str>) -> String {
+  let (full_path, query) = split_path_query(formatted);
+  let sub_path = sub_formatted
+    .map(|sub| split_path_query(sub).0)
+    .filter(|sub_path| !sub_path.is_empty());
+  let own_path = match sub_path {
+    Some(sub_path) => full_path.strip_suffix(sub_path).unwrap_or(full_path),
+    None => full_path,
+  };
+
+  let mut result = format!("path: {}", if own_path.is_empty() { "/" } else { own_path });
+  if let Some(query) = query.filter(|query| !query.is_empty()) {
+    result.push_str(" | query: ");
+    result.push_str(query);
+  }
+  if let Some(sub_path) = sub_path {
+    result.push_str(" | sub: ");
+    result.push_str(sub_path);
+  }
+  result
+}
+
+/// 把路由和查询参数组合为完整 URL
+///
+/// 查询参数格式化为空时不追加 '?'；路由自身已经带有查询参数时用 '&' 连接
+///
+/// # 参数
+///
+/// * `route` - 路由
+/// * `query` - 查询参数
+///
+/// # 返回值
+///
+/// 完整的 URL
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// use ruled_router::build_url;
+///
+/// let url = build_url(&UserRoute { id: 1 }, &SearchQuery { q: Some("rust".to_string()) });
+/// assert_eq!(url, "/users/1?q=rust");
+/// ```
+pub fn build_url<R: RouterData, Q: Query>(route: &R, query: &Q) -> String {
+  append_raw_query(&route.format(), &query.format())
+}
+
+/// 把完整 URL 解析为路由和查询参数
+///
+/// 按 '?' 拆分后，路径部分交给 `R::parse`，查询部分交给 `Q::parse`，片段被忽略。
+/// 路由自身的 `#[query]` 字段只能看到路径部分，因此会得到默认值。
+///
+/// # 参数
+///
+/// * `url` - 完整 URL
+///
+/// # 返回值
+///
+/// 成功时返回路由和查询参数，任一部分解析失败时返回 ParseError
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// use ruled_router::parse_url;
+///
+/// let (route, query): (UserRoute, UserQuery) = parse_url("/users/1?tab=posts")?;
+/// assert_eq!(route.id, 1);
+/// assert_eq!(query.tab.as_deref(), Some("posts"));
+/// ```
+pub fn parse_url<R: RouterData, Q: Query>(url: &str) -> Result<(R, Q), ParseError> {
+  let (path, query, _) = split_url(url);
+  Ok((R::parse(path)?, Q::parse(query.unwrap_or(""))?))
+}
+
+/// 在 URL 上追加已编码的查询字符串，片段保持在最后
+fn append_raw_query(url: &str, extra_query: &str) -> String {
+  if extra_query.is_empty() {
+    return url.to_string();
+  }
+
+  let (path, query, fragment) = split_url(url);
+  let mut result = String::with_capacity(url.len() + extra_query.len() + 2);
+  result.push_str(path);
+  result.push('?');
+  if let Some(query) = query.filter(|query| !query.is_empty()) {
+    result.push_str(query);
+    result.push('&');
+  }
+  result.push_str(extra_query);
+
+  if let Some(fragment) = fragment {
+    result.push('#');
+    result.push_str(fragment);
+  }
+
+  result
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_nested_query_keys() {
+    assert_eq!(nest_query_key("page", "size"), "page[size]");
+    assert_eq!(nest_query_key("filter", "price[min]"), "filter[price][min]");
+
+    assert_eq!(nested_query_key("page[size]", "page"), Some("size".to_string()));
+    assert_eq!(nested_query_key("filter[price][min]", "filter"), Some("price[min]".to_string()));
+    assert_eq!(nested_query_key("pages[size]", "page"), None);
+    assert_eq!(nested_query_key("page[]", "page"), None);
+    assert_eq!(nested_query_key("page[size", "page"), None);
+
+    // 两个方向互为逆操作
+    let key = nest_query_key("filter", &nest_query_key("price", "min"));
+    assert_eq!(key, "filter[price][min]");
+    assert_eq!(nested_query_key(&key, "filter").as_deref(), Some("price[min]"));
+  }
+
+  #[test]
+  fn test_nested_query_map() {
+    let params = parse_query_string("filter[status]=active&filter[status]=pending&filter[price][min]=10&page=1").unwrap();
+    let filter = nested_query_map(&params, "filter");
+    assert_eq!(filter.len(), 2);
+    assert_eq!(filter.get("status"), Some(&vec!["active".to_string(), "pending".to_string()]));
+
+    let price = nested_query_map(&filter, "price");
+    assert_eq!(price.get("min"), Some(&vec!["10".to_string()]));
+
+    // 编码后的方括号同样可以识别
+    let params = parse_query_string("page%5Bsize%5D=20").unwrap();
+    assert_eq!(nested_query_map(&params, "page").get("size"), Some(&vec!["20".to_string()]));
+  }
+
+  #[test]
+  fn test_canonicalize_path() {
+    // 已经规范的路径原样借用返回
+    assert_eq!(canonicalize_path("/"), Ok("/"));
+    assert_eq!(canonicalize_path("/users/1"), Ok("/users/1"));
+
+    // 重复斜杠
+    assert_eq!(canonicalize_path("//users//1"), Err("/users/1".to_string()));
+    assert_eq!(canonicalize_path("//"), Err("/".to_string()));
+
+    // 末尾斜杠
+    assert_eq!(canonicalize_path("/users/1/"), Err("/users/1".to_string()));
+
+    // 缺少前导斜杠或为空
+    assert_eq!(canonicalize_path("users/1"), Err("/users/1".to_string()));
+    assert_eq!(canonicalize_path(""), Err("/".to_string()));
+
+    // 结果与 normalize_path 一致
+    for path in ["/", "/a", "/a/b", "a//b/", "///", "/a/b//"] {
+      let canonical = canonicalize_path(path).map(str::to_string).unwrap_or_else(|canonical| canonical);
+      assert_eq!(canonical, normalize_path(path), "path '{path}'");
+    }
+  }
+
+  #[test]
+  fn test_append_query_pairs() {
+    assert_eq!(append_query_pairs("/users/1", &[]), "/users/1");
+    assert_eq!(append_query_pairs("/users/1", &[("ref", "email")]), "/users/1?ref=email");
+    assert_eq!(
+      append_query_pairs("/search?q=rust", &[("ref", "email"), ("utm source", "a&b")]),
+      "/search?q=rust&ref=email&utm%20source=a%26b"
+    );
+    // 空查询字符串和片段
+    assert_eq!(append_query_pairs("/search?", &[("ref", "x")]), "/search?ref=x");
+    assert_eq!(
+      append_query_pairs("/docs?page=2#intro", &[("ref", "x")]),
+      "/docs?page=2&ref=x#intro"
+    );
+  }
+
+  #[test]
+  fn test_join_url() {
+    // 没有路径的基础 URL
+    assert_eq!(join_url("https://example.com", "/users/123"), "https://example.com/users/123");
+    assert_eq!(join_url("https://example.com/", "/users/123"), "https://example.com/users/123");
+
+    // 带路径的基础 URL
+    assert_eq!(
+      join_url("https://example.com/app", "/users/123"),
+      "https://example.com/app/users/123"
+    );
+    assert_eq!(
+      join_url("https://example.com/app/", "/users/123?page=2"),
+      "https://example.com/app/users/123?page=2"
+    );
+
+    // 合并已有的查询参数并保留片段
+    assert_eq!(
+      join_url("https://example.com/app?lang=en#top", "/users/123?page=2"),
+      "https://example.com/app/users/123?lang=en&page=2#top"
+    );
+    assert_eq!(join_url("https://example.com?", "/"), "https://example.com/");
+  }
+
   #[test]
   fn test_url_encode() {
     assert_eq!(url_encode("hello world"), "hello%20world");
@@ -301,6 +985,19 @@ mod tests {
     assert_eq!(url_encode("中文"), "%E4%B8%AD%E6%96%87");
   }
 
+  #[test]
+  fn test_url_encode_with() {
+    let value = "a b/c&d=e?f:g@h#i%";
+    assert_eq!(url_encode_with(value, &EncodeSet::STRICT), url_encode(value));
+    assert_eq!(url_encode_with(value, &EncodeSet::PATH), "a%20b/c%26d%3De%3Ff%3Ag%40h%23i%25");
+    assert_eq!(url_encode_with(value, &EncodeSet::QUERY), "a%20b/c%26d%3De?f:g@h%23i%25");
+
+    // 自定义集合中的 '%' 仍然编码，保证可以解码回原值
+    let set = EncodeSet::custom(",%");
+    assert_eq!(url_encode_with("1,2%", &set), "1,2%25");
+    assert_eq!(url_decode(&url_encode_with(value, &EncodeSet::QUERY)).unwrap(), value);
+  }
+
   #[test]
   fn test_url_decode() {
     assert_eq!(url_decode("hello%20world").unwrap(), "hello world");
@@ -314,6 +1011,26 @@ mod tests {
     assert!(url_decode("%1").is_err());
   }
 
+  #[test]
+  fn test_url_decode_path_keeps_plus() {
+    assert_eq!(url_decode_path("c++").unwrap(), "c++");
+    assert_eq!(url_decode_path("c%2B%2B").unwrap(), "c++");
+    assert_eq!(url_decode_path("a%20b").unwrap(), "a b");
+    assert!(url_decode_path("%ZZ").is_err());
+
+    assert_eq!(url_decode_query("a+b").unwrap(), "a b");
+    assert_eq!(url_decode_query("c%2B%2B").unwrap(), "c++");
+  }
+
+  #[test]
+  fn test_normalize_empty_path() {
+    assert_eq!(normalize_empty_path(""), "/");
+    assert_eq!(normalize_empty_path("?page=1&q=a"), "/?page=1&q=a");
+    // 路径部分非空时借用原字符串
+    assert!(matches!(normalize_empty_path("/"), Cow::Borrowed("/")));
+    assert!(matches!(normalize_empty_path("users?page=1"), Cow::Borrowed(_)));
+  }
+
   #[test]
   fn test_split_path_query() {
     let (path, query) = split_path_query("/user/123?tab=profile");
@@ -327,6 +1044,28 @@ mod tests {
     let (path, query) = split_path_query("/?empty");
     assert_eq!(path, "/");
     assert_eq!(query, Some("empty"));
+
+    // 片段被丢弃，不会混入查询参数
+    assert_eq!(split_path_query("/page?tab=1#section"), ("/page", Some("tab=1")));
+    assert_eq!(split_path_query("/page#section"), ("/page", None));
+  }
+
+  #[test]
+  fn test_split_url() {
+    assert_eq!(split_url("/page#section"), ("/page", None, Some("section")));
+    assert_eq!(split_url("/page?tab=1#section"), ("/page", Some("tab=1"), Some("section")));
+    assert_eq!(split_url("/page?tab=1"), ("/page", Some("tab=1"), None));
+    assert_eq!(split_url("/page#"), ("/page", None, Some("")));
+
+    // 片段中的 '?' 不是查询分隔符
+    assert_eq!(split_url("/page#a?b"), ("/page", None, Some("a?b")));
+
+    // 已编码的 %23 属于查询值
+    let (path, query, fragment) = split_url("/search?q=c%23#results");
+    assert_eq!(path, "/search");
+    assert_eq!(fragment, Some("results"));
+    let params = parse_query_string(query.unwrap()).unwrap();
+    assert_eq!(params.get("q"), Some(&vec!["c#".to_string()]));
   }
 
   #[test]
@@ -337,6 +1076,16 @@ mod tests {
     assert_eq!(split_path_segments("user/123"), vec!["user", "123"]);
   }
 
+  #[test]
+  fn test_split_path_segments_raw() {
+    assert_eq!(split_path_segments_raw("/a//b"), vec!["a", "", "b"]);
+    assert_eq!(split_path_segments("/a//b"), vec!["a", "b"]);
+    assert_eq!(split_path_segments_raw("//a"), vec!["", "a"]);
+    assert_eq!(split_path_segments_raw("/a/"), vec!["a", ""]);
+    assert_eq!(split_path_segments_raw("a/b"), vec!["a", "b"]);
+    assert_eq!(split_path_segments_raw(""), Vec::<&str>::new());
+  }
+
   #[test]
   fn test_parse_query_string() {
     let params = parse_query_string("q=rust&page=2&tags=web&tags=backend").unwrap();
@@ -351,6 +1100,73 @@ mod tests {
     assert_eq!(params.get("flag"), Some(&vec!["".to_string()]));
   }
 
+  #[test]
+  fn test_parse_query_string_malformed_input() {
+    let params = parse_query_string("a==b").unwrap();
+    assert_eq!(params.get("a"), Some(&vec!["=b".to_string()]));
+
+    let params = parse_query_string("&&a=1&&").unwrap();
+    assert_eq!(params.len(), 1);
+    assert_eq!(params.get("a"), Some(&vec!["1".to_string()]));
+
+    assert!(parse_query_string("=").unwrap().is_empty());
+    assert!(parse_query_string("=x&&=").unwrap().is_empty());
+    assert_eq!(parse_query_pairs("=x&b=").unwrap(), vec![("b".to_string(), String::new())]);
+
+    assert!(parse_query_string("a=%").is_err());
+    assert!(parse_query_string("a=%zz").is_err());
+    assert!(parse_query_string("a=%FF").is_err());
+    assert!(parse_query_string("a=%+1").is_err());
+  }
+
+  #[test]
+  fn test_parse_query_string_with_sep() {
+    let legacy = &['&', ';'];
+
+    // 只用 ';' 分隔
+    let params = parse_query_string_with_sep("q=rust;page=2;tags=a;tags=b", legacy).unwrap();
+    assert_eq!(params.get("q"), Some(&vec!["rust".to_string()]));
+    assert_eq!(params.get("page"), Some(&vec!["2".to_string()]));
+    assert_eq!(params.get("tags"), Some(&vec!["a".to_string(), "b".to_string()]));
+
+    // '&' 与 ';' 混用，空参数对同样被跳过
+    let pairs = parse_query_pairs_with_sep("a=1;b=2&c=3;;&d", legacy).unwrap();
+    assert_eq!(
+      pairs,
+      vec![
+        ("a".to_string(), "1".to_string()),
+        ("b".to_string(), "2".to_string()),
+        ("c".to_string(), "3".to_string()),
+        ("d".to_string(), String::new()),
+      ]
+    );
+
+    // 默认只按 '&' 分隔，';' 是值的一部分；编码后的 %3B 不会被当作分隔符
+    let params = parse_query_string("a=1;b=2").unwrap();
+    assert_eq!(params.get("a"), Some(&vec!["1;b=2".to_string()]));
+    let params = parse_query_string_with_sep("a=1%3Bb", legacy).unwrap();
+    assert_eq!(params.get("a"), Some(&vec!["1;b".to_string()]));
+  }
+
+  #[test]
+  fn test_format_query_string_sorted() {
+    let params = parse_query_string("z=1&tags=web&a=%20x&tags=backend&flag").unwrap();
+    assert_eq!(format_query_string_sorted(&params), "a=%20x&flag&tags=web&tags=backend&z=1");
+    assert_eq!(format_query_string_sorted(&HashMap::new()), "");
+  }
+
+  #[test]
+  fn test_format_query_pairs() {
+    let pairs = vec![
+      ("z".to_string(), "1".to_string()),
+      ("a".to_string(), "hello world".to_string()),
+      ("flag".to_string(), String::new()),
+      ("z".to_string(), "2".to_string()),
+    ];
+    assert_eq!(format_query_pairs(&pairs), "z=1&a=hello%20world&flag&z=2");
+    assert_eq!(format_query_pairs(&[]), "");
+  }
+
   #[test]
   fn test_normalize_path() {
     assert_eq!(normalize_path("//user///123//profile/"), "/user/123/profile");
@@ -308,6 +308,47 @@ mod tests {
     assert_eq!(query.active, Some(true));
   }
 
+  #[test]
+  fn test_advanced_search_query_parse_lenient_default_impl() {
+    // 手动实现的 Query 使用默认的 parse_lenient，严格解析失败时整体回退
+    let (query, warnings) = AdvancedSearchQuery::parse_lenient("q=rust&page=2");
+    assert_eq!(query.page, Some(2));
+    assert!(warnings.is_empty());
+
+    let (query, warnings) = AdvancedSearchQuery::parse_lenient("q=rust&page=two");
+    assert_eq!(query, AdvancedSearchQuery::default());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].value, "q=rust&page=two");
+  }
+
+  #[test]
+  fn test_query_formatter_accepts_borrowed_values() {
+    let query = AdvancedSearchQuery::parse("q=rust&tags=programming&tags=tutorial&role=admin&page=2&active=true").unwrap();
+
+    // 引用、Cow<str> 与 Box<str> 都可以直接传给 QueryFormatter，无需先 clone 或 to_param
+    let mut formatter = ruled_router::formatter::QueryFormatter::new();
+    if let Some(ref q) = query.q {
+      formatter.set("q", q);
+    }
+    for tag in &query.tags {
+      formatter.add("tags", tag.as_str());
+    }
+    if let Some(ref role) = query.role {
+      formatter.set("role", role);
+    }
+    if let Some(ref page) = query.page {
+      formatter.set("page", page);
+    }
+    formatter.set("active", std::borrow::Cow::Borrowed("true"));
+    assert_eq!(AdvancedSearchQuery::parse(&formatter.format()).unwrap(), query);
+
+    let mut formatter = ruled_router::formatter::QueryFormatter::new();
+    formatter.set("sort_by", Box::<str>::from("name")).set("order", "asc");
+    let sorted = AdvancedSearchQuery::parse(&formatter.format()).unwrap();
+    assert_eq!(sorted.sort_by.as_deref(), Some("name"));
+    assert_eq!(sorted.order.as_deref(), Some("asc"));
+  }
+
   #[test]
   fn test_full_route_roundtrip() {
     let url = "/api/v1/users/123/edit?q=test&role=admin&page=1";
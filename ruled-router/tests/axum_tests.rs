@@ -0,0 +1,64 @@
+//! axum 集成测试
+//!
+//! 通过 axum 的 Router 和 tower::ServiceExt 测试 Route 提取器，需要启用 axum feature
+
+#![cfg(feature = "axum")]
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use ruled_router::axum::Route;
+use ruled_router::prelude::*;
+use tower::ServiceExt;
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct PageQuery {
+  page: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/users/:id")]
+struct UserRoute {
+  id: u32,
+  #[query]
+  query: PageQuery,
+}
+
+async fn show_user(Route(route): Route<UserRoute>) -> String {
+  format!("user={} page={:?}", route.id, route.query.page)
+}
+
+fn app() -> axum::Router {
+  axum::Router::new()
+    .route("/users/{id}", get(show_user))
+    .route("/other/{*rest}", get(show_user))
+}
+
+async fn send(uri: &str) -> (StatusCode, String) {
+  let response = app()
+    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+    .await
+    .unwrap();
+  let status = response.status();
+  let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+  (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_route_extractor_parses_path_and_query() {
+  let (status, body) = send("/users/123?page=2").await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, "user=123 page=Some(2)");
+}
+
+#[tokio::test]
+async fn test_route_extractor_rejections() {
+  // 类型转换失败返回 400
+  let (status, body) = send("/users/abc").await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert!(body.contains("Type conversion error"));
+
+  // 路径结构不匹配返回 404
+  let (status, _) = send("/other/a/b").await;
+  assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,31 @@
+//! 派生宏编译测试
+//!
+//! 使用 trybuild 检查派生宏在合法输入下能够编译，在非法输入下给出清晰的错误
+
+#[test]
+fn ui_pass() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/ui/pass/*.rs");
+}
+
+#[test]
+fn ui_fail() {
+  let t = trybuild::TestCases::new();
+  t.compile_fail("tests/ui/fail/*.rs");
+}
+
+/// 带正则约束的模式在未启用 `regex` feature 时无法编译
+#[cfg(not(feature = "regex"))]
+#[test]
+fn ui_fail_without_regex() {
+  let t = trybuild::TestCases::new();
+  t.compile_fail("tests/ui/fail_without_regex/*.rs");
+}
+
+/// `QuerySerde` 的派生错误，需要启用 `serde-query` feature
+#[cfg(feature = "serde-query")]
+#[test]
+fn ui_fail_serde_query() {
+  let t = trybuild::TestCases::new();
+  t.compile_fail("tests/ui/fail_serde_query/*.rs");
+}
@@ -0,0 +1,85 @@
+//! BrowserRouter 的浏览器测试
+//!
+//! 需要在浏览器中运行：`wasm-pack test --headless --firefox ruled-router --features dom`
+
+#![cfg(all(feature = "dom", target_arch = "wasm32"))]
+
+use ruled_router::dom::BrowserRouter;
+use ruled_router::prelude::*;
+use ruled_router::RouterMatch;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct TabQuery {
+  tab: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/users/:id")]
+struct UserRoute {
+  id: u32,
+  #[query]
+  query: TabQuery,
+}
+
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/about")]
+struct AboutRoute {}
+
+#[derive(Debug, Clone, PartialEq, RouterMatch)]
+enum AppRoute {
+  User(UserRoute),
+  About(AboutRoute),
+}
+
+fn set_location(url: &str) {
+  let history = web_sys::window().unwrap().history().unwrap();
+  history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(url)).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn test_current_parses_pathname_and_search() {
+  set_location("/users/42?tab=posts");
+  let router = BrowserRouter::<AppRoute>::new().unwrap();
+
+  let expected = AppRoute::User(UserRoute {
+    id: 42,
+    query: TabQuery {
+      tab: Some("posts".to_string()),
+    },
+  });
+  assert_eq!(router.current().unwrap(), expected);
+
+  set_location("/missing");
+  assert!(router.current().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_navigate_pushes_formatted_url() {
+  set_location("/about");
+  let router = BrowserRouter::<AppRoute>::new().unwrap();
+  let history_length = web_sys::window().unwrap().history().unwrap().length().unwrap();
+
+  let route = AppRoute::User(UserRoute {
+    id: 7,
+    query: TabQuery {
+      tab: Some("likes".to_string()),
+    },
+  });
+  router.navigate(&route).unwrap();
+
+  let location = web_sys::window().unwrap().location();
+  assert_eq!(location.pathname().unwrap(), "/users/7");
+  assert_eq!(location.search().unwrap(), "?tab=likes");
+  assert_eq!(web_sys::window().unwrap().history().unwrap().length().unwrap(), history_length + 1);
+  assert_eq!(router.current().unwrap(), route);
+}
+
+#[wasm_bindgen_test]
+fn test_current_url_includes_search() {
+  set_location("/users/42?tab=posts");
+  assert_eq!(ruled_router::dom::helpers::current_url().unwrap(), "/users/42?tab=posts");
+  assert!(AppRoute::try_parse(&ruled_router::dom::helpers::current_url().unwrap()).is_ok());
+}
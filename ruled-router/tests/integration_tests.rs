@@ -47,6 +47,40 @@ impl RouterData for UserRoute {
   }
 }
 
+/// 简单的博客路由
+#[derive(Debug, Clone, PartialEq)]
+struct BlogRoute {
+  slug: String,
+}
+
+impl RouterData for BlogRoute {
+  type SubRouterMatch = ::ruled_router::NoSubRouter;
+
+  fn parse(path: &str) -> Result<Self, ParseError> {
+    let (path_part, _) = ruled_router::utils::split_path_query(path);
+    let params = PathParser::new("/blog/:slug")?.match_path(path_part)?;
+    let slug = params.get("slug").ok_or_else(|| ParseError::missing_parameter("slug"))?.clone();
+    Ok(Self { slug })
+  }
+
+  fn format(&self) -> String {
+    format!("/blog/{}", self.slug)
+  }
+
+  fn pattern() -> &'static str {
+    "/blog/:slug"
+  }
+}
+
+/// 聚合路由，用于 try_routes! 测试
+#[derive(Debug, Clone, PartialEq)]
+enum AppRoute {
+  User(UserRoute),
+  Blog(BlogRoute),
+  // 与 User 重叠的通配路由，验证按声明顺序匹配
+  Any(UserRoute),
+}
+
 /// 简单的查询参数
 #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
 struct SimpleQuery {
@@ -165,6 +199,10 @@ mod tests {
     let decoded = ruled_router::utils::url_decode("hello%20world").unwrap();
     assert_eq!(decoded, "hello world");
 
+    // 路径段保留 '+'，查询参数把 '+' 解码为空格
+    assert_eq!(ruled_router::utils::url_decode_path("c++").unwrap(), "c++");
+    assert_eq!(ruled_router::utils::url_decode_query("a+b").unwrap(), "a b");
+
     // 测试查询字符串解析
     let params = ruled_router::utils::parse_query_string("name=john&age=30&tag=rust&tag=programming").unwrap();
     assert_eq!(params.get("name"), Some(&vec!["john".to_string()]));
@@ -213,6 +251,34 @@ mod tests {
     assert!(parser.match_path("/users").is_err());
   }
 
+  #[test]
+  fn test_try_routes_dispatch() {
+    let route = ruled_router::try_routes!("/users/7", UserRoute => AppRoute::User, BlogRoute => AppRoute::Blog);
+    assert_eq!(route, Some(AppRoute::User(UserRoute { id: 7 })));
+
+    let path = String::from("/blog/hello-world?ref=home");
+    let route = ruled_router::try_routes!(&path, UserRoute => AppRoute::User, BlogRoute => AppRoute::Blog,);
+    assert_eq!(
+      route,
+      Some(AppRoute::Blog(BlogRoute {
+        slug: "hello-world".to_string()
+      }))
+    );
+
+    // 第一个匹配的路由胜出
+    let route = ruled_router::try_routes!("/users/1", UserRoute => AppRoute::Any, UserRoute => AppRoute::User);
+    assert_eq!(route, Some(AppRoute::Any(UserRoute { id: 1 })));
+
+    // 构造器可以是任意闭包
+    let id = ruled_router::try_routes!("/users/42", UserRoute => |route: UserRoute| route.id);
+    assert_eq!(id, Some(42));
+
+    assert_eq!(
+      ruled_router::try_routes!("/users/abc", UserRoute => AppRoute::User, BlogRoute => AppRoute::Blog),
+      None
+    );
+  }
+
   #[test]
   fn test_roundtrip_consistency() {
     // 测试路由的往返一致性
@@ -0,0 +1,83 @@
+//! Param derive 宏测试
+//!
+//! 测试 #[derive(Param)] 为无字段枚举生成的 FromParam / ToParam 实现
+
+use ruled_router::prelude::*;
+
+/// 默认使用变体名，区分大小写
+#[derive(Debug, Clone, Copy, PartialEq, Param)]
+enum Priority {
+  Low,
+  High,
+  #[param(rename = "urgent!")]
+  Urgent,
+}
+
+/// 重命名为小写并忽略大小写，对应手写的 UserRole 实现
+#[derive(Debug, Clone, Copy, PartialEq, Param)]
+#[param(case_insensitive)]
+enum UserRole {
+  #[param(rename = "admin")]
+  Admin,
+  #[param(rename = "user")]
+  User,
+  #[param(rename = "guest")]
+  Guest,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct RoleQuery {
+  role: Option<UserRole>,
+  roles: Vec<UserRole>,
+}
+
+#[test]
+fn test_param_derive_variant_names() {
+  assert_eq!(Priority::from_param("Low").unwrap(), Priority::Low);
+  assert_eq!(Priority::from_param("High").unwrap(), Priority::High);
+  assert_eq!(Priority::High.to_param(), "High");
+
+  // 默认区分大小写
+  assert!(Priority::from_param("low").is_err());
+}
+
+#[test]
+fn test_param_derive_rename() {
+  assert_eq!(Priority::from_param("urgent!").unwrap(), Priority::Urgent);
+  assert_eq!(Priority::Urgent.to_param(), "urgent!");
+
+  // 重命名后原变体名不再被接受
+  assert!(Priority::from_param("Urgent").is_err());
+}
+
+#[test]
+fn test_param_derive_case_insensitive() {
+  assert_eq!(UserRole::from_param("admin").unwrap(), UserRole::Admin);
+  assert_eq!(UserRole::from_param("ADMIN").unwrap(), UserRole::Admin);
+  assert_eq!(UserRole::from_param("GuEsT").unwrap(), UserRole::Guest);
+
+  // 格式化总是输出声明的值
+  assert_eq!(UserRole::from_param("USER").unwrap().to_param(), "user");
+}
+
+#[test]
+fn test_param_derive_error_lists_allowed_values() {
+  match UserRole::from_param("root") {
+    Err(ParseError::TypeConversion(msg)) => {
+      assert!(msg.contains("'root'"));
+      assert!(msg.contains("UserRole"));
+      assert!(msg.contains("admin, user, guest"));
+    }
+    other => panic!("Expected TypeConversion error, got {other:?}"),
+  }
+}
+
+#[test]
+fn test_param_derive_in_query() {
+  let query = RoleQuery::parse("role=Admin&roles=user&roles=GUEST").unwrap();
+  assert_eq!(query.role, Some(UserRole::Admin));
+  assert_eq!(query.roles, vec![UserRole::User, UserRole::Guest]);
+  assert_eq!(query.format(), "role=admin&roles=user&roles=guest");
+
+  assert!(RoleQuery::parse("role=root").is_err());
+}
@@ -207,6 +207,36 @@ mod tests {
     assert!(elapsed.as_millis() < 100); // 应该很快完成
   }
 
+  #[test]
+  fn test_matches_vs_match_path_performance() {
+    // 对比 matches 与 match_path(..).is_ok() 在分发循环中的开销
+    let parsers: Vec<PathParser> = ["/users/:id", "/posts/:slug", "/api/:version/items", "/products/:category/:id"]
+      .iter()
+      .map(|pattern| PathParser::new(pattern).unwrap())
+      .collect();
+    let tester = PerformanceTester::new();
+
+    let start = Instant::now();
+    let fast_hits = tester
+      .test_routes
+      .iter()
+      .filter(|route| parsers.iter().any(|parser| parser.matches(route)))
+      .count();
+    let fast_time = start.elapsed();
+
+    let start = Instant::now();
+    let slow_hits = tester
+      .test_routes
+      .iter()
+      .filter(|route| parsers.iter().any(|parser| parser.match_path(route).is_ok()))
+      .count();
+    let slow_time = start.elapsed();
+
+    println!("matches: {fast_time:?}, match_path().is_ok(): {slow_time:?}");
+    assert_eq!(fast_hits, slow_hits);
+    assert_eq!(fast_hits, 1000);
+  }
+
   #[test]
   fn test_memory_efficiency() {
     // 测试大量路由对象的内存使用
@@ -87,6 +87,138 @@ struct DefaultValueQuery {
   tags: Vec<String>,
 }
 
+/// 字段名与查询参数名不同的重命名测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct RenamedQuery {
+  #[query(name = "per_page")]
+  per_page_size: Option<u32>,
+
+  #[query(rename = "q")]
+  keyword: Option<String>,
+
+  #[query(name = "tag")]
+  tag_list: Vec<String>,
+}
+
+/// 使用重命名查询参数的路由
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/search")]
+struct RenamedSearchRoute {
+  #[query]
+  query: RenamedQuery,
+}
+
+/// Option 字段与无效默认值测试
+#[derive(Debug, Clone, PartialEq, QueryDerive)]
+struct OptionalDefaultQuery {
+  #[query(default = "10")]
+  per_page: Option<u32>,
+
+  #[query(default = "not-a-number")]
+  broken: u32,
+}
+
+/// 分隔值数组参数测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct DelimitedQuery {
+  #[query(delimiter = ",")]
+  tags: Vec<String>,
+
+  #[query(name = "id", delimiter = "|")]
+  ids: Vec<u32>,
+
+  // 未设置分隔符的字段仍使用重复键
+  labels: Vec<String>,
+}
+
+/// CSV 数组参数测试：`ids=1,2,3`
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct CsvQuery {
+  #[query(csv)]
+  ids: Vec<u32>,
+
+  #[query(name = "tag", csv)]
+  tags: Vec<String>,
+}
+
+/// 结构体级别的 kebab-case 参数名
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(rename_all = "kebab-case")]
+struct KebabCaseQuery {
+  sort_by: Option<String>,
+  per_page: Option<u32>,
+
+  // 显式指定的参数名优先
+  #[query(name = "q")]
+  search_text: Option<String>,
+}
+
+/// 结构体级别的 camelCase 参数名
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(rename_all = "camelCase")]
+struct CamelCaseQuery {
+  sort_by: Option<String>,
+  include_deleted_items: Option<bool>,
+  tag_ids: Vec<u32>,
+}
+
+/// 结构体级别的 SCREAMING_SNAKE_CASE 参数名
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(rename_all = "SCREAMING_SNAKE_CASE")]
+struct ScreamingQuery {
+  api_key: Option<String>,
+}
+
+/// 只有键名的布尔标志测试：`?featured`
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct FlagQuery {
+  #[query(bool_style = "flag")]
+  featured: bool,
+
+  #[query(name = "in-stock", bool_style = "flag")]
+  in_stock: bool,
+
+  q: Option<String>,
+}
+
+/// 声明式校验测试：字符串长度上限和数值范围
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct ValidatedQuery {
+  #[query(max_len = 5)]
+  q: Option<String>,
+
+  #[query(range = "1..=100", default = "1")]
+  page: u32,
+
+  #[query(range = "0.0..1.0")]
+  ratio: Option<f64>,
+}
+
+/// 宽松解析的默认值测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct LenientDefaultQuery {
+  #[query(default = "1")]
+  page: u32,
+
+  limit: u32,
+}
+
+/// 逐字段宽松解析测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct LenientSearchQuery {
+  query: Option<String>,
+  page: Option<u32>,
+  per_page: Option<u32>,
+  min_price: Option<f64>,
+  active: Option<bool>,
+  status: Vec<String>,
+}
+
 /// 嵌套查询参数测试
 #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
 struct NestedQuery {
@@ -119,6 +251,128 @@ struct NestedQuery {
   public: Option<bool>,
 }
 
+/// 分组查询参数测试：`page[size]`、`filter[price][min]`
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct ListQuery {
+  q: Option<String>,
+
+  #[query(nested)]
+  page: PageQuery,
+
+  #[query(nested)]
+  filter: FilterGroup,
+}
+
+/// 分页分组
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct PageQuery {
+  size: Option<u32>,
+  number: Option<u32>,
+}
+
+/// 过滤分组，包含数组和第二层分组
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct FilterGroup {
+  status: Vec<String>,
+
+  #[query(nested)]
+  price: PriceRange,
+}
+
+/// 价格区间分组
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct PriceRange {
+  min: Option<f64>,
+  max: Option<f64>,
+}
+
+/// 多个查询结构体共用的分页参数
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct PaginationQuery {
+  page: Option<u32>,
+  per_page: Option<u32>,
+}
+
+/// 展开分页参数的搜索查询测试：`page`、`per_page` 与 `q` 处于同一层级
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct ProductSearchQuery {
+  q: Option<String>,
+
+  #[query(flatten)]
+  pagination: PaginationQuery,
+
+  sort: Option<String>,
+}
+
+/// 分页查询参数测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct PagedQuery {
+  q: Option<String>,
+
+  #[query(page)]
+  page: Option<u32>,
+
+  per_page: Option<u32>,
+}
+
+/// 带有不参与 URL 的计算字段的查询参数测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct EventQuery {
+  since: Option<u64>,
+
+  // 字段名与参数名相同也不会被读取
+  #[query(skip)]
+  since_label: String,
+
+  #[query(skip)]
+  page: Vec<u32>,
+}
+
+/// 以逗号连接的位标志，例如 `flags=read,write`
+mod permission_bits {
+  use ruled_router::error::ParseError;
+
+  const NAMES: [&str; 3] = ["read", "write", "admin"];
+
+  pub fn parse(value: &str) -> Result<u8, ParseError> {
+    value
+      .split(',')
+      .filter(|name| !name.is_empty())
+      .try_fold(0, |bits, name| match NAMES.iter().position(|known| *known == name) {
+        Some(index) => Ok(bits | (1 << index)),
+        None => Err(ParseError::type_conversion(format!("Unknown permission '{name}'"))),
+      })
+  }
+
+  pub fn format(bits: &u8) -> String {
+    NAMES
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| bits & (1 << index) != 0)
+      .map(|(_, name)| *name)
+      .collect::<Vec<_>>()
+      .join(",")
+  }
+}
+
+/// 使用自定义模块解析和格式化的查询参数测试
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+#[query(lenient)]
+struct PermissionQuery {
+  #[query(with = "permission_bits")]
+  flags: u8,
+
+  #[query(name = "default_flags", with = "permission_bits", default = "read")]
+  fallback: u8,
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -476,4 +730,564 @@ mod tests {
 
     assert_eq!(original, parsed);
   }
+
+  #[test]
+  fn test_renamed_fields_roundtrip() {
+    // 测试重命名字段在 parse -> format -> parse 过程中保持一致
+    let query = RenamedQuery::parse("per_page=20&q=rust&tag=web&tag=wasm").unwrap();
+    assert_eq!(query.per_page_size, Some(20));
+    assert_eq!(query.keyword, Some("rust".to_string()));
+    assert_eq!(query.tag_list, vec!["web", "wasm"]);
+
+    let formatted = query.format();
+    assert!(formatted.contains("per_page=20"));
+    assert!(!formatted.contains("per_page_size"));
+    assert!(formatted.contains("q=rust"));
+    assert_eq!(RenamedQuery::parse(&formatted).unwrap(), query);
+
+    // from_query_map 和 query_keys 同样使用重命名后的参数名
+    let query_map = ruled_router::utils::parse_query_string(&formatted).unwrap();
+    assert_eq!(RenamedQuery::from_query_map(&query_map).unwrap(), query);
+    assert_eq!(RenamedQuery::query_keys(), vec!["per_page", "q", "tag"]);
+
+    // 原始字段名不会被识别
+    assert_eq!(RenamedQuery::parse("per_page_size=20").unwrap().per_page_size, None);
+  }
+
+  #[test]
+  fn test_nested_groups() {
+    let query = ListQuery::parse("q=shoes&page[size]=20&page[number]=2&filter[price][min]=10&filter[price][max]=99.5").unwrap();
+    assert_eq!(query.q, Some("shoes".to_string()));
+    assert_eq!(
+      query.page,
+      PageQuery {
+        size: Some(20),
+        number: Some(2)
+      }
+    );
+    assert_eq!(
+      query.filter.price,
+      PriceRange {
+        min: Some(10.0),
+        max: Some(99.5)
+      }
+    );
+
+    // 格式化时按层级生成参数名（方括号会被编码），并能解析回同一个值
+    let formatted = query.format();
+    assert_eq!(
+      formatted,
+      "q=shoes&page%5Bsize%5D=20&page%5Bnumber%5D=2&filter%5Bprice%5D%5Bmin%5D=10&filter%5Bprice%5D%5Bmax%5D=99.5"
+    );
+    assert_eq!(ListQuery::parse(&formatted).unwrap(), query);
+
+    // 没有任何分组参数时得到默认值
+    assert_eq!(ListQuery::parse("").unwrap(), ListQuery::default());
+    assert_eq!(ListQuery::query_keys(), vec!["q", "page", "filter"]);
+  }
+
+  #[test]
+  fn test_nested_arrays_in_group() {
+    let query_str = "filter[status]=active&filter[status]=pending&page[size]=20";
+    let query = ListQuery::parse(query_str).unwrap();
+    assert_eq!(query.filter.status, vec!["active", "pending"]);
+    assert_eq!(query.page.size, Some(20));
+
+    let formatted = query.format();
+    assert_eq!(formatted, "page%5Bsize%5D=20&filter%5Bstatus%5D=active&filter%5Bstatus%5D=pending");
+
+    // from_query_map 与 parse 一致
+    let query_map = ruled_router::utils::parse_query_string(query_str).unwrap();
+    assert_eq!(ListQuery::from_query_map(&query_map).unwrap(), query);
+  }
+
+  #[test]
+  fn test_nested_strict_and_lenient() {
+    // 严格模式逐层检查分组内的参数名
+    assert!(ListQuery::parse_strict("page[size]=20&filter[price][min]=1&filter[status]=a").is_ok());
+    let err = ListQuery::parse_strict("page[sise]=20&filter[price][low]=1&page=3").unwrap_err();
+    assert_eq!(
+      err,
+      ParseError::invalid_query("Unexpected query parameters: filter[price][low], page, page[sise]")
+    );
+
+    // 宽松模式的警告带有完整的参数名
+    let (query, warnings) = ListQuery::parse_lenient("page[size]=abc&filter[price][min]=cheap&q=x");
+    assert_eq!(query.q, Some("x".to_string()));
+    assert_eq!(query.page.size, None);
+    let mut keys: Vec<&str> = warnings.iter().map(|warning| warning.key.as_str()).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, vec!["filter[price][min]", "page[size]"]);
+  }
+
+  #[test]
+  fn test_flatten_merges_keys() {
+    let query = ProductSearchQuery::parse("q=shoes&page=2&per_page=50&sort=price").unwrap();
+    assert_eq!(
+      query,
+      ProductSearchQuery {
+        q: Some("shoes".to_string()),
+        pagination: PaginationQuery {
+          page: Some(2),
+          per_page: Some(50)
+        },
+        sort: Some("price".to_string()),
+      }
+    );
+
+    // 格式化时子类型的参数名不加前缀，按字段声明顺序合并
+    let formatted = query.format();
+    assert_eq!(formatted, "q=shoes&page=2&per_page=50&sort=price");
+    assert_eq!(ProductSearchQuery::parse(&formatted).unwrap(), query);
+    assert_eq!(query.to_query_string(), formatted);
+
+    // 方括号分组的参数名不会被展开字段识别
+    assert_eq!(
+      ProductSearchQuery::parse("pagination[page]=2").unwrap(),
+      ProductSearchQuery::default()
+    );
+
+    // from_query_map 把同一个映射交给子类型
+    let query_map = ruled_router::utils::parse_query_string("per_page=10&q=hat").unwrap();
+    let from_map = ProductSearchQuery::from_query_map(&query_map).unwrap();
+    assert_eq!(from_map.pagination, PaginationQuery::from_query_map(&query_map).unwrap());
+    assert_eq!(from_map.pagination.per_page, Some(10));
+    assert_eq!(from_map.q, Some("hat".to_string()));
+
+    assert_eq!(ProductSearchQuery::query_keys(), vec!["q", "page", "per_page", "sort"]);
+  }
+
+  #[test]
+  fn test_flatten_strict_and_lenient() {
+    // 子类型的参数名在严格模式下视为已知
+    assert!(ProductSearchQuery::parse_strict("q=a&page=1&per_page=20&sort=new").is_ok());
+    let err = ProductSearchQuery::parse_strict("page=1&pages=2&pagination[page]=3").unwrap_err();
+    assert_eq!(
+      err,
+      ParseError::invalid_query("Unexpected query parameters: pages, pagination[page]")
+    );
+
+    // 子类型的解析错误同样向上传递
+    assert!(ProductSearchQuery::parse("page=abc").is_err());
+
+    // 宽松模式的警告保留原始参数名
+    let (query, warnings) = ProductSearchQuery::parse_lenient("page=abc&per_page=20&q=x");
+    assert_eq!(query.q, Some("x".to_string()));
+    assert_eq!(query.pagination.page, None);
+    assert_eq!(query.pagination.per_page, Some(20));
+    let keys: Vec<&str> = warnings.iter().map(|warning| warning.key.as_str()).collect();
+    assert_eq!(keys, vec!["page"]);
+  }
+
+  #[test]
+  fn test_query_keys_use_external_names() {
+    // Query 与 RouterData 的 query_keys 都返回外部参数名，而不是字段名
+    assert_eq!(RenamedQuery::query_keys(), vec!["per_page", "q", "tag"]);
+    assert_eq!(<RenamedSearchRoute as RouterData>::query_keys(), vec!["per_page", "q", "tag"]);
+    assert_eq!(DelimitedQuery::query_keys(), vec!["tags", "id", "labels"]);
+  }
+
+  #[test]
+  fn test_parse_strict_rejects_unknown_keys() {
+    // 宽松的 parse 把拼写错误当作参数缺失
+    assert_eq!(SearchQuery::parse("q=rust&pag=2").unwrap().page, None);
+
+    let err = SearchQuery::parse_strict("q=rust&pag=2&zzz=1").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::InvalidQuery);
+    assert_eq!(err, ParseError::invalid_query("Unexpected query parameters: pag, zzz"));
+
+    // 重命名字段按查询参数名校验
+    assert!(RenamedQuery::parse_strict("per_page_size=20").is_err());
+  }
+
+  #[test]
+  fn test_parse_strict_accepts_known_keys() {
+    let query_str = "q=rust&page=2&tags=web&tags=wasm";
+    assert_eq!(
+      SearchQuery::parse_strict(query_str).unwrap(),
+      SearchQuery::parse(query_str).unwrap()
+    );
+    assert_eq!(SearchQuery::parse_strict("").unwrap(), SearchQuery::default());
+    assert_eq!(RenamedQuery::parse_strict("per_page=20&q=rust").unwrap().per_page_size, Some(20));
+
+    // 已知参数的值无效时仍然返回解析错误
+    assert_eq!(
+      SearchQuery::parse_strict("page=abc").unwrap_err().kind(),
+      ParseErrorKind::TypeConversion
+    );
+  }
+
+  #[test]
+  fn test_default_values_from_query_map() {
+    // 测试 from_query_map 同样使用默认值
+    let query_map = ruled_router::utils::parse_query_string("limit=5").unwrap();
+    let query = DefaultValueQuery::from_query_map(&query_map).unwrap();
+
+    assert_eq!(query.page, 1);
+    assert_eq!(query.limit, 5);
+    assert!(query.active);
+    assert_eq!(query.sort, "created_at");
+  }
+
+  #[test]
+  fn test_default_values_option_and_invalid_default() {
+    // Option 字段的默认值包装为 Some，提供参数时使用实际值
+    let query = OptionalDefaultQuery::parse("broken=3").unwrap();
+    assert_eq!(query.per_page, Some(10));
+    assert_eq!(query.broken, 3);
+    assert_eq!(OptionalDefaultQuery::parse("per_page=50&broken=3").unwrap().per_page, Some(50));
+
+    // 默认值本身无法解析时返回错误而不是 panic
+    let err = OptionalDefaultQuery::parse("").unwrap_err();
+    assert!(matches!(err, ParseError::TypeConversion(_)));
+    assert!(err.to_string().contains("not-a-number"));
+  }
+
+  #[test]
+  fn test_delimited_array_parameters() {
+    // 逗号和竖线分隔的值，空段会被丢弃
+    let query = DelimitedQuery::parse("tags=rust,web,,wasm&id=1|2|3&labels=a&labels=b").unwrap();
+    assert_eq!(query.tags, vec!["rust", "web", "wasm"]);
+    assert_eq!(query.ids, vec![1, 2, 3]);
+    assert_eq!(query.labels, vec!["a", "b"]);
+
+    // 分隔字段格式化为单个键，普通数组字段仍为重复键
+    let formatted = query.format();
+    let query_map = ruled_router::utils::parse_query_string(&formatted).unwrap();
+    assert_eq!(query_map.get("tags").unwrap(), &vec!["rust,web,wasm".to_string()]);
+    assert_eq!(query_map.get("id").unwrap(), &vec!["1|2|3".to_string()]);
+    assert_eq!(query_map.get("labels").unwrap().len(), 2);
+
+    assert_eq!(DelimitedQuery::parse(&formatted).unwrap(), query);
+    assert_eq!(DelimitedQuery::from_query_map(&query_map).unwrap(), query);
+  }
+
+  #[test]
+  fn test_delimited_array_edge_cases() {
+    // 缺失参数得到空数组，元素解析失败时返回错误
+    let query = DelimitedQuery::parse("").unwrap();
+    assert!(query.tags.is_empty());
+    assert!(query.ids.is_empty());
+    assert!(!query.format().contains("tags"));
+
+    assert!(DelimitedQuery::parse("id=1|x").is_err());
+  }
+
+  #[test]
+  fn test_csv_array_parameters() {
+    let query = CsvQuery::parse("ids=1,2,3&tag=rust,web").unwrap();
+    assert_eq!(query.ids, vec![1, 2, 3]);
+    assert_eq!(query.tags, vec!["rust", "web"]);
+
+    let query_map = ruled_router::utils::parse_query_string(&query.format()).unwrap();
+    assert_eq!(query_map.get("ids").unwrap(), &vec!["1,2,3".to_string()]);
+    assert_eq!(query_map.get("tag").unwrap(), &vec!["rust,web".to_string()]);
+    assert_eq!(CsvQuery::parse(&query.format()).unwrap(), query);
+  }
+
+  #[test]
+  fn test_csv_array_edge_cases() {
+    // 单个元素
+    let query = CsvQuery::parse("ids=7&tag=rust").unwrap();
+    assert_eq!(query.ids, vec![7]);
+    assert_eq!(query.tags, vec!["rust"]);
+    assert_eq!(CsvQuery::parse(&query.format()).unwrap(), query);
+
+    // 缺失或空值得到空数组，空数组不输出参数
+    assert_eq!(CsvQuery::parse("").unwrap(), CsvQuery::default());
+    assert_eq!(CsvQuery::parse("ids=&tag=").unwrap(), CsvQuery::default());
+    assert_eq!(CsvQuery::default().format(), "");
+
+    assert!(CsvQuery::parse("ids=1,x").is_err());
+  }
+
+  #[test]
+  fn test_rename_all_kebab_case() {
+    assert_eq!(KebabCaseQuery::query_keys(), vec!["sort-by", "per-page", "q"]);
+
+    let query = KebabCaseQuery::parse("sort-by=name&per-page=20&q=rust").unwrap();
+    assert_eq!(query.sort_by.as_deref(), Some("name"));
+    assert_eq!(query.per_page, Some(20));
+    assert_eq!(query.search_text.as_deref(), Some("rust"));
+    assert_eq!(query.format(), "sort-by=name&per-page=20&q=rust");
+
+    // 原始字段名不再被识别
+    assert_eq!(KebabCaseQuery::parse("sort_by=name").unwrap(), KebabCaseQuery::default());
+  }
+
+  #[test]
+  fn test_rename_all_camel_case() {
+    assert_eq!(CamelCaseQuery::query_keys(), vec!["sortBy", "includeDeletedItems", "tagIds"]);
+
+    let query = CamelCaseQuery::parse("sortBy=date&includeDeletedItems=true&tagIds=1&tagIds=2").unwrap();
+    assert_eq!(query.sort_by.as_deref(), Some("date"));
+    assert_eq!(query.include_deleted_items, Some(true));
+    assert_eq!(query.tag_ids, vec![1, 2]);
+    assert_eq!(CamelCaseQuery::parse(&query.format()).unwrap(), query);
+
+    assert_eq!(ScreamingQuery::query_keys(), vec!["API_KEY"]);
+    assert_eq!(ScreamingQuery::parse("API_KEY=abc").unwrap().api_key.as_deref(), Some("abc"));
+  }
+
+  #[test]
+  fn test_flag_bool_parsing() {
+    // 只有键名、显式的 true/false 和缺失
+    let query = FlagQuery::parse("featured&q=phone").unwrap();
+    assert!(query.featured);
+    assert!(!query.in_stock);
+    assert_eq!(query.q.as_deref(), Some("phone"));
+
+    assert!(FlagQuery::parse("featured=true").unwrap().featured);
+    assert!(FlagQuery::parse("featured=").unwrap().featured);
+    assert!(!FlagQuery::parse("featured=false").unwrap().featured);
+    assert_eq!(FlagQuery::parse("").unwrap(), FlagQuery::default());
+    assert!(FlagQuery::parse("featured=maybe").is_err());
+
+    let query_map = ruled_router::utils::parse_query_string("in-stock").unwrap();
+    assert!(FlagQuery::from_query_map(&query_map).unwrap().in_stock);
+
+    let (query, warnings) = FlagQuery::parse_lenient("featured=maybe&in-stock");
+    assert!(!query.featured);
+    assert!(query.in_stock);
+    assert_eq!(warnings.len(), 1);
+  }
+
+  #[test]
+  fn test_flag_bool_formatting() {
+    let query = FlagQuery {
+      featured: true,
+      in_stock: false,
+      q: Some("phone".to_string()),
+    };
+    assert_eq!(query.format(), "featured&q=phone");
+    assert_eq!(FlagQuery::parse(&query.format()).unwrap(), query);
+    assert_eq!(FlagQuery::default().format(), "");
+  }
+
+  #[test]
+  fn test_validation_boundaries() {
+    // 长度按字符计算，"héllo" 是 5 个字符
+    let query = ValidatedQuery::parse("q=h%C3%A9llo&page=1").unwrap();
+    assert_eq!(query.q.as_deref(), Some("héllo"));
+    assert_eq!(ValidatedQuery::parse("page=100").unwrap().page, 100);
+    assert_eq!(ValidatedQuery::parse("ratio=0.0").unwrap().ratio, Some(0.0));
+    // 缺失的 Option 字段和默认值不触发校验错误
+    assert_eq!(ValidatedQuery::parse("").unwrap().page, 1);
+  }
+
+  #[test]
+  fn test_validation_max_len_violation() {
+    let error = ValidatedQuery::parse("q=abcdef").unwrap_err();
+    assert_eq!(error.kind(), ruled_router::ParseErrorKind::InvalidQuery);
+    assert!(error.to_string().contains("'q' is 6 characters long, exceeding max_len 5"));
+
+    let query_map = ruled_router::utils::parse_query_string("q=abcdef").unwrap();
+    assert!(ValidatedQuery::from_query_map(&query_map).is_err());
+  }
+
+  #[test]
+  fn test_validation_range_violation() {
+    let error = ValidatedQuery::parse("page=0").unwrap_err();
+    assert_eq!(error.kind(), ruled_router::ParseErrorKind::InvalidQuery);
+    assert!(error.to_string().contains("'page' value 0 is outside the range 1..=100"));
+    assert!(ValidatedQuery::parse("page=101").is_err());
+    // 半开区间不包含上界
+    assert!(ValidatedQuery::parse("ratio=1.0").is_err());
+
+    // 宽松模式下违规的字段回退到默认值并记录警告
+    let (query, warnings) = ValidatedQuery::parse_lenient("page=500&q=ok");
+    assert_eq!(query.page, 1);
+    assert_eq!(query.q.as_deref(), Some("ok"));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].key, "page");
+    assert_eq!(warnings[0].value, "500");
+  }
+
+  #[test]
+  fn test_format_is_deterministic() {
+    // 格式化结果按字段声明顺序输出，与 HashMap 迭代顺序无关
+    let query = SearchQuery {
+      q: Some("rust".to_string()),
+      page: Some(1),
+      limit: Some(10),
+      tags: vec!["web".to_string(), "api".to_string()],
+    };
+
+    assert_eq!(query.format(), "q=rust&page=1&limit=10&tags=web&tags=api");
+    assert_eq!(query.to_query_string(), "q=rust&page=1&limit=10&tags=web&tags=api");
+
+    let query = DefaultValueQuery::parse("tags=b&search=x&sort=name").unwrap();
+    assert_eq!(query.format(), "page=1&limit=20&active=true&sort=name&search=x&tags=b");
+    assert_eq!(query.format(), query.clone().format());
+  }
+
+  #[test]
+  fn test_parse_lenient_skips_invalid_values() {
+    // 严格解析整体失败，宽松解析保留有效字段
+    let query_str = "query=rust&page=abc&per_page=20&min_price=cheap&active=maybe&status=open";
+    assert!(LenientSearchQuery::parse(query_str).is_err());
+
+    let (query, warnings) = LenientSearchQuery::parse_lenient(query_str);
+    assert_eq!(query.query, Some("rust".to_string()));
+    assert_eq!(query.page, None);
+    assert_eq!(query.per_page, Some(20));
+    assert_eq!(query.min_price, None);
+    assert_eq!(query.active, None);
+    assert_eq!(query.status, vec!["open"]);
+
+    let keys: Vec<&str> = warnings.iter().map(|warning| warning.key.as_str()).collect();
+    assert_eq!(keys, vec!["page", "min_price", "active"]);
+    assert_eq!(warnings[0].value, "abc");
+    assert!(!warnings[0].reason.is_empty());
+  }
+
+  #[test]
+  fn test_parse_lenient_defaults_and_arrays() {
+    // 无效值回退到属性默认值，数组中只丢弃无效元素
+    let (query, warnings) = LenientDefaultQuery::parse_lenient("page=x&limit=5");
+    assert_eq!(query.page, 1);
+    assert_eq!(query.limit, 5);
+    assert_eq!(warnings.len(), 1);
+
+    let (query, warnings) = DelimitedQuery::parse_lenient("id=1|x|3&tags=a,b");
+    assert_eq!(query.ids, vec![1, 3]);
+    assert_eq!(query.tags, vec!["a", "b"]);
+    assert_eq!(warnings, vec![QueryWarning::new("id", "x", warnings[0].reason.clone())]);
+
+    // 有效输入与严格解析结果一致
+    let (query, warnings) = SearchQuery::parse_lenient("q=rust&page=2&tags=web");
+    assert_eq!(query, SearchQuery::parse("q=rust&page=2&tags=web").unwrap());
+    assert!(warnings.is_empty());
+
+    // 没有标注 #[query(lenient)] 的类型使用 trait 的默认实现，严格解析失败时整体回退
+    let (query, warnings) = SearchQuery::parse_lenient("q=rust&page=two");
+    assert_eq!(query, SearchQuery::default());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].key, "");
+  }
+
+  #[test]
+  fn test_paginated_next_page() {
+    // 缺少页码时视为第 1 页
+    let query = PagedQuery::parse("q=rust&per_page=20").unwrap();
+    assert_eq!(query.page(), None);
+    assert_eq!(query.next_page().page, Some(2));
+    assert_eq!(query.next_page().format(), "q=rust&page=2&per_page=20");
+
+    let query = PagedQuery::parse("q=rust&page=1").unwrap();
+    let next = query.next_page();
+    assert_eq!(next.page, Some(2));
+    assert_eq!(next.q, query.q);
+    assert_eq!(next.next_page().page, Some(3));
+
+    // 原值不受影响
+    assert_eq!(query.page, Some(1));
+  }
+
+  #[test]
+  fn test_paginated_prev_and_with_page() {
+    // 上一页最小为 1
+    assert_eq!(PagedQuery::default().prev_page().page, Some(1));
+    assert_eq!(PagedQuery::parse("page=1").unwrap().prev_page().page, Some(1));
+    assert_eq!(PagedQuery::parse("page=5").unwrap().prev_page().page, Some(4));
+
+    let query = PagedQuery::parse("q=rust&page=5").unwrap();
+    assert_eq!(query.with_page(9).format(), "q=rust&page=9");
+    assert_eq!(PagedQuery::parse(&query.with_page(9).format()).unwrap().page(), Some(9));
+
+    // 页码上限不溢出
+    assert_eq!(query.with_page(u32::MAX).next_page().page, Some(u32::MAX));
+  }
+
+  #[test]
+  fn test_skip_fields_stay_default() {
+    let query = EventQuery::parse("since=1700000000&since_label=now&page=2").unwrap();
+    assert_eq!(query.since, Some(1700000000));
+    assert_eq!(query.since_label, "");
+    assert!(query.page.is_empty());
+
+    // 手动填充的计算字段不会出现在 format 输出中
+    let query = EventQuery {
+      since: Some(5),
+      since_label: "five".to_string(),
+      page: vec![1, 2],
+    };
+    assert_eq!(query.format(), "since=5");
+    let parsed = EventQuery::parse(&query.format()).unwrap();
+    assert_eq!(
+      parsed,
+      EventQuery {
+        since: Some(5),
+        ..Default::default()
+      }
+    );
+
+    // from_query_map、parse_lenient 与 query_keys 同样忽略跳过的字段
+    let query_map = ruled_router::utils::parse_query_string("since=5&page=3").unwrap();
+    assert_eq!(EventQuery::from_query_map(&query_map).unwrap(), parsed);
+    let (lenient, warnings) = EventQuery::parse_lenient("page=x&since=5");
+    assert_eq!(lenient, parsed);
+    assert!(warnings.is_empty());
+    assert_eq!(EventQuery::query_keys(), vec!["since"]);
+    assert!(EventQuery::parse_strict("since=5&page=3").is_err());
+  }
+
+  #[test]
+  fn test_query_with_module_roundtrip() {
+    let query = PermissionQuery::parse("flags=read,admin").unwrap();
+    assert_eq!(query.flags, 0b101);
+    assert_eq!(query.fallback, 0b001);
+
+    let formatted = query.format();
+    assert_eq!(formatted, "flags=read%2Cadmin&default_flags=read");
+    assert_eq!(PermissionQuery::parse(&formatted).unwrap(), query);
+
+    let query_map = ruled_router::utils::parse_query_string("flags=write&default_flags=").unwrap();
+    assert_eq!(
+      PermissionQuery::from_query_map(&query_map).unwrap(),
+      PermissionQuery { flags: 0b010, fallback: 0 }
+    );
+
+    // 模块返回的错误原样传播，缺少没有默认值的参数时报缺少参数
+    assert!(matches!(PermissionQuery::parse("flags=root"), Err(ParseError::TypeConversion(_))));
+    assert_eq!(PermissionQuery::parse("").unwrap_err(), ParseError::missing_parameter("flags"));
+
+    // 宽松解析记录警告并回退到默认值
+    let (lenient, warnings) = PermissionQuery::parse_lenient("flags=read&default_flags=root");
+    assert_eq!(
+      lenient,
+      PermissionQuery {
+        flags: 0b001,
+        fallback: 0b001
+      }
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].key, "default_flags");
+  }
+
+  #[test]
+  fn test_with_prefix_feeds_component_queries() {
+    let parser = ruled_router::parser::QueryParser::new("user.page=1&user.q=alice&post.page=2&post.tags=rust&post.tags=web").unwrap();
+
+    // 每个组件只看到去掉前缀后的自己的参数
+    let user = SearchQuery::from_query_map(parser.with_prefix("user.").params()).unwrap();
+    assert_eq!(
+      user,
+      SearchQuery {
+        q: Some("alice".to_string()),
+        page: Some(1),
+        ..SearchQuery::default()
+      }
+    );
+
+    let post = SearchQuery::parse(&parser.with_prefix("post.").format()).unwrap();
+    assert_eq!(
+      post,
+      SearchQuery {
+        page: Some(2),
+        tags: vec!["rust".to_string(), "web".to_string()],
+        ..SearchQuery::default()
+      }
+    );
+  }
 }
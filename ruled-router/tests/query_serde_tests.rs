@@ -0,0 +1,109 @@
+//! QuerySerde derive 宏测试
+//!
+//! 测试基于 serde_urlencoded 的 Query 实现，需要启用 serde-query feature
+
+#![cfg(feature = "serde-query")]
+
+use ruled_router::prelude::*;
+use ruled_router::QuerySerde;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, QuerySerde)]
+struct SearchQuery {
+  #[serde(rename = "q")]
+  keyword: String,
+  page: Option<u32>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  sort: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, QuerySerde)]
+#[serde(rename_all = "camelCase")]
+struct ListingQuery {
+  page_size: Option<u32>,
+  sort_by: Option<String>,
+  #[serde(rename = "q")]
+  search_text: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+struct PriceRange {
+  min: u32,
+  max: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, QuerySerde)]
+struct RangeQuery {
+  price: PriceRange,
+}
+
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/search")]
+struct SearchRoute {
+  #[query]
+  query: SearchQuery,
+}
+
+#[test]
+fn test_serde_rename_roundtrip() {
+  let query = SearchQuery::parse("q=rust%20router&page=2").unwrap();
+  assert_eq!(
+    query,
+    SearchQuery {
+      keyword: "rust router".to_string(),
+      page: Some(2),
+      sort: None,
+    }
+  );
+
+  let formatted = query.format();
+  assert_eq!(formatted, "q=rust+router&page=2");
+  assert_eq!(SearchQuery::parse(&formatted).unwrap(), query);
+
+  // 原字段名不会被识别
+  assert!(SearchQuery::parse("keyword=rust").is_err());
+  assert_eq!(SearchQuery::query_keys(), vec!["q", "page", "sort"]);
+}
+
+#[test]
+fn test_from_query_map() {
+  let query_map = ruled_router::utils::parse_query_string("q=web&sort=new").unwrap();
+  let query = SearchQuery::from_query_map(&query_map).unwrap();
+  assert_eq!(query.keyword, "web");
+  assert_eq!(query.sort, Some("new".to_string()));
+
+  // 直接反序列化键值对，与解析查询字符串的结果一致
+  for query in ["q=a%26b&page=3", "q=web&page=x", "page=1", "q=a&q=b"] {
+    let query_map = ruled_router::utils::parse_query_string(query).unwrap();
+    assert_eq!(SearchQuery::from_query_map(&query_map), SearchQuery::parse(query), "{query}");
+  }
+}
+
+#[test]
+fn test_query_serde_in_route() {
+  let route = SearchRoute::parse("/search?q=hello&page=1").unwrap();
+  assert_eq!(route.query.keyword, "hello");
+  assert_eq!(route.format(), "/search?q=hello&page=1");
+}
+
+#[test]
+fn test_serde_rename_all_keys() {
+  // query_keys 按 rename_all 转换字段名，字段上的 rename 优先
+  assert_eq!(ListingQuery::query_keys(), vec!["pageSize", "sortBy", "q"]);
+
+  let query = ListingQuery::parse_strict("pageSize=20&sortBy=name&q=rust").unwrap();
+  assert_eq!(query.page_size, Some(20));
+  assert_eq!(query.sort_by, Some("name".to_string()));
+  assert_eq!(query.search_text, Some("rust".to_string()));
+  assert!(ListingQuery::parse_strict("page_size=20").is_err());
+}
+
+#[test]
+fn test_format_error_yields_empty_string() {
+  // serde_urlencoded 不支持嵌套结构体，format 在任何构建中都输出空字符串，错误只能通过 serde_query::format 取得
+  let query = RangeQuery {
+    price: PriceRange { min: 1, max: 9 },
+  };
+  assert!(ruled_router::serde_query::format(&query).is_err());
+  assert_eq!(query.format(), "");
+}
@@ -0,0 +1,55 @@
+//! 查询字符串属性测试
+//!
+//! 用随机输入检查 parse_query_string 不会 panic，并且与 format_query_string 互为逆操作
+
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use ruled_router::utils::{format_query_pairs, format_query_string, parse_query_pairs, parse_query_string};
+
+/// 偏向查询字符串特殊字符的输入，比完全随机的字符串更容易触发边界情况
+fn query_like_string() -> impl Strategy<Value = String> {
+  "[a-c=&%+ 0-9A-Fz\\[\\]é]{0,24}"
+}
+
+/// 合法的参数映射：键名非空，每个键至少有一个值
+fn query_map() -> impl Strategy<Value = HashMap<String, Vec<String>>> {
+  prop::collection::hash_map(".{1,8}", prop::collection::vec(".{0,8}", 1..4), 0..6)
+}
+
+proptest! {
+  #[test]
+  fn parse_never_panics_on_arbitrary_input(query in "\\PC{0,32}") {
+    let _ = parse_query_string(&query);
+    let _ = parse_query_pairs(&query);
+  }
+
+  #[test]
+  fn parse_never_panics_on_query_like_input(query in query_like_string()) {
+    if let Ok(params) = parse_query_string(&query) {
+      prop_assert!(params.keys().all(|key| !key.is_empty()));
+      prop_assert!(params.values().all(|values| !values.is_empty()));
+    }
+  }
+
+  #[test]
+  fn format_then_parse_round_trips(params in query_map()) {
+    let query = format_query_string(&params);
+    prop_assert_eq!(parse_query_string(&query).unwrap(), params);
+  }
+
+  #[test]
+  fn format_then_parse_pairs_keeps_order(pairs in prop::collection::vec((".{1,8}", ".{0,8}"), 0..8)) {
+    let query = format_query_pairs(&pairs);
+    prop_assert_eq!(parse_query_pairs(&query).unwrap(), pairs);
+  }
+
+  #[test]
+  fn parse_then_format_is_stable(query in query_like_string()) {
+    // 规范化一次之后，再次格式化得到相同的结果
+    if let Ok(pairs) = parse_query_pairs(&query) {
+      let formatted = format_query_pairs(&pairs);
+      prop_assert_eq!(parse_query_pairs(&formatted).unwrap(), pairs);
+    }
+  }
+}
@@ -7,7 +7,7 @@ use ruled_router::prelude::*;
 
 /// 基础路由测试
 #[derive(Debug, Clone, PartialEq, RouterData)]
-#[router(pattern = "/users/:id")]
+#[router(pattern = "/users/:id", std_traits)]
 struct UserRoute {
   id: u32,
 }
@@ -29,6 +29,14 @@ struct ApiRoute {
   post_id: u64,
 }
 
+/// 根路由测试
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/")]
+struct RootRoute {
+  #[query]
+  params: SearchParams,
+}
+
 /// 带查询参数的路由测试
 #[derive(Debug, Clone, PartialEq, RouterData)]
 #[router(pattern = "/search/:category")]
@@ -65,6 +73,32 @@ struct ModuleOptions {
   debug: Option<bool>,
 }
 
+/// 在属性中声明外部查询类型的路由测试
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/find/:category", query = SearchParams)]
+struct FindRoute {
+  category: String,
+}
+
+/// 包含必需字段的查询类型
+#[derive(Debug, Clone, PartialEq, QueryDerive)]
+struct RegionQuery {
+  region: String,
+  page: Option<u32>,
+}
+
+/// 声明的查询类型包含必需字段的路由测试
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/stores", query = RegionQuery)]
+struct StoresRoute {}
+
+/// 十六进制路径参数的路由测试
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/tx/:hash")]
+struct TransactionRoute {
+  hash: ruled_router::parser::Hex<u64>,
+}
+
 /// 子路由类型（占位符）
 type SubRouteType = ruled_router::NoSubRouter;
 
@@ -234,4 +268,1124 @@ mod tests {
     assert_eq!(route.params.q, Some("rust programming".to_string()));
     assert_eq!(route.params.tags, vec!["web dev".to_string()]);
   }
+
+  #[test]
+  fn test_parse_ignores_fragment() {
+    // 只有片段
+    assert_eq!(UserRoute::parse("/users/42#top").unwrap(), UserRoute { id: 42 });
+
+    // 查询参数加片段，片段不会混入最后一个查询值
+    let route = SearchRoute::parse("/search/tech?q=rust&page=2#results").unwrap();
+    assert_eq!(route.params.q, Some("rust".to_string()));
+    assert_eq!(route.params.page, Some(2));
+
+    // 已编码的 %23 仍然是查询值的一部分
+    let route = SearchRoute::parse("/search/lang?q=c%23#results").unwrap();
+    assert_eq!(route.params.q, Some("c#".to_string()));
+  }
+
+  #[test]
+  fn test_escaped_colon_literal() {
+    #[derive(Debug, PartialEq, RouterData)]
+    #[router(pattern = r"/legacy/\:export/:id")]
+    struct LegacyExportRoute {
+      id: u32,
+    }
+
+    let route = LegacyExportRoute::parse("/legacy/:export/7").unwrap();
+    assert_eq!(route, LegacyExportRoute { id: 7 });
+    assert_eq!(route.format(), "/legacy/:export/7");
+    assert!(LegacyExportRoute::parse("/legacy/export/7").is_err());
+  }
+
+  #[test]
+  fn test_format_with_query() {
+    // 没有查询参数的路由用 '?' 开始
+    let route = UserRoute { id: 42 };
+    assert_eq!(route.format_with_query(&[("ref", "email")]), "/users/42?ref=email");
+    assert_eq!(route.format_with_query(&[]), "/users/42");
+
+    // 已有查询参数的路由用 '&' 连接，并对键值编码
+    let route = SearchRoute::parse("/search/tech?q=rust").unwrap();
+    let url = route.format_with_query(&[("ref", "news letter"), ("a&b", "1")]);
+    assert_eq!(url, "/search/tech?q=rust&ref=news%20letter&a%26b=1");
+
+    let parsed = SearchRoute::parse(&url).unwrap();
+    assert_eq!(parsed, route);
+  }
+
+  #[test]
+  fn test_build_url() {
+    let route = UserRoute { id: 42 };
+
+    // 空查询参数不追加 '?'
+    assert_eq!(build_url(&route, &SearchParams::default()), "/users/42");
+
+    let query = SearchParams {
+      q: Some("rust lang".to_string()),
+      page: Some(2),
+      ..Default::default()
+    };
+    assert_eq!(build_url(&route, &query), "/users/42?q=rust%20lang&page=2");
+
+    // 路由自身的查询参数在前
+    let route = SearchRoute::parse("/search/tech?limit=5").unwrap();
+    assert_eq!(ruled_router::build_url(&route, &query), "/search/tech?limit=5&q=rust%20lang&page=2");
+  }
+
+  #[test]
+  fn test_parse_url() {
+    #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+    struct UserQuery {
+      tab: Option<String>,
+      page: Option<u32>,
+    }
+
+    let (route, query): (UserRoute, UserQuery) = parse_url("/users/42?tab=posts&page=3#top").unwrap();
+    assert_eq!(route, UserRoute { id: 42 });
+    assert_eq!(
+      query,
+      UserQuery {
+        tab: Some("posts".to_string()),
+        page: Some(3)
+      }
+    );
+    assert_eq!(build_url(&route, &query), "/users/42?tab=posts&page=3");
+
+    let (_, query) = ruled_router::parse_url::<UserRoute, UserQuery>("/users/42").unwrap();
+    assert_eq!(query, UserQuery::default());
+
+    // 路径或查询参数解析失败时返回错误
+    assert!(parse_url::<UserRoute, UserQuery>("/users/abc?tab=posts").is_err());
+    assert!(parse_url::<UserRoute, UserQuery>("/users/42?page=x").is_err());
+  }
+
+  #[test]
+  fn test_parent_path_of_leaf_routes() {
+    // 末尾是参数的路由
+    assert_eq!(UserRoute { id: 123 }.parent_path().as_deref(), Some("/users"));
+    let route = ApiRoute::parse("/api/v1/users/456/posts/789").unwrap();
+    assert_eq!(route.parent_path().as_deref(), Some("/api/v1/users/456/posts"));
+
+    // 查询参数不属于上级路径
+    let route = SearchRoute::parse("/search/books?q=rust").unwrap();
+    assert_eq!(route.parent_path().as_deref(), Some("/search"));
+  }
+
+  #[test]
+  fn test_hex_path_param() {
+    let route = TransactionRoute::parse("/tx/ff").unwrap();
+    assert_eq!(route.hash.0, 255);
+    assert_eq!(route.format(), "/tx/ff");
+
+    let error = TransactionRoute::parse("/tx/xyz").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::TypeConversion);
+  }
+
+  #[test]
+  fn test_root_route_accepts_empty_path() {
+    let root = RootRoute {
+      params: SearchParams::default(),
+    };
+    assert_eq!(RootRoute::parse("").unwrap(), root);
+    assert_eq!(RootRoute::parse("/").unwrap(), root);
+    assert_eq!(RootRoute::parse_nested("").unwrap(), root);
+
+    // 只有查询参数时按根路径解析，并保留查询参数
+    let expected = RootRoute {
+      params: SearchParams {
+        page: Some(1),
+        ..SearchParams::default()
+      },
+    };
+    assert_eq!(RootRoute::parse("?page=1").unwrap(), expected);
+    assert_eq!(RootRoute::parse("/?page=1").unwrap(), expected);
+    assert_eq!(RootRoute::parse_with_sub("?page=1").unwrap().0, expected);
+    assert_eq!(expected.format(), "/?page=1");
+
+    // 非根模式不会因为规范化而匹配空路径
+    assert!(UserRoute::parse("").is_err());
+    assert!(UserRoute::parse("?id=1").is_err());
+  }
+
+  #[test]
+  fn test_parse_bytes() {
+    assert_eq!(UserRoute::parse_bytes(b"/users/42").unwrap(), UserRoute { id: 42 });
+    assert_eq!(UserRoute::parse_bytes(b"/users/42?tab=posts").unwrap(), UserRoute { id: 42 });
+
+    // 非法 UTF-8 返回 UrlEncoding 错误，而不是有损替换后继续匹配
+    let error = UserRoute::parse_bytes(b"/users/\xFF").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::UrlEncoding);
+  }
+
+  #[test]
+  fn test_attribute_query_type() {
+    let url = "/find/books?q=rust&page=2&tags=a&tags=b";
+    let (route, query) = FindRoute::parse_with_query(url).unwrap();
+    assert_eq!(route.category, "books");
+    assert_eq!(
+      query,
+      SearchParams {
+        q: Some("rust".to_string()),
+        page: Some(2),
+        limit: None,
+        tags: vec!["a".to_string(), "b".to_string()],
+      }
+    );
+    assert_eq!(FindRoute::parse_query(url).unwrap(), query);
+
+    // format 只输出路径，format_with 追加查询参数
+    assert_eq!(route.format(), "/find/books");
+    let formatted = route.format_with(&query);
+    assert_eq!(FindRoute::parse_with_query(&formatted).unwrap(), (route.clone(), query));
+
+    // 生成的方法不会遮蔽 RouterData::format_with_query
+    assert_eq!(route.format_with_query(&[("ref", "email")]), "/find/books?ref=email");
+
+    // parse 同样接受带查询的路径，但查询无法解析为 SearchParams 时返回错误
+    assert_eq!(FindRoute::parse(url).unwrap(), route);
+    assert!(FindRoute::parse("/find/books?page=abc").is_err());
+    assert!(FindRoute::parse_with_sub("/find/books?page=abc").is_err());
+    assert!(FindRoute::parse_with_sub(url).is_ok());
+    assert_eq!(FindRoute::query_keys(), SearchParams::query_keys());
+  }
+
+  #[test]
+  fn test_attribute_query_type_with_required_field() {
+    let url = "/stores?region=eu&page=3#map";
+    let (route, query) = StoresRoute::parse_with_query(url).unwrap();
+    assert_eq!(route, StoresRoute {});
+    assert_eq!(
+      query,
+      RegionQuery {
+        region: "eu".to_string(),
+        page: Some(3),
+      }
+    );
+    assert_eq!(StoresRoute::parse(url).unwrap(), route);
+    assert_eq!(StoresRoute::parse_with_sub(url).unwrap().0, route);
+
+    // 每个入口都按同样的规则校验查询：缺少必需参数或值无效时都返回错误
+    for invalid in ["/stores", "/stores?page=3", "/stores?region=eu&page=x"] {
+      assert!(StoresRoute::parse(invalid).is_err(), "{invalid}");
+      assert!(StoresRoute::parse_with_sub(invalid).is_err(), "{invalid}");
+      assert!(StoresRoute::parse_with_query(invalid).is_err(), "{invalid}");
+    }
+    assert_eq!(
+      StoresRoute::parse_with_query("/stores").unwrap_err(),
+      ParseError::missing_parameter("region")
+    );
+  }
+}
+
+/// 带正则约束参数的路由测试
+#[cfg(feature = "regex")]
+mod constrained_route_tests {
+  use ruled_router::prelude::*;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = r"/posts/:id(\d+)")]
+  struct PostByIdRoute {
+    id: u32,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/posts/{slug:[a-z0-9-]+}")]
+  struct PostBySlugRoute {
+    slug: String,
+  }
+
+  #[test]
+  fn test_constrained_route_derive() {
+    let route = PostByIdRoute::parse("/posts/42").unwrap();
+    assert_eq!(route.id, 42);
+    assert_eq!(route.format(), "/posts/42");
+    assert!(matches!(
+      PostByIdRoute::parse("/posts/rust-tips"),
+      Err(ParseError::ConstraintViolation { .. })
+    ));
+
+    let route = PostBySlugRoute::parse("/posts/rust-tips").unwrap();
+    assert_eq!(route.slug, "rust-tips");
+    assert_eq!(route.format(), "/posts/rust-tips");
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/tags/:name([a-z)")]
+  struct InvalidConstraintRoute {
+    name: String,
+  }
+
+  #[test]
+  fn test_invalid_constraint_formats_without_panicking() {
+    // 正则在运行时编译，无效时解析返回错误，格式化不需要正则
+    assert!(InvalidConstraintRoute::parse("/tags/rust").is_err());
+    let route = InvalidConstraintRoute { name: "rust".to_string() };
+    assert_eq!(route.format(), "/tags/rust");
+  }
+}
+
+/// 嵌套路径的消费长度测试
+mod consumed_length_tests {
+  use super::*;
+
+  #[test]
+  fn test_consumed_length_for_nested_path() {
+    let path = "/users/123/profile/basic?tab=1";
+    let consumed = UserRoute::consumed_length(path).unwrap();
+    assert_eq!(&path[..consumed], "/users/123");
+
+    let (result, remaining) = UserRoute::parse_from_full_path(path).unwrap();
+    assert_eq!(result.current.id, 123);
+    assert_eq!(remaining, "/profile/basic?tab=1");
+
+    assert!(UserRoute::consumed_length("/posts/123").is_err());
+  }
+}
+
+/// 子路由字段的递归解析测试
+mod recursive_parse_tests {
+  use super::*;
+  use ruled_router::{RouteMatcher, RouterMatch};
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users")]
+  struct UserModuleRoute {
+    #[sub_router]
+    sub_router: RouteState<UserSubRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum UserSubRouterMatch {
+    Profile(ProfileRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/profile")]
+  struct ProfileRoute {
+    #[sub_router]
+    sub_router: Option<ProfileDetailRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum ProfileDetailRouterMatch {
+    Basic(BasicInfoRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/basic/:id")]
+  struct BasicInfoRoute {
+    id: u32,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AppModuleMatch {
+    Users(UserModuleRoute),
+    Basic(BasicInfoRoute),
+  }
+
+  #[test]
+  fn test_describe_route_tree() {
+    use ruled_router::traits::RouteTree;
+
+    let leaf = |pattern| RouteTree { pattern, children: vec![] };
+    let tree = AppModuleMatch::describe();
+    assert_eq!(
+      tree,
+      RouteTree {
+        pattern: "",
+        children: vec![
+          RouteTree {
+            pattern: "/users",
+            children: vec![RouteTree {
+              pattern: "/profile",
+              children: vec![leaf("/basic/:id")],
+            }],
+          },
+          leaf("/basic/:id"),
+        ],
+      }
+    );
+
+    // 与实例无关，也适用于中间层的路由匹配器
+    let sub_tree = UserSubRouterMatch::describe();
+    assert_eq!(sub_tree.pattern, "");
+    assert_eq!(sub_tree.children, tree.children[0].children);
+    assert!(ruled_router::NoSubRouter::describe().children.is_empty());
+  }
+
+  #[test]
+  fn test_parse_populates_nested_sub_routers() {
+    let route = UserModuleRoute::parse("/users/profile/basic/123").unwrap();
+    let expected = UserModuleRoute {
+      sub_router: RouteState::SubRoute(UserSubRouterMatch::Profile(ProfileRoute {
+        sub_router: Some(ProfileDetailRouterMatch::Basic(BasicInfoRoute { id: 123 })),
+      })),
+    };
+    assert_eq!(route, expected);
+    assert_eq!(route.format(), "/users/profile/basic/123");
+  }
+
+  #[test]
+  fn test_parse_without_remaining_path() {
+    let route = UserModuleRoute::parse("/users").unwrap();
+    assert_eq!(route.sub_router, RouteState::NoSubRoute);
+
+    let route = ProfileRoute::parse("/profile/").unwrap();
+    assert_eq!(route.sub_router, None);
+  }
+
+  #[test]
+  fn test_nested_parse_ignores_fragment() {
+    let route = UserModuleRoute::parse("/users/profile/basic/123#section").unwrap();
+    assert_eq!(route.format(), "/users/profile/basic/123");
+
+    let route = UserModuleRoute::parse("/users#section").unwrap();
+    assert_eq!(route.sub_router, RouteState::NoSubRoute);
+  }
+
+  #[test]
+  fn test_parse_with_unmatched_sub_path() {
+    // RouteState 字段记录解析失败，Option 字段返回错误
+    let route = UserModuleRoute::parse("/users/unknown").unwrap();
+    assert!(route.sub_router.is_parse_failed());
+    assert!(ProfileRoute::parse("/profile/unknown/1").is_err());
+  }
+
+  #[test]
+  fn test_parse_failed_diagnostics() {
+    let (_, state) = UserModuleRoute::parse_with_sub("/users/profiles/basic?tab=1").unwrap();
+    match &state {
+      RouteState::ParseFailed {
+        remaining_path,
+        attempted_patterns,
+        closest_match,
+      } => {
+        assert_eq!(remaining_path, "/profiles/basic?tab=1");
+        assert_eq!(attempted_patterns, &vec!["/profile".to_string()]);
+        // "/profiles" 与 "/profile" 没有完全相同的字面量段
+        assert_eq!(closest_match, &None);
+      }
+      other => panic!("Expected ParseFailed, got {other:?}"),
+    }
+
+    let (_, state) = ProfileRoute::parse_with_sub("/profile/basic/abc").unwrap();
+    let debug_info = state.debug_info().unwrap();
+    assert_eq!(debug_info.available_routes, vec!["/basic/:id".to_string()]);
+    let suggestion = debug_info.suggestion.as_deref().unwrap();
+    assert!(suggestion.contains("Did you mean '/basic/:id'?"));
+    assert!(suggestion.contains("matched 6 characters"));
+    assert!(debug_info.to_error_message().contains("Available routes: /basic/:id"));
+  }
+
+  #[test]
+  fn test_parent_path_drops_deepest_sub_route() {
+    let route = UserModuleRoute::parse("/users/profile/basic/123").unwrap();
+    assert_eq!(route.parent_path().as_deref(), Some("/users/profile"));
+
+    let route = UserModuleRoute::parse("/users/profile").unwrap();
+    assert_eq!(route.parent_path().as_deref(), Some("/users"));
+
+    // 没有子路由时去掉最后一段，单段的顶层路由没有上级
+    assert_eq!(UserModuleRoute::parse("/users").unwrap().parent_path(), None);
+  }
+
+  #[test]
+  fn test_parse_with_sub_matches_trait_signature() {
+    // 派生实现的签名与 trait 声明一致，可以当作 trait 方法的函数指针使用
+    type ParseWithSub<R> = fn(&str) -> Result<(R, RouteState<<R as RouterData>::SubRouterMatch>), ruled_router::error::ParseError>;
+    let parse_with_sub: ParseWithSub<ProfileRoute> = <ProfileRoute as RouterData>::parse_with_sub;
+
+    // 通过泛型约束调用，只依赖 trait 中的 RouteState 返回类型
+    fn sub_state<R: RouterData>(path: &str) -> RouteState<R::SubRouterMatch> {
+      R::parse_with_sub(path).unwrap().1
+    }
+
+    let (route, state) = parse_with_sub("/profile/basic/7").unwrap();
+    assert_eq!(route.sub_router, None);
+    assert_eq!(
+      state,
+      RouteState::SubRoute(ProfileDetailRouterMatch::Basic(BasicInfoRoute { id: 7 }))
+    );
+    assert_eq!(sub_state::<ProfileRoute>("/profile"), RouteState::NoSubRoute);
+    assert!(sub_state::<ProfileRoute>("/profile/unknown").is_parse_failed());
+    assert_eq!(sub_state::<UserRoute>("/users/1"), RouteState::NoSubRoute);
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AppRouterMatch {
+    Users(UserModuleRoute),
+  }
+
+  #[test]
+  fn test_try_parse_nested_three_levels() {
+    let route = AppRouterMatch::try_parse_nested("/users/profile/basic/123").unwrap();
+    let expected = AppRouterMatch::Users(UserModuleRoute {
+      sub_router: RouteState::SubRoute(UserSubRouterMatch::Profile(ProfileRoute {
+        sub_router: Some(ProfileDetailRouterMatch::Basic(BasicInfoRoute { id: 123 })),
+      })),
+    });
+    assert_eq!(route, expected);
+    assert_eq!(route, AppRouterMatch::try_parse("/users/profile/basic/123").unwrap());
+
+    // 中间层级没有剩余路径时停止
+    let route = AppRouterMatch::try_parse_nested("/users/profile").unwrap();
+    assert_eq!(
+      route,
+      AppRouterMatch::Users(UserModuleRoute {
+        sub_router: RouteState::SubRoute(UserSubRouterMatch::Profile(ProfileRoute { sub_router: None })),
+      })
+    );
+  }
+
+  #[test]
+  fn test_try_parse_nested_unparseable_tail() {
+    // try_parse 把失败记录在 RouteState 中，try_parse_nested 返回错误
+    assert!(AppRouterMatch::try_parse("/users/unknown").is_ok());
+    assert!(AppRouterMatch::try_parse_nested("/users/unknown").is_err());
+
+    assert!(AppRouterMatch::try_parse_nested("/users/profile/basic/abc").is_err());
+    assert!(AppRouterMatch::try_parse_nested("/users/profile/basic/1/extra").is_err());
+    assert!(UserModuleRoute::parse_nested("/users/profile/missing").is_err());
+    assert!(AppRouterMatch::try_parse_nested("/other").is_err());
+  }
+
+  /// 子路由必须存在的模块路由
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/admin")]
+  struct AdminModuleRoute {
+    #[sub_router]
+    sub_router: AdminSubRouterMatch,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AdminSubRouterMatch {
+    User(AdminUserRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users/:id")]
+  struct AdminUserRoute {
+    id: u32,
+  }
+
+  #[test]
+  fn test_required_sub_router() {
+    let route = AdminModuleRoute::parse("/admin/users/5").unwrap();
+    assert_eq!(route.sub_router, AdminSubRouterMatch::User(AdminUserRoute { id: 5 }));
+    assert_eq!(route.format(), "/admin/users/5");
+  }
+
+  #[test]
+  fn test_required_sub_router_missing_child() {
+    assert!(matches!(AdminModuleRoute::parse("/admin"), Err(ParseError::InvalidPath(_))));
+    assert!(matches!(AdminModuleRoute::parse("/admin/"), Err(ParseError::InvalidPath(_))));
+    assert!(matches!(AdminModuleRoute::parse("/admin/unknown"), Err(ParseError::InvalidPath(_))));
+  }
+
+  #[test]
+  fn test_router_match_try_parse_with_params() {
+    let matched = ProfileDetailRouterMatch::try_parse("/basic/7").unwrap();
+    assert_eq!(matched, ProfileDetailRouterMatch::Basic(BasicInfoRoute { id: 7 }));
+  }
+}
+
+/// 子路由类型只取决于字段声明，与结构体和枚举的命名无关
+mod unconventional_names_tests {
+  use super::*;
+  use ruled_router::{RouteMatcher, RouterMatch};
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/shop")]
+  struct Storefront {
+    #[sub_router]
+    aisle: Option<Aisles>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum Aisles {
+    Fruit(FruitShelf),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/fruit/:kind")]
+  struct FruitShelf {
+    kind: String,
+    #[sub_router]
+    detail: RouteState<Zzz>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum Zzz {
+    Price(PriceTag),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/price")]
+  struct PriceTag {}
+
+  #[test]
+  fn test_sub_router_type_from_field_declaration() {
+    let route = Storefront::parse("/shop/fruit/apple/price").unwrap();
+    let expected = Storefront {
+      aisle: Some(Aisles::Fruit(FruitShelf {
+        kind: "apple".to_string(),
+        detail: RouteState::SubRoute(Zzz::Price(PriceTag {})),
+      })),
+    };
+    assert_eq!(route, expected);
+    assert_eq!(route.format(), "/shop/fruit/apple/price");
+    assert_eq!(Aisles::try_parse_nested("/fruit/pear/price").unwrap().format(), "/fruit/pear/price");
+  }
+}
+
+mod explicit_prefix_tests {
+  use super::*;
+  use ruled_router::RouterMatch;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/api/:resource")]
+  struct UsersApiRoute {
+    resource: String,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/api/:resource")]
+  struct GenericApiRoute {
+    resource: String,
+  }
+
+  // 两个变体的模式都以 /api 开头，显式前缀让 Users 只负责 /api/users
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum ApiRoutes {
+    #[route(prefix = "/api/users")]
+    Users(UsersApiRoute),
+    Generic(GenericApiRoute),
+  }
+
+  #[test]
+  fn test_explicit_prefix_selects_variant() {
+    let (route, remaining) = ApiRoutes::try_parse_with_remaining("/api/users", 0).unwrap();
+    assert_eq!(
+      route,
+      ApiRoutes::Users(UsersApiRoute {
+        resource: "users".to_string()
+      })
+    );
+    assert_eq!(remaining, "");
+
+    let (route, _) = ApiRoutes::try_parse_with_remaining("/api/posts", 0).unwrap();
+    assert_eq!(
+      route,
+      ApiRoutes::Generic(GenericApiRoute {
+        resource: "posts".to_string()
+      })
+    );
+
+    // 前缀必须在段边界结束
+    let (route, _) = ApiRoutes::try_parse_with_remaining("/api/usersettings", 0).unwrap();
+    assert!(matches!(route, ApiRoutes::Generic(_)));
+  }
+
+  #[test]
+  fn test_explicit_prefix_applies_to_try_parse() {
+    assert!(matches!(ApiRoutes::try_parse("/api/users").unwrap(), ApiRoutes::Users(_)));
+    assert!(matches!(ApiRoutes::try_parse("/api/posts?page=2").unwrap(), ApiRoutes::Generic(_)));
+    assert!(matches!(ApiRoutes::try_parse_nested("/api/users").unwrap(), ApiRoutes::Users(_)));
+    assert!(matches!(ApiRoutes::try_parse_nested("/api/posts").unwrap(), ApiRoutes::Generic(_)));
+  }
+}
+
+mod longest_prefix_tests {
+  use super::*;
+  use ruled_router::RouterMatch;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/user")]
+  struct CurrentUserRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users/:id")]
+  struct UserByIdRoute {
+    id: String,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users/new")]
+  struct NewUserRoute {}
+
+  // 通用的 /users/:id 声明在更具体的 /users/new 之前
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum UserRoutes {
+    Current(CurrentUserRoute),
+    ById(UserByIdRoute),
+    New(NewUserRoute),
+  }
+
+  #[test]
+  fn test_overlapping_prefix_picks_matching_variant() {
+    assert_eq!(
+      UserRoutes::try_parse("/users/1").unwrap(),
+      UserRoutes::ById(UserByIdRoute { id: "1".to_string() })
+    );
+    assert_eq!(UserRoutes::try_parse("/user").unwrap(), UserRoutes::Current(CurrentUserRoute {}));
+  }
+
+  #[test]
+  fn test_longest_literal_prefix_wins_regardless_of_order() {
+    assert_eq!(UserRoutes::try_parse("/users/new").unwrap(), UserRoutes::New(NewUserRoute {}));
+    assert_eq!(
+      UserRoutes::try_parse("/users/new?ref=home").unwrap(),
+      UserRoutes::New(NewUserRoute {})
+    );
+    assert!(UserRoutes::try_parse("/accounts/1").is_err());
+  }
+
+  #[test]
+  fn test_match_with_params_returns_winning_params() {
+    let (route, params) = UserRoutes::match_with_params("/users/42?tab=posts").unwrap();
+    assert_eq!(route, UserRoutes::ById(UserByIdRoute { id: "42".to_string() }));
+    let expected = PathParser::new(UserByIdRoute::pattern()).unwrap().match_path("/users/42").unwrap();
+    assert_eq!(params, expected);
+    assert_eq!(params.get("id"), Some(&"42".to_string()));
+
+    let (route, params) = UserRoutes::match_with_params("/users/new").unwrap();
+    assert_eq!(route, UserRoutes::New(NewUserRoute {}));
+    assert!(params.is_empty());
+
+    assert!(UserRoutes::match_with_params("/accounts/1").is_err());
+  }
+}
+
+mod route_info_tests {
+  use super::*;
+  use ruled_router::traits::ToRouteInfo;
+  use ruled_router::RouterMatch;
+
+  /// 格式化为大写、只能解析小写的参数，format 结果无法再被 parse
+  #[derive(Debug, Clone, PartialEq)]
+  struct ShoutingCode(String);
+
+  impl FromParam for ShoutingCode {
+    fn from_param(param: &str) -> Result<Self, ParseError> {
+      if param.chars().all(|c| c.is_ascii_lowercase()) {
+        Ok(Self(param.to_string()))
+      } else {
+        Err(ParseError::type_conversion(format!("Code must be lowercase: {param}")))
+      }
+    }
+  }
+
+  impl ToParam for ShoutingCode {
+    fn to_param(&self) -> String {
+      self.0.to_uppercase()
+    }
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/teams/:code")]
+  struct TeamRoute {
+    code: ShoutingCode,
+    #[sub_router]
+    sub_router: Option<TeamSubRoutes>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum TeamSubRoutes {
+    Members(MembersRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/members")]
+  struct MembersRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AppRoutes {
+    Team(TeamRoute),
+  }
+
+  #[test]
+  fn test_to_route_info_with_lossy_format() {
+    let route = TeamRoute::parse("/teams/rust/members").unwrap();
+    assert_eq!(route.format(), "/teams/RUST/members");
+    assert!(TeamRoute::parse(&route.format()).is_err());
+
+    let info = route.to_route_info();
+    assert_eq!(info.pattern, "/teams/:code");
+    let sub_info = info.sub_route_info.expect("sub route info read from the field");
+    assert_eq!(sub_info.pattern, "/members");
+
+    let app_info = AppRoutes::Team(route).to_route_info();
+    let sub_info = app_info.sub_route_info.expect("sub route info through RouterMatch");
+    assert_eq!(sub_info.pattern, "/members");
+  }
+
+  #[test]
+  fn test_to_route_info_without_sub_route() {
+    let route = TeamRoute::parse("/teams/rust").unwrap();
+    assert!(route.to_route_info().sub_route_info.is_none());
+    assert!(MembersRoute {}.to_route_info().sub_route_info.is_none());
+  }
+}
+
+mod wildcard_tests {
+  use super::*;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/assets/*rest")]
+  struct AssetRoute {
+    rest: String,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/repos/:owner/*path")]
+  struct RepoFileRoute {
+    owner: String,
+    path: String,
+  }
+
+  #[test]
+  fn test_wildcard_captures_remaining_path() {
+    let route = AssetRoute::parse("/assets/css/site.css").unwrap();
+    assert_eq!(
+      route,
+      AssetRoute {
+        rest: "css/site.css".to_string()
+      }
+    );
+    assert_eq!(route.format(), "/assets/css/site.css");
+
+    let route = RepoFileRoute::parse("/repos/respo/src/lib.rs?raw=1").unwrap();
+    assert_eq!(route.owner, "respo");
+    assert_eq!(route.path, "src/lib.rs");
+    assert_eq!(RepoFileRoute::parse(&route.format()).unwrap(), route);
+  }
+
+  #[test]
+  fn test_wildcard_field_encoding_roundtrip() {
+    let route = AssetRoute {
+      rest: "img/hello world.png".to_string(),
+    };
+    assert_eq!(route.format(), "/assets/img/hello%20world.png");
+    assert_eq!(AssetRoute::parse(&route.format()).unwrap(), route);
+  }
+}
+
+mod optional_param_tests {
+  use super::*;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/api/:version/users/:id?:format")]
+  struct ApiUserRoute {
+    version: String,
+    id: u32,
+    format: Option<String>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/docs/?:page")]
+  struct DocsRoute {
+    page: Option<u32>,
+  }
+
+  #[test]
+  fn test_optional_param_present() {
+    let route = ApiUserRoute::parse("/api/v1/users/7/json").unwrap();
+    assert_eq!(route.version, "v1");
+    assert_eq!(route.id, 7);
+    assert_eq!(route.format, Some("json".to_string()));
+    assert_eq!(route.format(), "/api/v1/users/7/json");
+
+    assert_eq!(DocsRoute::parse("/docs/3").unwrap(), DocsRoute { page: Some(3) });
+    assert!(DocsRoute::parse("/docs/intro").is_err());
+  }
+
+  #[test]
+  fn test_optional_param_absent() {
+    let route = ApiUserRoute::parse("/api/v1/users/7").unwrap();
+    assert_eq!(route.format, None);
+    assert_eq!(route.format(), "/api/v1/users/7");
+
+    let docs = DocsRoute::parse("/docs").unwrap();
+    assert_eq!(docs, DocsRoute { page: None });
+    assert_eq!(docs.format(), "/docs");
+  }
+}
+
+mod tuple_struct_tests {
+  use super::*;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/u/:id")]
+  struct UserRoute(u32);
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/posts/:year/:slug")]
+  struct PostRoute(u32, String);
+
+  #[test]
+  fn test_newtype_route() {
+    let route = UserRoute::parse("/u/42").unwrap();
+    assert_eq!(route, UserRoute(42));
+    assert_eq!(route.format(), "/u/42");
+    assert!(UserRoute::parse("/u/abc").is_err());
+  }
+
+  #[test]
+  fn test_tuple_fields_bind_by_position() {
+    let route = PostRoute::parse("/posts/2024/hello-world").unwrap();
+    assert_eq!(route, PostRoute(2024, "hello-world".to_string()));
+    assert_eq!(route.format(), "/posts/2024/hello-world");
+
+    let roundtrip = PostRoute::parse(&route.format()).unwrap();
+    assert_eq!(roundtrip, route);
+  }
+}
+
+mod std_traits_tests {
+  use super::*;
+
+  #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+  struct ArchiveQuery {
+    page: Option<u32>,
+  }
+
+  /// std_traits 单独写在另一个属性中
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/archive/:year")]
+  #[router(std_traits)]
+  struct ArchiveRoute {
+    year: u32,
+    #[query]
+    query: ArchiveQuery,
+  }
+
+  #[test]
+  fn test_from_str() {
+    let route: UserRoute = "/users/123".parse().unwrap();
+    assert_eq!(route, UserRoute { id: 123 });
+    assert_eq!("/users/123".parse::<UserRoute>().unwrap(), route);
+
+    let err = "/users/abc".parse::<UserRoute>().unwrap_err();
+    assert!(matches!(err, ParseError::TypeConversionWithSource { .. }));
+    assert!("/posts/1".parse::<UserRoute>().is_err());
+  }
+
+  #[test]
+  fn test_display_matches_format() {
+    let route = UserRoute { id: 42 };
+    assert_eq!(route.to_string(), route.format());
+    assert_eq!(format!("{route}"), "/users/42");
+
+    let archive: ArchiveRoute = "/archive/2024?page=3".parse().unwrap();
+    assert_eq!(archive.year, 2024);
+    assert_eq!(archive.query.page, Some(3));
+    assert_eq!(archive.to_string(), archive.format());
+    assert_eq!(archive.to_string(), "/archive/2024?page=3");
+  }
+}
+
+/// 从参数映射构建路由的测试
+mod from_params_tests {
+  use ruled_router::prelude::*;
+  use std::collections::HashMap;
+
+  #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+  struct DetailQuery {
+    #[query(default = "1")]
+    page: u32,
+    format: Option<String>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/detail/:category/:id")]
+  struct ProductDetailRoute {
+    category: String,
+    id: u32,
+    #[query]
+    query: DetailQuery,
+  }
+
+  fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+  }
+
+  #[test]
+  fn test_from_params_builds_route_with_default_query() {
+    let route = ProductDetailRoute::from_params(params(&[("category", "books"), ("id", "5")])).unwrap();
+    assert_eq!(
+      route,
+      ProductDetailRoute {
+        category: "books".to_string(),
+        id: 5,
+        query: DetailQuery { page: 1, format: None },
+      }
+    );
+
+    // 与 match_path 互为逆操作，需要编码的值同样能往返
+    let route = ProductDetailRoute::from_params(params(&[("category", "sci fi"), ("id", "7"), ("extra", "x")])).unwrap();
+    assert_eq!(route.category, "sci fi");
+    let formatted = route.format();
+    let (path, _) = ruled_router::utils::split_path_query(&formatted);
+    let matched = PathParser::new(ProductDetailRoute::pattern()).unwrap().match_path(path).unwrap();
+    assert_eq!(ProductDetailRoute::from_params(matched).unwrap(), route);
+  }
+
+  #[test]
+  fn test_from_params_errors() {
+    assert_eq!(
+      ProductDetailRoute::from_params(params(&[("category", "books")])).unwrap_err(),
+      ParseError::missing_parameter("id")
+    );
+    assert!(matches!(
+      ProductDetailRoute::from_params(params(&[("category", "books"), ("id", "five")])),
+      Err(ParseError::TypeConversionWithSource { .. })
+    ));
+  }
+}
+
+/// 别名模式测试：`#[router(alias = "...")]`
+mod alias_tests {
+  use ruled_router::prelude::*;
+  use ruled_router::RouterMatch;
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users/:id", alias = "/u/:id", alias = "/members/:id/profile")]
+  struct AliasedUserRoute {
+    id: u32,
+    #[query]
+    query: AliasQuery,
+  }
+
+  #[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+  struct AliasQuery {
+    tab: Option<String>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/posts")]
+  struct PostsRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AliasedApp {
+    Users(AliasedUserRoute),
+    Posts(PostsRoute),
+  }
+
+  #[test]
+  fn test_alias_parses_into_same_struct() {
+    let canonical = AliasedUserRoute::parse("/users/42?tab=posts").unwrap();
+    assert_eq!(AliasedUserRoute::parse("/u/42?tab=posts").unwrap(), canonical);
+    assert_eq!(AliasedUserRoute::parse("/members/42/profile?tab=posts").unwrap(), canonical);
+    assert_eq!(canonical.id, 42);
+
+    assert_eq!(AliasedUserRoute::aliases(), &["/u/:id", "/members/:id/profile"]);
+    assert!(AliasedUserRoute::parse("/members/42").is_err());
+    assert!(AliasedUserRoute::parse("/u/abc").is_err());
+  }
+
+  #[test]
+  fn test_alias_formats_to_primary_pattern() {
+    let route = AliasedUserRoute::parse("/u/7").unwrap();
+    assert_eq!(route.format(), "/users/7");
+    assert_eq!(
+      AliasedUserRoute::parse("/members/7/profile?tab=a").unwrap().format(),
+      "/users/7?tab=a"
+    );
+  }
+
+  #[test]
+  fn test_alias_through_router_match() {
+    let route = AliasedApp::try_parse("/u/5").unwrap();
+    assert!(matches!(&route, AliasedApp::Users(user) if user.id == 5));
+    assert_eq!(route.format(), "/users/5");
+    assert!(matches!(
+      AliasedApp::try_parse_nested("/members/5/profile"),
+      Ok(AliasedApp::Users(_))
+    ));
+    assert!(matches!(AliasedApp::try_parse("/posts"), Ok(AliasedApp::Posts(_))));
+  }
+
+  #[test]
+  fn test_path_parser_caches_follow_pattern_order() {
+    let caches = AliasedUserRoute::path_parser_caches();
+    let patterns: Vec<&str> = caches.iter().map(|cache| cache.pattern()).collect();
+    assert_eq!(patterns, vec!["/users/:id", "/u/:id", "/members/:id/profile"]);
+
+    // 每次调用返回同一个编译后的解析器
+    let first = AliasedUserRoute::path_parser_caches()[1].get().unwrap();
+    assert!(std::ptr::eq(first, caches[1].get().unwrap()));
+  }
+}
+
+/// 使用自定义错误类型的参数测试
+mod custom_param_error_tests {
+  use ruled_router::prelude::*;
+  use std::fmt;
+
+  /// 订单号：以 "ord_" 开头
+  #[derive(Debug, Clone, PartialEq)]
+  struct OrderId(String);
+
+  /// 订单号格式错误
+  #[derive(Debug, Clone, PartialEq)]
+  struct InvalidOrderId(String);
+
+  impl fmt::Display for InvalidOrderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(f, "'{}' is not an order id", self.0)
+    }
+  }
+
+  impl std::error::Error for InvalidOrderId {}
+
+  impl From<InvalidOrderId> for ParseError {
+    fn from(err: InvalidOrderId) -> Self {
+      ParseError::type_conversion_with_source(err.to_string(), err)
+    }
+  }
+
+  impl TryFromParam for OrderId {
+    type Error = InvalidOrderId;
+
+    fn try_from_param(param: &str) -> Result<Self, InvalidOrderId> {
+      match param.strip_prefix("ord_") {
+        Some(rest) if !rest.is_empty() => Ok(OrderId(param.to_string())),
+        _ => Err(InvalidOrderId(param.to_string())),
+      }
+    }
+  }
+
+  impl ToParam for OrderId {
+    fn to_param(&self) -> String {
+      self.0.clone()
+    }
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/orders/:id")]
+  struct OrderRoute {
+    id: OrderId,
+  }
+
+  #[test]
+  fn test_custom_error_converts_to_parse_error() {
+    assert_eq!(OrderId::try_from_param("42"), Err(InvalidOrderId("42".to_string())));
+
+    // 通用实现把自定义错误转换为 ParseError，并保留原始错误作为错误源
+    let error = OrderId::from_param("42").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::TypeConversion);
+    let source = std::error::Error::source(&error).unwrap();
+    assert_eq!(source.downcast_ref::<InvalidOrderId>(), Some(&InvalidOrderId("42".to_string())));
+  }
+
+  #[test]
+  fn test_custom_error_param_in_route() {
+    let route = OrderRoute::parse("/orders/ord_abc").unwrap();
+    assert_eq!(route.id, OrderId("ord_abc".to_string()));
+    assert_eq!(route.format(), "/orders/ord_abc");
+
+    let error = OrderRoute::parse("/orders/abc").unwrap_err();
+    assert!(error.to_string().contains("'abc' is not an order id"));
+  }
 }
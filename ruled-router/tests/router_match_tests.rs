@@ -87,6 +87,49 @@ enum SubRouterMatch {
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_openapi_paths() {
+    use ruled_router::traits::OpenApiPath;
+
+    let paths = AppRouterMatch::openapi_paths();
+    assert_eq!(
+      paths,
+      vec![
+        OpenApiPath {
+          path: "/users/{id}".to_string(),
+          parameters: vec!["id".to_string()],
+        },
+        OpenApiPath {
+          path: "/products/{category}/{id}".to_string(),
+          parameters: vec!["category".to_string(), "id".to_string()],
+        },
+        OpenApiPath {
+          path: "/settings".to_string(),
+          parameters: vec![],
+        },
+        OpenApiPath {
+          path: "/api/{version}".to_string(),
+          parameters: vec!["version".to_string()],
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_openapi_path_from_pattern() {
+    use ruled_router::traits::OpenApiPath;
+
+    let path = OpenApiPath::from_pattern("/files/:id/?:format/*path").unwrap();
+    assert_eq!(path.path, "/files/{id}/{format}/{path}");
+    assert_eq!(path.parameters, vec!["id", "format", "path"]);
+
+    let root = OpenApiPath::from_pattern("/").unwrap();
+    assert_eq!(root.path, "/");
+    assert!(root.parameters.is_empty());
+
+    assert!(OpenApiPath::from_pattern("/files/*").is_err());
+  }
+
   #[test]
   fn test_router_match_format_user_route() {
     // 测试用户路由的格式化
@@ -469,4 +512,307 @@ mod tests {
       "Should not contain query separator when query is empty"
     );
   }
+
+  #[test]
+  fn test_router_match_format_with_query() {
+    let without_query = AppRouterMatch::User(UserRoute {
+      id: 7,
+      query: UserQuery::default(),
+    });
+    assert_eq!(without_query.format_with_query(&[("ref", "email")]), "/users/7?ref=email");
+
+    let with_query = AppRouterMatch::User(UserRoute {
+      id: 7,
+      query: UserQuery {
+        page: Some(2),
+        limit: None,
+      },
+    });
+    assert_eq!(
+      with_query.format_with_query(&[("ref", "email"), ("utm", "a b")]),
+      "/users/7?page=2&ref=email&utm=a%20b"
+    );
+  }
+
+  #[test]
+  fn test_router_match_format_pretty_with_query() {
+    let route_match = AppRouterMatch::User(UserRoute {
+      id: 123,
+      query: UserQuery {
+        page: Some(1),
+        limit: Some(20),
+      },
+    });
+    assert_eq!(route_match.format_pretty(), "path: /users/123 | query: page=1&limit=20");
+  }
+
+  #[test]
+  fn test_try_parse_under_runtime_prefix() {
+    // 同一组路由挂载在不同的前缀下
+    let expected = AppRouterMatch::try_parse("/users/7?page=2").unwrap();
+    assert_eq!(AppRouterMatch::try_parse_under("/v1", "/v1/users/7?page=2").unwrap(), expected);
+    assert_eq!(AppRouterMatch::try_parse_under("/v2/", "/v2/users/7?page=2").unwrap(), expected);
+    assert!(matches!(
+      AppRouterMatch::try_parse_under("/v2", "/v2/settings").unwrap(),
+      AppRouterMatch::Settings(_)
+    ));
+
+    // 前缀必须在段边界结束
+    assert!(AppRouterMatch::try_parse_under("/v1", "/v2/users/7").is_err());
+    assert!(AppRouterMatch::try_parse_under("/v1", "/v10/users/7").is_err());
+    assert!(AppRouterMatch::try_parse_under("/v1", "/v1").is_err());
+  }
+}
+
+/// 与 examples/debug_format_example.rs 相同的三层路由结构
+mod full_patterns_tests {
+  use ruled_router::error::RouteState;
+  use ruled_router::RouteMatcher;
+  use ruled_router_derive::{RouterData, RouterMatch};
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/basic/:id")]
+  struct UserBasicInfoRoute {
+    id: u32,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/settings")]
+  struct UserSettingsRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/posts/:id")]
+  struct UserPostRoute {
+    id: u32,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/comments/:id")]
+  struct UserCommentRoute {
+    id: u32,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/manage")]
+  struct AdminUserManageRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/config")]
+  struct SystemConfigRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum UserProfileDetailRouterMatch {
+    BasicInfo(UserBasicInfoRoute),
+    Settings(UserSettingsRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum UserContentDetailRouterMatch {
+    Post(UserPostRoute),
+    Comment(UserCommentRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AdminUserDetailRouterMatch {
+    Manage(AdminUserManageRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AdminSystemDetailRouterMatch {
+    Config(SystemConfigRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/profile")]
+  struct UserProfileCategoryRoute {
+    #[sub_router]
+    sub_router: RouteState<UserProfileDetailRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/content")]
+  struct UserContentCategoryRoute {
+    #[sub_router]
+    sub_router: RouteState<UserContentDetailRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users")]
+  struct AdminUserCategoryRoute {
+    #[sub_router]
+    sub_router: RouteState<AdminUserDetailRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/system")]
+  struct AdminSystemCategoryRoute {
+    #[sub_router]
+    sub_router: RouteState<AdminSystemDetailRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum UserSubRouterMatch {
+    Profile(UserProfileCategoryRoute),
+    Content(UserContentCategoryRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AdminSubRouterMatch {
+    Users(AdminUserCategoryRoute),
+    System(AdminSystemCategoryRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/users")]
+  struct UserModuleRoute {
+    #[sub_router]
+    sub_router: RouteState<UserSubRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/admin")]
+  struct AdminModuleRoute {
+    #[sub_router]
+    sub_router: RouteState<AdminSubRouterMatch>,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AppRouterMatch {
+    User(UserModuleRoute),
+    Admin(AdminModuleRoute),
+  }
+
+  #[test]
+  fn test_all_full_patterns() {
+    assert_eq!(
+      AppRouterMatch::all_full_patterns(),
+      vec![
+        "/users/profile/basic/:id",
+        "/users/profile/settings",
+        "/users/content/posts/:id",
+        "/users/content/comments/:id",
+        "/admin/users/manage",
+        "/admin/system/config",
+      ]
+    );
+
+    // 中间层的匹配器只包含自己以下的部分
+    assert_eq!(AdminSubRouterMatch::all_full_patterns(), vec!["/users/manage", "/system/config"]);
+    assert!(ruled_router::NoSubRouter::all_full_patterns().is_empty());
+  }
+
+  #[test]
+  fn test_format_pretty() {
+    // 两层：当前层级的路径和子路由的路径分开显示
+    let sub_match = UserSubRouterMatch::try_parse("/profile/basic/7").unwrap();
+    assert_eq!(sub_match.format_pretty(), "path: /profile | sub: /basic/7");
+
+    let app_match = AppRouterMatch::try_parse("/users/profile/basic/7").unwrap();
+    assert_eq!(app_match.format_pretty(), "path: /users | sub: /profile/basic/7");
+
+    // 没有子路由时只有路径
+    let app_match = AppRouterMatch::try_parse("/admin").unwrap();
+    assert_eq!(app_match.format_pretty(), "path: /admin");
+  }
+}
+
+/// 变体直接包装另一个 RouteMatcher 的测试
+mod nested_matcher_tests {
+  use ruled_router::traits::{RouteMatcher, RouteTree, ToRouteInfo};
+  use ruled_router_derive::{RouterData, RouterMatch};
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/")]
+  struct HomeRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/admin/:section")]
+  struct AdminSectionRoute {
+    section: String,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/admin/users")]
+  struct AdminUsersRoute {}
+
+  #[derive(Debug, Clone, PartialEq, RouterData)]
+  #[router(pattern = "/admin/users/:id")]
+  struct AdminUserRoute {
+    id: u32,
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AdminMatch {
+    User(AdminUserRoute),
+    Users(AdminUsersRoute),
+  }
+
+  #[derive(Debug, Clone, PartialEq, RouterMatch)]
+  enum AppMatch {
+    Home(HomeRoute),
+    Section(AdminSectionRoute),
+    #[route(matcher)]
+    Admin(AdminMatch),
+  }
+
+  #[test]
+  fn test_parse_into_wrapped_matcher() {
+    assert_eq!(
+      AppMatch::try_parse("/admin/users/5").unwrap(),
+      AppMatch::Admin(AdminMatch::User(AdminUserRoute { id: 5 }))
+    );
+    // 嵌套匹配器中更具体的模式优先于当前层级的参数模式
+    assert_eq!(
+      AppMatch::try_parse("/admin/users").unwrap(),
+      AppMatch::Admin(AdminMatch::Users(AdminUsersRoute {}))
+    );
+    assert_eq!(
+      AppMatch::try_parse("/admin/billing").unwrap(),
+      AppMatch::Section(AdminSectionRoute {
+        section: "billing".to_string()
+      })
+    );
+    assert_eq!(AppMatch::try_parse("/").unwrap(), AppMatch::Home(HomeRoute {}));
+
+    assert_eq!(
+      AppMatch::try_parse_nested("/admin/users/7").unwrap(),
+      AppMatch::Admin(AdminMatch::User(AdminUserRoute { id: 7 }))
+    );
+    let (route, params) = AppMatch::match_with_params("/admin/users/9").unwrap();
+    assert_eq!(route, AppMatch::Admin(AdminMatch::User(AdminUserRoute { id: 9 })));
+    assert_eq!(params.get("id").map(String::as_str), Some("9"));
+  }
+
+  #[test]
+  fn test_match_score_covers_wrapped_matcher() {
+    // 嵌套匹配器的得分取其中最具体的可匹配模式
+    assert_eq!(AdminMatch::match_score("/admin/users/5"), Some("/admin/users".len()));
+    assert_eq!(AppMatch::match_score("/admin/billing"), Some("/admin".len()));
+    assert_eq!(AppMatch::match_score("/admin/users"), Some("/admin/users".len()));
+    assert_eq!(AdminMatch::match_score("/shop"), None);
+  }
+
+  #[test]
+  fn test_wrapped_matcher_format_and_info() {
+    let route = AppMatch::Admin(AdminMatch::User(AdminUserRoute { id: 3 }));
+    assert_eq!(route.format(), "/admin/users/3");
+    assert_eq!(AppMatch::try_parse(&route.format()).unwrap(), route);
+    assert_eq!(route.to_route_info().pattern, "/admin/users/:id");
+    assert_eq!(route.format_pretty(), "path: /admin/users/3");
+    assert!(route.debug_format(0).starts_with("AppMatch::Admin\n  AdminMatch::User"));
+  }
+
+  #[test]
+  fn test_wrapped_matcher_contributes_patterns() {
+    assert_eq!(
+      AppMatch::patterns(),
+      vec!["/", "/admin/:section", "/admin/users/:id", "/admin/users"]
+    );
+
+    let leaf = |pattern| RouteTree { pattern, children: vec![] };
+    assert_eq!(
+      AppMatch::describe().children,
+      vec![leaf("/"), leaf("/admin/:section"), leaf("/admin/users/:id"), leaf("/admin/users")]
+    );
+  }
 }
@@ -0,0 +1,111 @@
+//! routes! 宏测试
+//!
+//! 测试由路由树字面量生成的 RouterData 结构体和 RouterMatch 枚举
+
+use ruled_router::prelude::*;
+use ruled_router::routes;
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct ListQuery {
+  page: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct DetailQuery {
+  tab: Option<String>,
+}
+
+routes! {
+  /// 应用的顶层路由
+  pub enum AppRoute {
+    "/" => Home,
+    "/users" => Users(#[query] query: ListQuery) {
+      "/:id" => UserDetail(pub id: u32, #[query] query: DetailQuery) {
+        "/posts/:post_id" => UserPost(post_id: u64),
+        "/settings" => UserSettings,
+      },
+    },
+    /// 商店模块
+    "/shop" => Shop {
+      "/products/:slug" => Product(slug: String),
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_generated_types() {
+    // 直接构造生成的类型，确认结构体字段和枚举变体的形状
+    let route = AppRoute::Users(Users {
+      query: ListQuery { page: Some(2) },
+      sub_router: Some(UsersMatch::UserDetail(UserDetail {
+        id: 7,
+        query: DetailQuery::default(),
+        sub_router: Some(UserDetailMatch::UserSettings(UserSettings {})),
+      })),
+    });
+    assert_eq!(route.format(), "/users/7/settings?page=2");
+
+    assert_eq!(Home::pattern(), "/");
+    assert_eq!(UserPost::pattern(), "/posts/:post_id");
+    assert_eq!(AppRoute::patterns(), vec!["/", "/users", "/shop"]);
+    assert_eq!(UsersMatch::patterns(), vec!["/:id"]);
+  }
+
+  #[test]
+  fn test_parse_end_to_end() {
+    let route = AppRoute::try_parse("/users/42/posts/9?tab=activity").unwrap();
+    assert_eq!(
+      route,
+      AppRoute::Users(Users {
+        query: ListQuery::default(),
+        sub_router: Some(UsersMatch::UserDetail(UserDetail {
+          id: 42,
+          query: DetailQuery {
+            tab: Some("activity".to_string())
+          },
+          sub_router: Some(UserDetailMatch::UserPost(UserPost { post_id: 9 })),
+        })),
+      })
+    );
+
+    // 子路由可选，路径停在中间节点时为 None
+    assert_eq!(
+      AppRoute::try_parse("/users?page=3").unwrap(),
+      AppRoute::Users(Users {
+        query: ListQuery { page: Some(3) },
+        sub_router: None,
+      })
+    );
+    assert_eq!(AppRoute::try_parse("/").unwrap(), AppRoute::Home(Home {}));
+    assert_eq!(
+      AppRoute::try_parse("/shop/products/red-shoes").unwrap(),
+      AppRoute::Shop(Shop {
+        sub_router: Some(ShopMatch::Product(Product {
+          slug: "red-shoes".to_string()
+        })),
+      })
+    );
+    // 严格解析时无法匹配的子路径返回错误
+    assert!(AppRoute::try_parse_nested("/users/abc").is_err());
+    assert!(Users::parse("/users/abc").is_err());
+
+    // 非严格解析同样不会丢弃无法消费的剩余路径
+    assert!(AppRoute::try_parse("/users/abc").is_err());
+    assert!(AppRoute::try_parse("/users/1/extra").is_err());
+    assert!(AppRoute::try_parse("/shop/products/hat/garbage").is_err());
+    assert!(UsersMatch::try_parse("/123/garbage").is_err());
+  }
+
+  #[test]
+  fn test_format_round_trip() {
+    for path in ["/", "/users", "/users/42", "/users/42/settings", "/shop/products/hat"] {
+      let route = AppRoute::try_parse(path).unwrap();
+      assert_eq!(route.format(), path);
+      assert_eq!(AppRoute::try_parse(&route.format()).unwrap(), route);
+    }
+  }
+}
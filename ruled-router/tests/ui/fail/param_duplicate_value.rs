@@ -0,0 +1,12 @@
+use ruled_router::Param;
+
+#[derive(Param)]
+#[param(case_insensitive)]
+enum Mode {
+  #[param(rename = "fast")]
+  Fast,
+  #[param(rename = "FAST")]
+  Turbo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ruled_router::Param;
+
+#[derive(Param)]
+enum Value {
+  Empty,
+  Number(u32),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ruled_router::prelude::*;
+
+#[derive(QueryDerive)]
+struct SearchQuery {
+  #[query(name = "q")]
+  keyword: Option<String>,
+  q: Option<String>,
+}
+
+fn main() {}
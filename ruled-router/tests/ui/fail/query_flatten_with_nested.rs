@@ -0,0 +1,14 @@
+use ruled_router::prelude::*;
+
+#[derive(Clone, Default, QueryDerive)]
+struct PaginationQuery {
+  page: Option<u32>,
+}
+
+#[derive(Clone, QueryDerive)]
+struct SearchQuery {
+  #[query(flatten, nested)]
+  pagination: PaginationQuery,
+}
+
+fn main() {}
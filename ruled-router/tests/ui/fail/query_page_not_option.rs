@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(Clone, QueryDerive)]
+struct ListQuery {
+  #[query(page)]
+  page: u32,
+}
+
+fn main() {}
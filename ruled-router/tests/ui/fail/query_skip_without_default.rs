@@ -0,0 +1,12 @@
+use ruled_router::prelude::*;
+
+struct Timestamp(u64);
+
+#[derive(QueryDerive)]
+struct EventQuery {
+  since: Option<u64>,
+  #[query(skip)]
+  parsed_since: Timestamp,
+}
+
+fn main() {}
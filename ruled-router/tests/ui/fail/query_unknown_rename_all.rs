@@ -0,0 +1,9 @@
+use ruled_router::QueryDerive;
+
+#[derive(QueryDerive)]
+#[query(rename_all = "PascalCase")]
+struct SortQuery {
+  sort_by: Option<String>,
+}
+
+fn main() {}
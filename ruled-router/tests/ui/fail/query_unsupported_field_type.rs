@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use ruled_router::prelude::*;
+
+#[derive(QueryDerive)]
+struct FilterQuery {
+  q: Option<String>,
+  ranges: HashMap<String, u32>,
+}
+
+#[derive(QueryDerive)]
+struct PairQuery {
+  bounds: Option<(u32, u32)>,
+}
+
+#[derive(QueryDerive)]
+struct MatrixQuery {
+  rows: Vec<Vec<u32>>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(Clone, QueryDerive)]
+struct TagQuery {
+  #[query(max_len = 10)]
+  tags: Vec<String>,
+}
+
+fn main() {}
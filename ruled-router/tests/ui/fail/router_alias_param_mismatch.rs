@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/users/:id", alias = "/u/:user_id")]
+struct UserRoute {
+  id: u32,
+}
+
+fn main() {}
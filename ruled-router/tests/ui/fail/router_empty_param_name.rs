@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/users/:")]
+struct UserRoute {
+  id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/users/:userId")]
+struct UserRoute {
+  user_id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/docs/?:page")]
+struct DocsRoute {
+  page: u32,
+}
+
+fn main() {}
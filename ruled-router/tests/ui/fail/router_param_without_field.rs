@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/users/:id/posts/:post_id")]
+struct UserPostRoute {
+  id: u32,
+}
+
+fn main() {}
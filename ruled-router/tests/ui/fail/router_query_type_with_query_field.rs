@@ -0,0 +1,15 @@
+use ruled_router::prelude::*;
+
+#[derive(Default, QueryDerive)]
+struct SearchQuery {
+  q: Option<String>,
+}
+
+#[derive(RouterData)]
+#[router(pattern = "/search", query = SearchQuery)]
+struct SearchRoute {
+  #[query]
+  query: SearchQuery,
+}
+
+fn main() {}
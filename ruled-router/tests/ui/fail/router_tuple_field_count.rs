@@ -0,0 +1,7 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/posts/:year/:slug")]
+struct PostRoute(u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(RouterData)]
+#[router(pattern = "/tags/:tags")]
+struct TagsRoute {
+  tags: Vec<String>,
+}
+
+fn main() {}
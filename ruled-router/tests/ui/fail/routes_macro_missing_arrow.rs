@@ -0,0 +1,9 @@
+use ruled_router::routes;
+
+routes! {
+  enum AppRoute {
+    "/users" Users,
+  }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use ruled_router::QuerySerde;
+
+#[derive(QuerySerde)]
+struct TagsQuery {
+  tags: Vec<String>,
+}
+
+#[derive(QuerySerde)]
+struct OptionalIdsQuery {
+  ids: Option<std::collections::BTreeSet<u32>>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ruled_router::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = r"/posts/:id(\d+)")]
+struct PostByIdRoute {
+  id: u32,
+}
+
+fn main() {}
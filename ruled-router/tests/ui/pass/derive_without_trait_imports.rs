@@ -0,0 +1,31 @@
+// 派生宏生成的代码不依赖调用方导入任何 trait
+
+#[derive(Debug, Clone, PartialEq, Default, ruled_router::QueryDerive)]
+struct PageQuery {
+  page: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, ruled_router::RouterData)]
+#[route(pattern = "/items/:id")]
+struct ItemRoute {
+  id: u32,
+  #[query]
+  query: PageQuery,
+}
+
+#[derive(Debug, Clone, PartialEq, ruled_router::RouterMatch)]
+enum ItemRouterMatch {
+  Item(ItemRoute),
+}
+
+#[derive(Debug, Clone, PartialEq, ruled_router::RouterData)]
+#[router(pattern = "/shop")]
+struct ShopRoute {
+  #[sub_router]
+  sub_router: Option<ItemRouterMatch>,
+}
+
+fn main() {
+  let route = <ShopRoute as ruled_router::traits::RouterData>::parse("/shop/items/3?page=2").unwrap();
+  assert_eq!(ruled_router::traits::RouterData::format(&route), "/shop/items/3?page=2");
+}
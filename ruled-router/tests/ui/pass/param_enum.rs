@@ -0,0 +1,25 @@
+use ruled_router::traits::{FromParam, ToParam};
+use ruled_router::Param;
+
+#[derive(Debug, PartialEq, Param)]
+enum Status {
+  Active,
+  #[param(rename = "on-hold")]
+  OnHold,
+}
+
+#[derive(Debug, PartialEq, Param)]
+#[param(case_insensitive)]
+enum Theme {
+  #[param(rename = "light")]
+  Light,
+  #[param(rename = "dark")]
+  Dark,
+}
+
+fn main() {
+  assert_eq!(Status::from_param("Active").unwrap(), Status::Active);
+  assert_eq!(Status::OnHold.to_param(), "on-hold");
+  assert_eq!(Theme::from_param("DARK").unwrap(), Theme::Dark);
+  assert_eq!(Theme::Light.to_param(), "light");
+}
@@ -0,0 +1,20 @@
+use ruled_router::prelude::*;
+use ruled_router::parser::CommaSeparated;
+
+#[derive(Debug, PartialEq, Default, QueryDerive)]
+struct ShapesQuery {
+  q: Option<String>,
+  tags: Vec<String>,
+  page: u32,
+  ids: CommaSeparated<u64>,
+  maybe_list: Option<CommaSeparated<u32>>,
+  #[query(skip)]
+  cache: std::collections::HashMap<String, u32>,
+}
+
+fn main() {
+  let query = ShapesQuery::parse("q=rust&tags=a&tags=b&page=2&ids=1,2&maybe_list=3,4").unwrap();
+  assert_eq!(query.page, 2);
+  assert_eq!(query.tags, vec!["a".to_string(), "b".to_string()]);
+  assert!(query.cache.is_empty());
+}
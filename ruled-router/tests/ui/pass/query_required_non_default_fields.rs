@@ -0,0 +1,33 @@
+// 必需字段的类型不需要实现 Default，未标注 #[query(lenient)] 时不生成逐字段的宽松解析
+use std::num::NonZeroU32;
+
+use ruled_router::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Region(String);
+
+impl FromParam for Region {
+  fn from_param(param: &str) -> Result<Self, ParseError> {
+    Ok(Region(param.to_string()))
+  }
+}
+
+impl ToParam for Region {
+  fn to_param(&self) -> String {
+    self.0.clone()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, QueryDerive)]
+struct ShipmentQuery {
+  region: Region,
+  count: NonZeroU32,
+  note: Option<String>,
+}
+
+fn main() {
+  let query = ShipmentQuery::parse("region=eu&count=3").unwrap();
+  assert_eq!(query.region, Region("eu".to_string()));
+  assert_eq!(query.count.get(), 3);
+  assert_eq!(query.format(), "region=eu&count=3");
+}
@@ -0,0 +1,52 @@
+use ruled_router::traits::Query;
+use ruled_router::QueryDerive;
+
+mod codecs {
+  pub mod hex {
+    use ruled_router::error::ParseError;
+
+    pub fn parse(value: &str) -> Result<Vec<u8>, ParseError> {
+      (0..value.len())
+        .step_by(2)
+        .map(|index| {
+          value
+            .get(index..index + 2)
+            .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            .ok_or_else(|| ParseError::type_conversion(format!("Invalid hex value '{value}'")))
+        })
+        .collect()
+    }
+
+    pub fn format(bytes: &Vec<u8>) -> String {
+      bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+  }
+}
+
+mod upper {
+  use ruled_router::error::ParseError;
+
+  pub fn parse(value: &str) -> Result<String, ParseError> {
+    Ok(value.to_uppercase())
+  }
+
+  pub fn format(value: &String) -> String {
+    value.to_lowercase()
+  }
+}
+
+#[derive(Debug, PartialEq, Default, QueryDerive)]
+struct BlobQuery {
+  #[query(with = "codecs::hex")]
+  blob: Vec<u8>,
+
+  #[query(with = "upper", default = "en")]
+  lang: String,
+}
+
+fn main() {
+  let query = BlobQuery::parse("blob=ff00").unwrap();
+  assert_eq!(query.blob, vec![0xff, 0x00]);
+  assert_eq!(query.lang, "EN");
+  assert_eq!(query.format(), "blob=ff00&lang=en");
+}
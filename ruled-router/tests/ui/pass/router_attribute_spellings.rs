@@ -0,0 +1,26 @@
+use ruled_router::{Router, RouterData};
+
+// 规范写法：#[derive(RouterData)] + #[router(...)]
+#[derive(Debug, PartialEq, RouterData)]
+#[router(pattern = "/users/:id")]
+struct UserRoute {
+  id: u32,
+}
+
+// 别名写法：#[derive(Router)] + #[route(...)]
+#[derive(Debug, PartialEq, Router)]
+#[route(pattern = "/blog/:slug")]
+struct BlogRoute {
+  slug: String,
+}
+
+// 两种写法实现的是同一个 trait
+fn parse_with_alias<T: Router>(path: &str) -> T {
+  T::parse(path).unwrap()
+}
+
+fn main() {
+  assert_eq!(parse_with_alias::<UserRoute>("/users/7"), UserRoute { id: 7 });
+  assert_eq!(<BlogRoute as RouterData>::parse("/blog/hi").unwrap().format(), "/blog/hi");
+  assert_eq!(<BlogRoute as Router>::pattern(), "/blog/:slug");
+}
@@ -0,0 +1,16 @@
+use ruled_router::prelude::*;
+
+// 单字段 newtype 路由：字段按位置绑定到 `:id`
+#[derive(Debug, PartialEq, RouterData)]
+#[router(pattern = "/u/:id")]
+struct UserRoute(u32);
+
+// 多字段元组结构体：按声明顺序依次绑定模式参数
+#[derive(Debug, PartialEq, RouterData)]
+#[router(pattern = "/posts/:year/:slug")]
+struct PostRoute(u32, String);
+
+fn main() {
+  assert_eq!(UserRoute::parse("/u/7").unwrap(), UserRoute(7));
+  assert_eq!(PostRoute(2024, "hi".to_string()).format(), "/posts/2024/hi");
+}
@@ -0,0 +1,40 @@
+use ruled_router::prelude::*;
+use ruled_router::routes;
+
+// 不带任何 use 的模块中展开，生成的代码只使用绝对路径
+mod app {
+  ruled_router::routes! {
+    pub enum AppRoute {
+      "/" => Home,
+      "/blog" => Blog {
+        "/:slug" => Post(pub slug: String),
+      },
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct SearchQuery {
+  q: Option<String>,
+}
+
+routes! {
+  enum Root {
+    "/search" => Search(#[query] query: SearchQuery,),
+    "/docs" => Docs {
+      "/guide" => Guide {
+        "/:chapter" => Chapter(chapter: u32),
+      },
+    },
+  }
+}
+
+fn main() {
+  let post = app::Post { slug: "hello".to_string() };
+  assert_eq!(post.format(), "/hello");
+  let _: Option<app::BlogMatch> = Some(app::BlogMatch::Post(post));
+
+  let route = Root::try_parse("/docs/guide/3").unwrap();
+  assert_eq!(route.format(), "/docs/guide/3");
+  let _ = Search { query: SearchQuery::default() };
+}
@@ -0,0 +1,65 @@
+//! url 集成测试
+//!
+//! 测试 RouterData::to_url 与基础 URL 的拼接，需要启用 url feature
+
+#![cfg(feature = "url")]
+
+use ruled_router::prelude::*;
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq, Default, QueryDerive)]
+struct PageQuery {
+  page: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, RouterData)]
+#[router(pattern = "/users/:id")]
+struct UserRoute {
+  id: u32,
+  #[query]
+  query: PageQuery,
+}
+
+#[test]
+fn test_to_url_base_without_path() {
+  let route = UserRoute {
+    id: 42,
+    query: PageQuery { page: Some(2) },
+  };
+
+  let base = Url::parse("https://example.com").unwrap();
+  assert_eq!(route.to_url(&base).as_str(), "https://example.com/users/42?page=2");
+
+  let base = Url::parse("https://example.com/").unwrap();
+  assert_eq!(route.to_url(&base).as_str(), "https://example.com/users/42?page=2");
+}
+
+#[test]
+fn test_to_url_base_with_path() {
+  let route = UserRoute {
+    id: 42,
+    query: PageQuery::default(),
+  };
+
+  let base = Url::parse("https://example.com/app").unwrap();
+  assert_eq!(route.to_url(&base).as_str(), "https://example.com/app/users/42");
+
+  let base = Url::parse("https://example.com/app/").unwrap();
+  assert_eq!(route.to_url(&base).as_str(), "https://example.com/app/users/42");
+}
+
+#[test]
+fn test_to_url_merges_existing_query() {
+  let route = UserRoute {
+    id: 7,
+    query: PageQuery { page: Some(3) },
+  };
+
+  let base = Url::parse("https://example.com/app/?lang=en#top").unwrap();
+  let url = route.to_url(&base);
+  assert_eq!(url.as_str(), "https://example.com/app/users/7?lang=en&page=3#top");
+
+  // 解析回来仍然得到同一个路由
+  let path_and_query = format!("{}?{}", url.path().trim_start_matches("/app"), url.query().unwrap());
+  assert_eq!(UserRoute::parse(&path_and_query).unwrap(), route);
+}
@@ -570,33 +570,30 @@ impl App {
     let closure = Closure::wrap(Box::new(move |_event: Event| {
       console::log_1(&"URL变化被检测到".into());
 
-      // 获取当前URL路径
-      if let Some(window) = window() {
-        let location = window.location();
-        if let Ok(pathname) = location.pathname() {
-          console::log_1(&format!("当前URL路径: {pathname}").into());
-
-          // 尝试解析当前URL
-          if let Ok(app_router) = AppRouter::parse(&pathname) {
-            if let Some(new_route) = app_router.sub_router {
-              // 检查是否与内存状态不一致
-              let current_state_route = app_state.borrow().get_route().clone();
-              if current_state_route != new_route {
-                console::log_1(&format!("状态不一致，更新内存状态: {new_route:?}").into());
-
-                // 更新内存状态
-                app_state.borrow_mut().set_route(new_route.clone());
-
-                // 更新页面内容
-                if let Err(e) = render_route(&new_route, &content_element) {
-                  console::log_1(&format!("渲染错误: {e:?}").into());
-                }
-                if let Err(e) = update_route_json(&new_route) {
-                  console::log_1(&format!("更新路由JSON错误: {e:?}").into());
-                }
-              } else {
-                console::log_1(&"状态一致，无需更新".into());
+      // 获取当前URL（路径 + 查询字符串），否则后退时会丢失搜索条件
+      if let Ok(url) = helpers::current_url() {
+        console::log_1(&format!("当前URL: {url}").into());
+
+        // 尝试解析当前URL
+        if let Ok(app_router) = AppRouter::parse(&url) {
+          if let Some(new_route) = app_router.sub_router {
+            // 检查是否与内存状态不一致
+            let current_state_route = app_state.borrow().get_route().clone();
+            if current_state_route != new_route {
+              console::log_1(&format!("状态不一致，更新内存状态: {new_route:?}").into());
+
+              // 更新内存状态
+              app_state.borrow_mut().set_route(new_route.clone());
+
+              // 更新页面内容
+              if let Err(e) = render_route(&new_route, &content_element) {
+                console::log_1(&format!("渲染错误: {e:?}").into());
               }
+              if let Err(e) = update_route_json(&new_route) {
+                console::log_1(&format!("更新路由JSON错误: {e:?}").into());
+              }
+            } else {
+              console::log_1(&"状态一致，无需更新".into());
             }
           }
         }
@@ -1043,3 +1040,25 @@ pub fn main() -> Result<(), JsValue> {
 
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_popstate_url_keeps_search_query() {
+    // popstate 回调解析的是 pathname + search，查询参数必须保留下来
+    let url = "/search?q=rust&page=2";
+    let app_router = AppRouter::parse(url).unwrap();
+
+    let expected = AppRoute::Search(SearchRoute {
+      query: SearchQuery {
+        q: Some("rust".to_string()),
+        page: Some(2),
+        tags: vec![],
+      },
+    });
+    assert_eq!(app_router.sub_router, Some(expected.clone()));
+    assert_eq!(AppRouter::parse(&expected.format()).unwrap().sub_router, Some(expected));
+  }
+}